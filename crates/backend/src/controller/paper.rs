@@ -1,60 +1,135 @@
 use crate::use_case::paper::PaperUseCase;
 use domain::{
     paper::{
-        entity::dto,
+        entity::{dto, model::{Paper, PaperChanges}},
+        listing_service::PaperFilter,
+        network_export::{Network, NetworkEdge, NetworkNode},
         repository::PaperRepository,
+        search::{count_matches, snippet},
+        search_service::SearchService,
+        service::PaperServiceError,
+        AuthorCitationMetrics, CitationFormat, CitationStyle, CoAuthorRole, GraphFormat, PaperId, PaperSort, PaperStatus, PaperStatusTransition,
     },
+    review::repository::ReviewRepository,
     user::{repository::UserRepository, UserPrimaryKey}
 };
 use interface::paper::*;
+use std::str::FromStr;
 
-pub struct PaperController<A: PaperRepository, U: UserRepository, UK: UserPrimaryKey> {
-    use_case: PaperUseCase<A, U, UK>,
+pub struct PaperController<A: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> {
+    use_case: PaperUseCase<A, U, V, UK>,
 }
 
-impl<R: PaperRepository, U: UserRepository, UK: UserPrimaryKey> PaperController<R, U, UK>
+impl<R: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> PaperController<R, U, V, UK>
 where
     R: PaperRepository<UserPrimaryKey = UK> + Clone,
     U: UserRepository<PrimaryKey = UK> + Clone,
+    V: ReviewRepository<UserPrimaryKey = UK> + Clone,
 {
-    pub fn new(repository: R, user_repository: U) -> Self {
+    pub fn new(repository: R, user_repository: U, review_repository: V, admin: Option<UK>) -> Self {
         Self {
-            use_case: PaperUseCase::new(repository, user_repository)
+            use_case: PaperUseCase::new(repository, user_repository, review_repository, admin)
         }
     }
 
-    pub fn fetch(&self, paper_id: &str) -> Result<dto::Paper, String> {
-        let (paper, paper_id) = self.use_case.get_paper(paper_id)?;
+    pub fn fetch(&self, paper_id: &str, caller: UK) -> Result<dto::Paper, PaperServiceError> {
+        let (paper, paper_id) = self.use_case.get_paper(paper_id, &caller)?;
 
-        dto::Paper::from_model(paper, self.use_case.get_user_repository(), paper_id)
-            .ok_or_else(|| format!("Failed to convert Paper model to DTO for paper: {}", paper_id))
+        self.visible_dto(paper, paper_id, &caller)
+            .ok_or_else(|| PaperServiceError::Rejected(format!("Failed to convert Paper model to DTO for paper: {}", paper_id)))
     }
 
-    pub fn fetch_as_author(&self, paper_id: &str, caller: UK) -> Result<dto::Paper, String> {
-        // Check author permissions to retrieve models
+    // A referenced paper the caller may read, built from its metadata alone
+    fn fetch_referenced(&self, paper_id: &PaperId, caller: &UK) -> Option<dto::Paper> {
+        let paper = self.use_case.get_paper_metadata(paper_id, caller)?;
+
+        self.visible_dto(paper, *paper_id, caller)
+    }
+
+    fn visible_dto(&self, paper: Paper<UK>, paper_id: PaperId, caller: &UK) -> Option<dto::Paper> {
+        let hides_authors = self.use_case.hides_authors_from(&paper, caller);
+        let shows_tombstone = paper.status.is_tombstone() && self.use_case.role_of(&paper, caller).is_none();
+
+        let paper = dto::Paper::from_model(paper, self.use_case.get_user_repository(), paper_id)?;
+
+        // Authors of a paper under double-blind review are masked here so no client can see them
+        let paper = if hides_authors { paper.masked() } else { paper };
+        Some(if shows_tombstone { paper.tombstone() } else { paper })
+    }
+
+    pub fn fetch_as_author(&self, paper_id: &str, caller: UK) -> Result<dto::Paper, PaperServiceError> {
+        // Check read permissions to retrieve models
         let (paper, paper_id) = self.use_case.get_paper_as_author(paper_id, caller)?;
+        let hides_authors = self.use_case.hides_authors_from(&paper, &caller);
 
-        dto::Paper::from_model(paper, self.use_case.get_user_repository(), paper_id)
-            .ok_or_else(|| format!("Failed to convert Paper model to DTO for paper: {}", paper_id))
+        let paper = dto::Paper::from_model(paper, self.use_case.get_user_repository(), paper_id)
+            .ok_or_else(|| PaperServiceError::Rejected(format!("Failed to convert Paper model to DTO for paper: {}", paper_id)))?;
+
+        // Reviewers may read drafts under review, but not who wrote them
+        Ok(if hides_authors { paper.masked() } else { paper })
+    }
+
+    // The PDF of a paper is sent as a reference, and its content read in ranges here
+    pub fn read_blob(&self, hash: &str, offset: u64, len: u64) -> Result<Vec<u8>, PaperServiceError> {
+        self.use_case.read_blob(hash, offset, len)
     }
 
     pub fn fetch_all_summaries(&self) -> Vec<PaperSummaryDto> {
         let summaries = self.use_case.get_all_paper_summaries();
 
-        summaries.into_iter().map(|(id, title, author, author_name)| {
-            let author_id = self.use_case.get_user_repository().get_user_id(&author);
-            let lead_author_id = match author_id {
-                Some(id) => id.to_string(),
-                None => format!("p_{}", author),
-            };
+        summaries.into_iter().map(|summary| self.summary_dto(summary)).collect()
+    }
+
+    pub fn list_papers(&self, filter: PaperListFilter, sort: PaperSort, page_size: u32, cursor: Option<&str>) -> Result<PaperListDto, PaperServiceError> {
+        let filter = PaperFilter {
+            status: filter.status.unwrap_or(PaperStatus::Published),
+            category: filter.category,
+            tag: filter.tag,
+            author: filter.author_id,
+            created_from: filter.created_from,
+            created_until: filter.created_until,
+        };
+        let page = self.use_case.list_papers(filter, sort, page_size, cursor)?;
+
+        Ok(PaperListDto {
+            papers: page.papers.iter()
+                .filter_map(|paper_id| self.use_case.get_paper_summary(paper_id))
+                .map(|summary| self.summary_dto(summary))
+                .collect(),
+            next_cursor: page.next.map(|cursor| cursor.to_string()),
+        })
+    }
 
-            PaperSummaryDto {
+    fn summary_dto(&self, (id, title, author, author_name): (String, String, UK, String)) -> PaperSummaryDto {
+        let hides_author = PaperId::from_str(&id).is_ok_and(|id| self.use_case.hides_authors(&id));
+        if hides_author {
+            let masked = dto::Paper::anonymous_author();
+            return PaperSummaryDto {
                 id,
                 title,
-                lead_author_id,
-                lead_author_name: author_name,
-            }
-        }).collect()
+                lead_author_id: masked.0,
+                lead_author_name: masked.1.to_string(),
+            };
+        }
+
+        PaperSummaryDto {
+            id,
+            title,
+            lead_author_id: self.user_id_string(&author),
+            lead_author_name: author_name,
+        }
+    }
+
+    pub fn fetch_revisions(&self, paper_id: &str, caller: UK) -> Result<Vec<PaperRevisionDto>, PaperServiceError> {
+        let revisions = self.use_case.get_revisions(paper_id, &caller)?;
+
+        Ok(revisions.into_iter().map(|paper| PaperRevisionDto {
+            id: paper.id.to_string(),
+            version: paper.id.version(),
+            title: paper.title.to_string(),
+            status: paper.status,
+            updated_at: paper.updated_at,
+        }).collect())
     }
 
     pub fn create_draft(&mut self, user: UK) -> Result<String, String> {
@@ -62,14 +137,286 @@ where
         Ok(draft_id.to_string())
     }
 
-    pub fn update(&mut self, user: UK, paper_dto: dto::Paper) -> Result<(), String> {
+    pub fn update(&mut self, user: UK, paper_dto: dto::Paper) -> Result<u64, PaperServiceError> {
         let model = paper_dto.into_model(self.use_case.get_user_repository())
-            .ok_or("Failed to convert paper to model")?;
+            .ok_or_else(|| PaperServiceError::Rejected("Failed to convert paper to model".to_string()))?;
 
         self.use_case.update_paper(user, &paper_dto.id, model)
     }
 
-    pub fn delete(&mut self, user: UK, paper_id: &str) -> Result<(), String> {
+    pub fn patch(&mut self, user: UK, patch: PaperPatch) -> Result<u64, PaperServiceError> {
+        let changes = PaperChanges {
+            title: patch.title,
+            ab: patch.ab,
+            text: patch.text,
+            tags: patch.tags,
+            categories: patch.categories,
+            references: patch.references,
+            cover_image: patch.cover_image,
+            pdf: None,
+        };
+
+        self.use_case.patch_paper(user, &patch.id, changes, patch.updated_at)
+    }
+
+    pub fn transition_status(&mut self, user: UK, paper_id: &str, transition: PaperStatusTransition) -> Result<PaperStatus, PaperServiceError> {
+        self.use_case.transition_paper_status(user, paper_id, transition)
+    }
+
+    pub fn publish_revision(&mut self, user: UK, paper_dto: dto::Paper) -> Result<String, PaperServiceError> {
+        let model = paper_dto.into_model(self.use_case.get_user_repository())
+            .ok_or_else(|| PaperServiceError::Rejected("Failed to convert paper to model".to_string()))?;
+
+        self.use_case.publish_revision(user, &paper_dto.id, model)
+            .map(|id| id.to_string())
+    }
+
+    pub fn invite_co_author(&mut self, user: UK, paper_id: &str, invitee_id: &str, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+        self.use_case.invite_co_author(user, paper_id, invitee_id, role)
+    }
+
+    pub fn accept_invitation(&mut self, user: UK, paper_id: &str) -> Result<(), PaperServiceError> {
+        self.use_case.accept_invitation(user, paper_id)
+    }
+
+    pub fn decline_invitation(&mut self, user: UK, paper_id: &str) -> Result<(), PaperServiceError> {
+        self.use_case.decline_invitation(user, paper_id)
+    }
+
+    pub fn remove_co_author(&mut self, user: UK, paper_id: &str, co_author_id: &str) -> Result<(), PaperServiceError> {
+        self.use_case.remove_co_author(user, paper_id, co_author_id)
+    }
+
+    pub fn set_co_author_role(&mut self, user: UK, paper_id: &str, co_author_id: &str, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+        self.use_case.set_co_author_role(user, paper_id, co_author_id, role)
+    }
+
+    pub fn fetch_co_authors(&self, user: UK, paper_id: &str) -> Result<CoAuthorsDto, PaperServiceError> {
+        let (co_authors, invitations) = self.use_case.get_co_author_roles(user, paper_id)?;
+
+        let to_dto = |users: Vec<(UK, CoAuthorRole)>| users.into_iter()
+            .filter_map(|(key, role)| {
+                let name = self.use_case.get_user_repository().get_name_by_primary_key(&key)?;
+                Some(CoAuthorDto { id: self.user_id_string(&key), name: name.to_string(), role })
+            })
+            .collect();
+
+        Ok(CoAuthorsDto {
+            co_authors: to_dto(co_authors),
+            invitations: to_dto(invitations),
+        })
+    }
+
+    pub fn fetch_invitations(&self, user: UK) -> Vec<CoAuthorInvitationDto> {
+        self.use_case.get_invitations(user).into_iter().filter_map(|paper| {
+            let lead_author_name = self.use_case.get_user_repository().get_name_by_primary_key(&paper.lead_author)?;
+
+            Some(CoAuthorInvitationDto {
+                paper_id: paper.id.to_string(),
+                title: paper.title.to_string(),
+                lead_author_id: self.user_id_string(&paper.lead_author),
+                lead_author_name: lead_author_name.to_string(),
+            })
+        }).collect()
+    }
+
+    fn user_id_string(&self, user: &UK) -> String {
+        match self.use_case.get_user_repository().get_user_id(user) {
+            Some(id) => id.to_string(),
+            None => format!("p_{}", user),
+        }
+    }
+
+    pub fn delete(&mut self, user: UK, paper_id: &str) -> Result<(), PaperServiceError> {
         self.use_case.delete_paper(user, paper_id)
     }
+
+    pub fn export_citation(&self, paper_id: &str, format: CitationFormat, caller: UK) -> Result<String, PaperServiceError> {
+        let paper = self.fetch(paper_id, caller)?;
+
+        Ok(format.export_paper(&paper))
+    }
+
+    pub fn export_references(&self, paper_id: &str, format: CitationFormat, caller: UK) -> Result<String, PaperServiceError> {
+        let paper = self.fetch(paper_id, caller)?;
+
+        // Referenced papers the caller cannot read are exported as unavailable
+        Ok(format.export_references(&paper.references, |id| self.fetch_referenced(id, &caller)))
+    }
+
+    pub fn format_citation(&self, paper_id: &str, style: CitationStyle, caller: UK) -> Result<String, PaperServiceError> {
+        let paper = self.fetch(paper_id, caller)?;
+
+        Ok(style.format_paper(&paper))
+    }
+
+    pub fn format_references(&self, paper_id: &str, style: CitationStyle, caller: UK) -> Result<Vec<String>, PaperServiceError> {
+        let paper = self.fetch(paper_id, caller)?;
+
+        // Referenced papers the caller cannot read are rendered as unavailable
+        Ok(style.format_references(&paper.references, |id| self.fetch_referenced(id, &caller)))
+    }
+
+    pub fn export_citation_network(&self, format: GraphFormat, center: Option<&str>, hops: u8, cursor: Option<&str>, caller: UK) -> Result<NetworkPageDto, PaperServiceError> {
+        let page = self.use_case.get_citation_network(center.map(|id| (id, hops)), cursor, &caller)?;
+
+        let nodes: Vec<_> = page.papers.iter().map(|paper| {
+            let mut data = vec![("label", paper.title.to_string())];
+            if !paper.categories.is_empty() {
+                let categories: Vec<_> = paper.categories.iter().map(|category| category.to_string()).collect();
+                data.push(("category", categories.join(", ")));
+            }
+            data.push(("status", paper.status.to_string()));
+            NetworkNode { id: paper.id.first_version().to_string(), data }
+        }).collect();
+        let edges: Vec<_> = page.citations.iter().map(|(citing, cited)| NetworkEdge {
+            source: citing.to_string(),
+            target: cited.to_string(),
+            data: Vec::new(),
+        }).collect();
+
+        Ok(NetworkPageDto {
+            document: format.export_network(&Network::CITATIONS, &nodes, &edges, cursor.is_none(), page.next.is_none()),
+            next_cursor: page.next.map(|id| id.to_string()),
+        })
+    }
+
+    pub fn export_co_authorship_network(&self, format: GraphFormat, center: Option<&str>, hops: u8, cursor: Option<&str>, caller: UK) -> Result<NetworkPageDto, PaperServiceError> {
+        let page = self.use_case.get_co_authorship_network(center.map(|id| (id, hops)), cursor, &caller)?;
+
+        let nodes: Vec<_> = page.authors.iter().map(|author| {
+            let name = self.use_case.get_user_repository().get_name_by_primary_key(author);
+            NetworkNode {
+                id: self.user_id_string(author),
+                data: name.map(|name| ("label", name.to_string())).into_iter().collect(),
+            }
+        }).collect();
+        let edges: Vec<_> = page.collaborations.iter().map(|(author, co_author, paper)| NetworkEdge {
+            source: self.user_id_string(author),
+            target: self.user_id_string(co_author),
+            data: vec![("paper", paper.to_string())],
+        }).collect();
+
+        Ok(NetworkPageDto {
+            document: format.export_network(&Network::CO_AUTHORSHIP, &nodes, &edges, cursor.is_none(), page.next.is_none()),
+            next_cursor: page.next.map(|id| id.to_string()),
+        })
+    }
+
+    pub fn search_papers(&self, query: &str, filters: SearchFilters, page: u32) -> Result<SearchResultsDto, PaperServiceError> {
+        let page = self.use_case.search_papers(
+            query,
+            &filters.categories,
+            &filters.tags,
+            filters.lead_author_id.as_deref(),
+            page,
+        )?;
+        let terms = SearchService::<R>::query_terms(query);
+
+        let results = page.papers.into_iter().map(|(paper, score)| {
+            let (lead_author_id, lead_author_name) = if self.use_case.hides_authors(&paper.id) {
+                let masked = dto::Paper::anonymous_author();
+                (masked.0, masked.1.to_string())
+            } else {
+                let name = self.use_case.get_user_repository().get_name_by_primary_key(&paper.lead_author);
+                (self.user_id_string(&paper.lead_author), name.map(|name| name.to_string()).unwrap_or_default())
+            };
+
+            // The snippet comes from whichever of the abstract and content mentions the query most,
+            // the abstract winning ties
+            let text = self.use_case.get_paper_text(&paper.id).unwrap_or_default();
+            let snippet = [text.as_str(), paper.ab.as_str()].into_iter()
+                .filter(|source| !source.trim().is_empty())
+                .max_by_key(|source| count_matches(source, &terms))
+                .map(|source| snippet(source, &terms))
+                .unwrap_or_default();
+
+            SearchResultDto {
+                id: paper.id.to_string(),
+                title: paper.title.to_string(),
+                lead_author_id,
+                lead_author_name,
+                score,
+                snippet,
+            }
+        }).collect();
+
+        Ok(SearchResultsDto { results, has_more: page.has_more })
+    }
+
+    pub fn import_references(&self, format: CitationFormat, document: &str, caller: UK) -> ImportedReferencesDto {
+        // Only papers the caller can read are recognized, so drafts of others stay hidden
+        let imported = format.import_references(document, |id| self.use_case.get_paper(&id.to_string(), &caller).is_ok());
+
+        ImportedReferencesDto {
+            references: imported.references,
+            errors: imported.errors.into_iter().map(|error| ReferenceImportErrorDto {
+                index: error.index as u32,
+                key: error.key,
+                message: error.message,
+            }).collect(),
+        }
+    }
+
+    pub fn fetch_citation_count(&self, paper_id: &str, caller: UK) -> Result<u32, PaperServiceError> {
+        self.use_case.get_citation_count(paper_id, &caller)
+    }
+
+    pub fn fetch_citing_papers(&self, paper_id: &str, hops: u8, caller: UK) -> Result<Vec<CitingPaperDto>, PaperServiceError> {
+        let citing = self.use_case.get_cited_by_within(paper_id, hops, &caller)?;
+
+        let mut citing: Vec<_> = citing.into_iter().map(|(id, hops, contexts)| CitingPaperDto {
+            id: id.to_string(),
+            title: self.use_case.get_title(&id).map(|title| title.to_string()).unwrap_or_default(),
+            hops,
+            contexts,
+        }).collect();
+        citing.sort_by_key(|paper| paper.hops);
+        Ok(citing)
+    }
+
+    pub fn find_citation_cycle(&self, paper_id: &str, caller: UK) -> Result<Option<Vec<String>>, PaperServiceError> {
+        let cycle = self.use_case.find_citation_cycle(paper_id, &caller)?;
+
+        Ok(cycle.map(|papers| papers.iter().map(|id| id.to_string()).collect()))
+    }
+
+    pub fn fetch_author_metrics(&self, user_id: &str) -> Result<AuthorCitationMetrics, PaperServiceError> {
+        self.use_case.get_author_metrics(user_id)
+    }
+
+    pub fn fetch_paper_broken_references(&self, paper_id: &str, caller: UK) -> Result<BrokenReferencesDto, PaperServiceError> {
+        let (paper, paper_id) = self.use_case.get_paper(paper_id, &caller)?;
+        // Tombstones show no references to those who cannot see the full paper
+        let shows_tombstone = paper.status.is_tombstone() && self.use_case.role_of(&paper, &caller).is_none();
+        let references = if shows_tombstone { Vec::new() } else { self.use_case.get_broken_references(&paper, &caller) };
+
+        Ok(BrokenReferencesDto {
+            paper_id: paper_id.to_string(),
+            title: paper.title.to_string(),
+            updated_at: paper.updated_at,
+            references,
+        })
+    }
+
+    pub fn fetch_broken_references(&self, caller: UK) -> Vec<BrokenReferencesDto> {
+        self.use_case.get_papers_with_broken_references(&caller).into_iter().map(|(paper, references)| BrokenReferencesDto {
+            paper_id: paper.id.to_string(),
+            title: paper.title.to_string(),
+            updated_at: paper.updated_at,
+            references,
+        }).collect()
+    }
+
+    pub fn fix_broken_references(&mut self, user: UK, paper_id: &str, updated_at: u64) -> Result<u64, PaperServiceError> {
+        self.use_case.fix_broken_references(user, paper_id, updated_at)
+    }
+
+    pub fn rebuild_citations(&mut self, cursor: Option<&str>) -> Result<CitationRebuildDto, PaperServiceError> {
+        let (corrected, next) = self.use_case.rebuild_citations(cursor)?;
+        Ok(CitationRebuildDto {
+            corrected: corrected as u64,
+            next_cursor: next.map(|id| id.to_string()),
+        })
+    }
 }
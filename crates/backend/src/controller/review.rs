@@ -0,0 +1,65 @@
+use crate::use_case::review::ReviewUseCase;
+use domain::{
+    paper::{repository::PaperRepository, PaperStatus},
+    review::{entity::dto, repository::ReviewRepository, EditorialDecision, ReviewMode},
+    user::{repository::UserRepository, UserPrimaryKey},
+};
+use interface::review::*;
+
+pub struct ReviewController<V: ReviewRepository, P: PaperRepository, U: UserRepository, UK: UserPrimaryKey> {
+    use_case: ReviewUseCase<V, P, U, UK>,
+}
+
+impl<V: ReviewRepository, P: PaperRepository, U: UserRepository, UK: UserPrimaryKey> ReviewController<V, P, U, UK>
+where
+    V: ReviewRepository<UserPrimaryKey = UK> + Clone,
+    P: PaperRepository<UserPrimaryKey = UK> + Clone,
+    U: UserRepository<PrimaryKey = UK> + Clone,
+{
+    pub fn new(repository: V, paper_repository: P, user_repository: U) -> Self {
+        Self {
+            use_case: ReviewUseCase::new(repository, paper_repository, user_repository)
+        }
+    }
+
+    pub fn fetch(&self, caller: UK, paper_id: &str) -> Result<dto::Review, String> {
+        let (review, hides_reviewers) = self.use_case.get_review(caller, paper_id)?;
+
+        let review = dto::Review::from_model(review, self.use_case.get_user_repository())
+            .ok_or_else(|| format!("Failed to convert Review model to DTO for paper: {}", paper_id))?;
+
+        // Reviewers of a double-blind review are masked here so no client can see them
+        Ok(if hides_reviewers { review.masked() } else { review })
+    }
+
+    pub fn fetch_assigned(&self, caller: UK) -> Vec<dto::Review> {
+        self.use_case.get_assigned_reviews(caller)
+            .into_iter()
+            .filter_map(|review| dto::Review::from_model(review, self.use_case.get_user_repository()))
+            .collect()
+    }
+
+    pub fn set_mode(&mut self, caller: UK, paper_id: &str, mode: ReviewMode) -> Result<(), String> {
+        self.use_case.set_mode(caller, paper_id, mode)
+    }
+
+    pub fn assign_reviewer(&mut self, caller: UK, paper_id: &str, reviewer_id: &str) -> Result<(), String> {
+        self.use_case.assign_reviewer(caller, paper_id, reviewer_id)
+    }
+
+    pub fn submit_report(&mut self, caller: UK, paper_id: &str, request: SubmitReviewReportRequest) -> Result<(), String> {
+        self.use_case.submit_report(caller, paper_id, request.score, request.recommendation, request.comments)
+    }
+
+    pub fn decide(&mut self, caller: UK, paper_id: &str, decision: EditorialDecision) -> Result<PaperStatus, String> {
+        self.use_case.decide(caller, paper_id, decision)
+    }
+
+    pub fn add_editor(&mut self, user_id: &str) -> Result<bool, String> {
+        self.use_case.add_editor(user_id)
+    }
+
+    pub fn remove_editor(&mut self, user_id: &str) -> Result<bool, String> {
+        self.use_case.remove_editor(user_id)
+    }
+}
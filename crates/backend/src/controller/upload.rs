@@ -0,0 +1,43 @@
+use crate::use_case::{paper::PaperUseCase, upload::UploadUseCase};
+use domain::{
+    paper::{repository::{PaperRepository, UploadRepository}, service::PaperServiceError},
+    review::repository::ReviewRepository,
+    user::{repository::UserRepository, UserPrimaryKey},
+};
+
+pub struct UploadController<L: UploadRepository, A: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> {
+    use_case: UploadUseCase<L, UK>,
+    paper_use_case: PaperUseCase<A, U, V, UK>,
+}
+
+impl<L: UploadRepository, R: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> UploadController<L, R, U, V, UK>
+where
+    L: UploadRepository<UserPrimaryKey = UK> + Clone,
+    R: PaperRepository<UserPrimaryKey = UK> + Clone,
+    U: UserRepository<PrimaryKey = UK> + Clone,
+    V: ReviewRepository<UserPrimaryKey = UK> + Clone,
+{
+    pub fn new(repository: L, paper_repository: R, user_repository: U, review_repository: V, admin: Option<UK>) -> Self {
+        Self {
+            use_case: UploadUseCase::new(repository),
+            paper_use_case: PaperUseCase::new(paper_repository, user_repository, review_repository, admin),
+        }
+    }
+
+    pub fn begin(&mut self, user: UK, name: &str, size: u64, sha256: &str) -> Result<u64, PaperServiceError> {
+        Ok(self.use_case.begin_upload(user, name, size, sha256)?)
+    }
+
+    pub fn append(&mut self, user: UK, upload_id: u64, offset: u64, bytes: Vec<u8>) -> Result<u64, PaperServiceError> {
+        Ok(self.use_case.upload_chunk(&user, upload_id, offset, bytes)?)
+    }
+
+    // The upload is closed whether or not the paper takes the file, so a failed commit
+    // does not keep its bytes counted against the open uploads
+    pub fn commit(&mut self, user: UK, upload_id: u64, paper_id: &str, updated_at: u64) -> Result<u64, PaperServiceError> {
+        let file = self.use_case.get_uploaded_file(&user, upload_id)?;
+        let result = self.paper_use_case.attach_pdf(user, paper_id, file, updated_at);
+        self.use_case.close_upload(upload_id);
+        result
+    }
+}
@@ -6,28 +6,30 @@ use domain::{
         UserPrimaryKey,
         UserPrincipal
     },
-    paper::repository::PaperRepository
+    paper::repository::PaperRepository,
+    review::repository::ReviewRepository,
 };
 use ic_cdk::api::caller;
 
-pub struct UserController<R: UserRepository, P: PaperRepository, K: UserPrimaryKey> {
-    use_case: UserUseCase<R, P, K>,
+pub struct UserController<R: UserRepository, P: PaperRepository, V: ReviewRepository, K: UserPrimaryKey> {
+    use_case: UserUseCase<R, P, V, K>,
 }
 
-impl<R, P, K> UserController<R, P, K>
+impl<R, P, V, K> UserController<R, P, V, K>
 where
     R: UserRepository<PrimaryKey = UserPrincipal> + Clone,
     P: PaperRepository + Clone,
+    V: ReviewRepository<UserPrimaryKey = UserPrincipal> + Clone,
     K: UserPrimaryKey,
 {
-    pub fn new(repository: R, paper_repository: P) -> Self {
+    pub fn new(repository: R, paper_repository: P, review_repository: V) -> Self {
         Self {
-            use_case: UserUseCase::new(repository, paper_repository),
+            use_case: UserUseCase::new(repository, paper_repository, review_repository),
         }
     }
 
     pub fn fetch(&self, user_id: &str) -> Result<dto::User, String> {
-        let user = self.use_case.get_user(user_id)?;
+        let user = self.use_case.get_public_user(user_id)?;
         Ok(dto::User::from_model(user, self.use_case.get_paper_repository()))
     }
 
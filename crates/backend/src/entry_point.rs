@@ -3,6 +3,11 @@
 use domain::{
     paper::{
         entity::dto::Paper,
+        service::PaperServiceError,
+        value_object::*,
+    },
+    review::{
+        entity::dto::Review,
         value_object::*,
     },
     user::{
@@ -12,9 +17,12 @@ use domain::{
 };
 use interface::user::*;
 use interface::paper::*;
+use interface::review::*;
 
 mod guards;
 mod paper;
+mod review;
+mod upload;
 mod user;
 
 ic_cdk::export_candid!();
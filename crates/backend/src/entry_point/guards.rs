@@ -1,6 +1,7 @@
-use ic_cdk::api::caller;
+use ic_cdk::api::{caller, is_controller};
 use candid::Principal;
 use crate::infrastructure::user::repository::{StableUserRepository, UserRepository};
+use domain::UserPrincipal;
 
 pub fn caller_is_not_anonymous() -> Result<(), String> {
     match caller() != Principal::anonymous() {
@@ -16,3 +17,15 @@ pub fn caller_is_user() -> Result<(), String> {
         false => Err("Caller is not a user".to_string()),
     }
 }
+
+pub fn caller_is_controller() -> Result<(), String> {
+    match is_controller(&caller()) {
+        true => Ok(()),
+        false => Err("Caller is not a controller".to_string()),
+    }
+}
+
+/// Returns the caller if it is a controller of the canister, which acts as an administrator
+pub fn caller_as_admin() -> Option<UserPrincipal> {
+    is_controller(&caller()).then(|| caller().into())
+}
@@ -4,36 +4,49 @@ use crate::{
     controller::paper::PaperController,
     infrastructure::{
         paper::repository::StablePaperRepository,
+        review::repository::StableReviewRepository,
         user::repository::StableUserRepository,
     },
 };
-use domain::{paper::entity::dto::Paper, UserPrincipal};
+use domain::{
+    paper::{entity::dto::Paper, service::PaperServiceError, AuthorCitationMetrics, CitationFormat, CitationStyle, CoAuthorRole, GraphFormat, PaperSort, PaperStatus, PaperStatusTransition},
+    UserPrincipal,
+};
 use ic_cdk::api::caller;
 use ic_cdk_macros::*;
 use interface::paper::*;
-use super::guards::{caller_is_user, caller_is_not_anonymous};
+use super::guards::{caller_as_admin, caller_is_controller, caller_is_user, caller_is_not_anonymous};
 
-fn controller() -> PaperController<StablePaperRepository, StableUserRepository, UserPrincipal> {
-    PaperController::<StablePaperRepository, StableUserRepository, UserPrincipal>::new(
+fn controller() -> PaperController<StablePaperRepository, StableUserRepository, StableReviewRepository, UserPrincipal> {
+    PaperController::<StablePaperRepository, StableUserRepository, StableReviewRepository, UserPrincipal>::new(
         StablePaperRepository::new(),
-        StableUserRepository::new()
+        StableUserRepository::new(),
+        StableReviewRepository::new(),
+        caller_as_admin(),
     )
 }
 
 #[query]
-fn fetch_paper(paper_id: String) -> Result<Paper, String> {
+fn fetch_paper(paper_id: String) -> Result<Paper, PaperServiceError> {
     let controller = controller();
 
-    controller.fetch(&paper_id)
+    controller.fetch(&paper_id, caller().into())
 }
 
 #[query]
-fn fetch_paper_as_author(paper_id: String) -> Result<Paper, String> {
+fn fetch_paper_as_author(paper_id: String) -> Result<Paper, PaperServiceError> {
     let controller = controller();
 
     controller.fetch_as_author(&paper_id, caller().into())
 }
 
+#[query]
+fn read_blob(hash: String, offset: u64, len: u64) -> Result<Vec<u8>, PaperServiceError> {
+    let controller = controller();
+
+    controller.read_blob(&hash, offset, len)
+}
+
 #[query]
 fn fetch_all_paper_summaries() -> Vec<PaperSummaryDto> {
     let controller = controller();
@@ -41,13 +54,181 @@ fn fetch_all_paper_summaries() -> Vec<PaperSummaryDto> {
     controller.fetch_all_summaries()
 }
 
+#[query]
+fn list_papers(filter: PaperListFilter, sort: PaperSort, page_size: u32, cursor: Option<String>) -> Result<PaperListDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.list_papers(filter, sort, page_size, cursor.as_deref())
+}
+
+#[query]
+fn fetch_paper_revisions(paper_id: String) -> Result<Vec<PaperRevisionDto>, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_revisions(&paper_id, caller().into())
+}
+
+#[query]
+fn export_citation(paper_id: String, format: CitationFormat) -> Result<String, PaperServiceError> {
+    let controller = controller();
+
+    controller.export_citation(&paper_id, format, caller().into())
+}
+
+#[query]
+fn export_references(paper_id: String, format: CitationFormat) -> Result<String, PaperServiceError> {
+    let controller = controller();
+
+    controller.export_references(&paper_id, format, caller().into())
+}
+
+#[query]
+fn format_citation(paper_id: String, style: CitationStyle) -> Result<String, PaperServiceError> {
+    let controller = controller();
+
+    controller.format_citation(&paper_id, style, caller().into())
+}
+
+#[query]
+fn format_references(paper_id: String, style: CitationStyle) -> Result<Vec<String>, PaperServiceError> {
+    let controller = controller();
+
+    controller.format_references(&paper_id, style, caller().into())
+}
+
+#[query]
+fn export_citation_network(format: GraphFormat, paper_id: Option<String>, hops: u8, cursor: Option<String>) -> Result<NetworkPageDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.export_citation_network(format, paper_id.as_deref(), hops, cursor.as_deref(), caller().into())
+}
+
+#[query]
+fn export_co_authorship_network(format: GraphFormat, paper_id: Option<String>, hops: u8, cursor: Option<String>) -> Result<NetworkPageDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.export_co_authorship_network(format, paper_id.as_deref(), hops, cursor.as_deref(), caller().into())
+}
+
+#[query]
+fn search_papers(query: String, filters: SearchFilters, page: u32) -> Result<SearchResultsDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.search_papers(&query, filters, page)
+}
+
+#[query(guard = "caller_is_user")]
+fn import_references(format: CitationFormat, document: String) -> ImportedReferencesDto {
+    let controller = controller();
+
+    controller.import_references(format, &document, caller().into())
+}
+
+#[query]
+fn fetch_citation_count(paper_id: String) -> Result<u32, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_citation_count(&paper_id, caller().into())
+}
+
+#[query]
+fn fetch_citing_papers(paper_id: String, hops: u8) -> Result<Vec<CitingPaperDto>, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_citing_papers(&paper_id, hops, caller().into())
+}
+
+#[query]
+fn find_citation_cycle(paper_id: String) -> Result<Option<Vec<String>>, PaperServiceError> {
+    let controller = controller();
+
+    controller.find_citation_cycle(&paper_id, caller().into())
+}
+
+#[query]
+fn fetch_author_citation_metrics(user_id: String) -> Result<AuthorCitationMetrics, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_author_metrics(&user_id)
+}
+
 #[update(guard = "caller_is_user")]
-fn update_paper(paper: Paper) -> Result<(), String> {
+fn update_paper(paper: Paper) -> Result<u64, PaperServiceError> {
     let mut controller = controller();
 
     controller.update(caller().into(), paper)
 }
 
+#[update(guard = "caller_is_user")]
+fn patch_paper(patch: PaperPatch) -> Result<u64, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.patch(caller().into(), patch)
+}
+
+#[update(guard = "caller_is_user")]
+fn transition_paper_status(paper_id: String, transition: PaperStatusTransition) -> Result<PaperStatus, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.transition_status(caller().into(), &paper_id, transition)
+}
+
+#[update(guard = "caller_is_user")]
+fn publish_paper_revision(paper: Paper) -> Result<String, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.publish_revision(caller().into(), paper)
+}
+
+#[query(guard = "caller_is_user")]
+fn fetch_co_authors(paper_id: String) -> Result<CoAuthorsDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_co_authors(caller().into(), &paper_id)
+}
+
+#[query(guard = "caller_is_user")]
+fn fetch_caller_invitations() -> Vec<CoAuthorInvitationDto> {
+    let controller = controller();
+
+    controller.fetch_invitations(caller().into())
+}
+
+#[update(guard = "caller_is_user")]
+fn invite_co_author(paper_id: String, user_id: String, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+    let mut controller = controller();
+
+    controller.invite_co_author(caller().into(), &paper_id, &user_id, role)
+}
+
+#[update(guard = "caller_is_user")]
+fn accept_co_author_invitation(paper_id: String) -> Result<(), PaperServiceError> {
+    let mut controller = controller();
+
+    controller.accept_invitation(caller().into(), &paper_id)
+}
+
+#[update(guard = "caller_is_user")]
+fn decline_co_author_invitation(paper_id: String) -> Result<(), PaperServiceError> {
+    let mut controller = controller();
+
+    controller.decline_invitation(caller().into(), &paper_id)
+}
+
+#[update(guard = "caller_is_user")]
+fn remove_co_author(paper_id: String, user_id: String) -> Result<(), PaperServiceError> {
+    let mut controller = controller();
+
+    controller.remove_co_author(caller().into(), &paper_id, &user_id)
+}
+
+#[update(guard = "caller_is_user")]
+fn set_co_author_role(paper_id: String, user_id: String, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+    let mut controller = controller();
+
+    controller.set_co_author_role(caller().into(), &paper_id, &user_id, role)
+}
+
 #[update(guard = "caller_is_user")]
 fn create_draft() -> String {
     let mut controller = controller();
@@ -56,8 +237,36 @@ fn create_draft() -> String {
 }
 
 #[update(guard = "caller_is_user")]
-fn delete_paper(paper_id: String) -> Result<(), String> {
+fn delete_paper(paper_id: String) -> Result<(), PaperServiceError> {
     let mut controller = controller();
 
     controller.delete(caller().into(), &paper_id)
 }
+
+#[query]
+fn fetch_paper_broken_references(paper_id: String) -> Result<BrokenReferencesDto, PaperServiceError> {
+    let controller = controller();
+
+    controller.fetch_paper_broken_references(&paper_id, caller().into())
+}
+
+#[query(guard = "caller_is_user")]
+fn fetch_broken_references() -> Vec<BrokenReferencesDto> {
+    let controller = controller();
+
+    controller.fetch_broken_references(caller().into())
+}
+
+#[update(guard = "caller_is_user")]
+fn fix_broken_references(paper_id: String, updated_at: u64) -> Result<u64, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.fix_broken_references(caller().into(), &paper_id, updated_at)
+}
+
+#[update(guard = "caller_is_controller")]
+fn rebuild_citation_index(cursor: Option<String>) -> Result<CitationRebuildDto, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.rebuild_citations(cursor.as_deref())
+}
@@ -0,0 +1,83 @@
+#![allow(unused)]
+
+use crate::{
+    controller::review::ReviewController,
+    infrastructure::{
+        paper::repository::StablePaperRepository,
+        review::repository::StableReviewRepository,
+        user::repository::StableUserRepository,
+    },
+};
+use domain::{
+    paper::PaperStatus,
+    review::{entity::dto::Review, EditorialDecision, ReviewMode},
+    UserPrincipal,
+};
+use ic_cdk::api::caller;
+use ic_cdk_macros::*;
+use interface::review::*;
+use super::guards::{caller_is_controller, caller_is_user};
+
+fn controller() -> ReviewController<StableReviewRepository, StablePaperRepository, StableUserRepository, UserPrincipal> {
+    ReviewController::<StableReviewRepository, StablePaperRepository, StableUserRepository, UserPrincipal>::new(
+        StableReviewRepository::new(),
+        StablePaperRepository::new(),
+        StableUserRepository::new()
+    )
+}
+
+#[query(guard = "caller_is_user")]
+fn fetch_review(paper_id: String) -> Result<Review, String> {
+    let controller = controller();
+
+    controller.fetch(caller().into(), &paper_id)
+}
+
+#[query(guard = "caller_is_user")]
+fn fetch_assigned_reviews() -> Vec<Review> {
+    let controller = controller();
+
+    controller.fetch_assigned(caller().into())
+}
+
+#[update(guard = "caller_is_user")]
+fn set_review_mode(paper_id: String, mode: ReviewMode) -> Result<(), String> {
+    let mut controller = controller();
+
+    controller.set_mode(caller().into(), &paper_id, mode)
+}
+
+#[update(guard = "caller_is_user")]
+fn assign_reviewer(paper_id: String, reviewer_id: String) -> Result<(), String> {
+    let mut controller = controller();
+
+    controller.assign_reviewer(caller().into(), &paper_id, &reviewer_id)
+}
+
+#[update(guard = "caller_is_user")]
+fn submit_review_report(paper_id: String, report: SubmitReviewReportRequest) -> Result<(), String> {
+    let mut controller = controller();
+
+    controller.submit_report(caller().into(), &paper_id, report)
+}
+
+#[update(guard = "caller_is_user")]
+fn decide_review(paper_id: String, decision: EditorialDecision) -> Result<PaperStatus, String> {
+    let mut controller = controller();
+
+    controller.decide(caller().into(), &paper_id, decision)
+}
+
+#[update(guard = "caller_is_controller")]
+fn add_editor(user_id: String) -> Result<bool, String> {
+    let mut controller = controller();
+
+    controller.add_editor(&user_id)
+}
+
+#[update(guard = "caller_is_controller")]
+fn remove_editor(user_id: String) -> Result<bool, String> {
+    let mut controller = controller();
+
+    controller.remove_editor(&user_id)
+}
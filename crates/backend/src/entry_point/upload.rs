@@ -0,0 +1,45 @@
+#![allow(unused)]
+
+use crate::{
+    controller::upload::UploadController,
+    infrastructure::{
+        paper::{repository::StablePaperRepository, upload_repository::StableUploadRepository},
+        review::repository::StableReviewRepository,
+        user::repository::StableUserRepository,
+    },
+};
+use domain::{paper::service::PaperServiceError, UserPrincipal};
+use ic_cdk::api::caller;
+use ic_cdk_macros::*;
+use super::guards::{caller_as_admin, caller_is_user};
+
+fn controller() -> UploadController<StableUploadRepository, StablePaperRepository, StableUserRepository, StableReviewRepository, UserPrincipal> {
+    UploadController::<StableUploadRepository, StablePaperRepository, StableUserRepository, StableReviewRepository, UserPrincipal>::new(
+        StableUploadRepository::new(),
+        StablePaperRepository::new(),
+        StableUserRepository::new(),
+        StableReviewRepository::new(),
+        caller_as_admin(),
+    )
+}
+
+#[update(guard = "caller_is_user")]
+fn begin_upload(name: String, size: u64, sha256: String) -> Result<u64, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.begin(caller().into(), &name, size, &sha256)
+}
+
+#[update(guard = "caller_is_user")]
+fn upload_chunk(upload_id: u64, offset: u64, bytes: Vec<u8>) -> Result<u64, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.append(caller().into(), upload_id, offset, bytes)
+}
+
+#[update(guard = "caller_is_user")]
+fn commit_upload(upload_id: u64, paper_id: String, updated_at: u64) -> Result<u64, PaperServiceError> {
+    let mut controller = controller();
+
+    controller.commit(caller().into(), upload_id, &paper_id, updated_at)
+}
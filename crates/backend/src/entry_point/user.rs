@@ -1,6 +1,10 @@
 use crate::{
     controller::user::UserController,
-    infrastructure::{user::repository::StableUserRepository, paper::repository::StablePaperRepository},
+    infrastructure::{
+        user::repository::StableUserRepository,
+        paper::repository::StablePaperRepository,
+        review::repository::StableReviewRepository,
+    },
 };
 use domain::user::{
     entity::dto::User,
@@ -10,10 +14,11 @@ use ic_cdk_macros::*;
 use interface::user::*;
 use super::guards::{caller_is_user, caller_is_not_anonymous};
 
-fn controller() -> UserController<StableUserRepository, StablePaperRepository, UserPrincipal> {
-    UserController::<StableUserRepository, StablePaperRepository, UserPrincipal>::new(
+fn controller() -> UserController<StableUserRepository, StablePaperRepository, StableReviewRepository, UserPrincipal> {
+    UserController::<StableUserRepository, StablePaperRepository, StableReviewRepository, UserPrincipal>::new(
         StableUserRepository::new(),
         StablePaperRepository::new(),
+        StableReviewRepository::new(),
     )
 }
 
@@ -1,23 +1,35 @@
 use domain::{
     paper::{
-        entity::dao::PaperDao,
-        value_object::PaperId,
-    }, user::{
+        entity::{dao::PaperDao, model::PaperMetadata},
+        index::PaperIndexKey,
+        search::{PostingKey, SearchDocument, TOKENIZER_VERSION},
+        upload::{UploadChunkKey, UploadSession},
+        upload_service::UploadService,
+        value_object::{content::{BlobFile, StoredFile}, AuthorCitationMetrics, BlobChunkKey, BlobEntry, BlobHash, PaperCategory, PaperId, PaperStatus},
+    }, review::entity::dao::ReviewDao,
+    user::{
         entity::dao::UserDao,
         value_object::{UserId, UserPrincipal, UserName}
     }, PaperTitle
 };
 use crate::{
-    infrastructure::paper::repository::PaperCounter,
+    infrastructure::paper::{
+        blob_store::{collect_blobs, store_blob},
+        repository::{index_listing, index_revision, index_search, insert_contents, read_paper, PaperCounter},
+        sort_index::SortIndex,
+        upload_repository::StableUploadRepository,
+    },
     log::Log,
 };
 use ic_stable_structures::{StableBTreeMap, StableLog};
 use no_panic::no_panic;
 use serde::{Serialize, Deserialize};
-use std::{cell::RefCell, collections::{HashMap, HashSet, BTreeMap}, sync::Mutex};
+use std::{cell::RefCell, collections::{HashMap, HashSet, BTreeMap, BTreeSet}, ops::Bound, sync::Mutex, time::Duration};
+use util::time::now;
 
 mod memory;
 pub mod paper;
+pub mod review;
 pub mod user;
 mod pre_upgrade;
 mod post_upgrade;
@@ -35,12 +47,118 @@ pub struct State {
     paper_counter: Mutex<PaperCounter>,
     paper_titles: BTreeMap<PaperId, PaperTitle>,
     paper_lead_authors: BTreeMap<PaperId, UserPrincipal>,
+    /// Number of papers in the full-text index
+    #[serde(default)]
+    search_document_count: u64,
+    /// Weighted number of words of all the papers in the full-text index
+    #[serde(default)]
+    search_total_length: u64,
+    /// Version of the tokenizer the full-text index was built with
+    #[serde(default)]
+    search_tokenizer: u32,
+    /// Whether the sort indexes list the latest revision of each paper under its status
+    #[serde(default)]
+    papers_listed_by_status: bool,
+    /// Whether the indexes of statuses, co-authors, citations and listings are kept in
+    /// stable memory, rather than in the heap as they were at first
+    #[serde(default)]
+    paper_indexes_in_stable_memory: bool,
+    /// Migration under way, and the paper its next batch starts after
+    #[serde(default)]
+    migration: Option<(Migration, Option<PaperId>)>,
+    /// Citation metrics of each user, computed from `author_citations`
+    #[serde(default)]
+    author_metrics: HashMap<UserPrincipal, AuthorCitationMetrics>,
+    /// Whether papers stored before their contents were kept apart have been migrated
+    #[serde(default)]
+    paper_contents_split: bool,
+    /// Whether PDFs stored before the blob store existed have been moved to it
+    #[serde(default)]
+    paper_files_in_blobs: bool,
+    /// Blobs left without references, to be collected
+    #[serde(default)]
+    unreferenced_blobs: BTreeSet<BlobHash>,
+    #[serde(default)]
+    co_author_invitations: HashMap<UserPrincipal, BTreeSet<PaperId>>,
+    #[serde(default)]
+    editors: HashSet<UserPrincipal>,
+    #[serde(default)]
+    review_assignments: HashMap<UserPrincipal, BTreeSet<PaperId>>,
+    /// Files being uploaded in chunks
+    #[serde(default)]
+    uploads: BTreeMap<u64, UploadSession<UserPrincipal>>,
+    /// Identifier of the last upload begun
+    #[serde(default)]
+    upload_counter: u64,
+    /// Total size of the uploads in `uploads`, as declared when they began
+    #[serde(default)]
+    upload_bytes: u64,
     #[serde(skip, default = "init_stable_log")]
     pub log: StableLog<Log, Memory, Memory>,
     #[serde(skip, default = "init_users")]
     users: StableBTreeMap<UserPrincipal, UserDao, Memory>,
     #[serde(skip, default = "init_papers")]
     papers: StableBTreeMap<PaperId, PaperDao<UserPrincipal>, Memory>,
+    #[serde(skip, default = "init_reviews")]
+    reviews: StableBTreeMap<PaperId, ReviewDao<UserPrincipal>, Memory>,
+    #[serde(skip, default = "init_paper_statuses")]
+    paper_statuses: StableBTreeMap<PaperId, PaperStatus, Memory>,
+    /// Co-authors of each paper, by their position in its list of co-authors
+    #[serde(skip, default = "init_paper_co_authors")]
+    paper_co_authors: StableBTreeMap<(PaperId, u32), UserPrincipal, Memory>,
+    /// Papers each paper references, filed under it
+    #[serde(skip, default = "init_paper_references")]
+    paper_references: StableBTreeMap<PaperIndexKey<PaperId>, (), Memory>,
+    /// Papers citing each paper, filed under it
+    #[serde(skip, default = "init_paper_citing")]
+    paper_citing: StableBTreeMap<PaperIndexKey<PaperId>, (), Memory>,
+    /// Position of each paper in listings sorted by creation date
+    #[serde(skip, default = "init_papers_by_created")]
+    papers_by_created: SortIndex,
+    /// Position of each paper in listings sorted by update date
+    #[serde(skip, default = "init_papers_by_updated")]
+    papers_by_updated: SortIndex,
+    /// Position of each paper in listings sorted by citation count
+    #[serde(skip, default = "init_papers_by_citations")]
+    papers_by_citations: SortIndex,
+    /// Position of each paper in listings sorted by title
+    #[serde(skip, default = "init_papers_by_title")]
+    papers_by_title: SortIndex,
+    /// Citation count of each paper each user wrote a revision of, by first revision, filed under the user
+    #[serde(skip, default = "init_author_citations")]
+    author_citations: StableBTreeMap<PaperIndexKey<UserPrincipal>, u32, Memory>,
+    /// Weighted number of occurrences of each word in each paper containing it
+    #[serde(skip, default = "init_search_postings")]
+    search_postings: StableBTreeMap<PostingKey, u32, Memory>,
+    /// Weighted number of words of each paper in the full-text index
+    #[serde(skip, default = "init_search_lengths")]
+    search_lengths: StableBTreeMap<PaperId, u32, Memory>,
+    /// Words indexed for each paper, to drop its postings when it changes
+    #[serde(skip, default = "init_search_documents")]
+    search_documents: StableBTreeMap<PaperId, SearchDocument, Memory>,
+    /// Papers each user is the lead author or a co-author of
+    #[serde(skip, default = "init_papers_by_author")]
+    papers_by_author: StableBTreeMap<PaperIndexKey<UserPrincipal>, (), Memory>,
+    #[serde(skip, default = "init_papers_by_category")]
+    papers_by_category: StableBTreeMap<PaperIndexKey<PaperCategory>, (), Memory>,
+    /// Papers with each tag, in lowercase
+    #[serde(skip, default = "init_papers_by_tag")]
+    papers_by_tag: StableBTreeMap<PaperIndexKey<String>, (), Memory>,
+    /// Text content of each paper that has some
+    #[serde(skip, default = "init_paper_texts")]
+    paper_texts: StableBTreeMap<PaperId, String, Memory>,
+    /// PDF of each paper that has one
+    #[serde(skip, default = "init_paper_files")]
+    paper_files: StableBTreeMap<PaperId, StoredFile, Memory>,
+    /// Chunks received for each upload, by offset
+    #[serde(skip, default = "init_upload_chunks")]
+    upload_chunks: StableBTreeMap<UploadChunkKey, Vec<u8>, Memory>,
+    /// Files referenced by papers, by the SHA-256 digest of their content
+    #[serde(skip, default = "init_blobs")]
+    blobs: StableBTreeMap<BlobHash, BlobEntry, Memory>,
+    /// Content of each blob, in chunks
+    #[serde(skip, default = "init_blob_chunks")]
+    blob_chunks: StableBTreeMap<BlobChunkKey, Vec<u8>, Memory>,
 }
 
 impl Default for State {
@@ -53,13 +171,237 @@ impl Default for State {
             paper_counter: Mutex::new(PaperCounter::default()),
             paper_titles: BTreeMap::new(),
             paper_lead_authors: BTreeMap::new(),
+            search_document_count: 0,
+            search_total_length: 0,
+            search_tokenizer: TOKENIZER_VERSION,
+            papers_listed_by_status: true,
+            paper_indexes_in_stable_memory: true,
+            migration: None,
+            author_metrics: HashMap::new(),
+            paper_contents_split: true,
+            paper_files_in_blobs: true,
+            unreferenced_blobs: BTreeSet::new(),
+            co_author_invitations: HashMap::new(),
+            editors: HashSet::new(),
+            review_assignments: HashMap::new(),
+            uploads: BTreeMap::new(),
+            upload_counter: 0,
+            upload_bytes: 0,
             log: init_stable_log(),
             users: init_users(),
             papers: init_papers(),
+            reviews: init_reviews(),
+            paper_statuses: init_paper_statuses(),
+            paper_co_authors: init_paper_co_authors(),
+            paper_references: init_paper_references(),
+            paper_citing: init_paper_citing(),
+            papers_by_created: init_papers_by_created(),
+            papers_by_updated: init_papers_by_updated(),
+            papers_by_citations: init_papers_by_citations(),
+            papers_by_title: init_papers_by_title(),
+            author_citations: init_author_citations(),
+            search_postings: init_search_postings(),
+            search_lengths: init_search_lengths(),
+            search_documents: init_search_documents(),
+            papers_by_author: init_papers_by_author(),
+            papers_by_category: init_papers_by_category(),
+            papers_by_tag: init_papers_by_tag(),
+            paper_texts: init_paper_texts(),
+            paper_files: init_paper_files(),
+            upload_chunks: init_upload_chunks(),
+            blobs: init_blobs(),
+            blob_chunks: init_blob_chunks(),
         }
     }
 }
 
+/// Migration of the stored papers, run a batch at a time after an upgrade so that no
+/// single message exceeds the instruction limit
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum Migration {
+    /// Moves the contents that papers stored before `PaperDao` V6 hold inline to their own maps
+    SplitPaperContents,
+    /// Moves the PDFs stored before the blob store existed to it
+    MoveFilesToBlobs,
+    /// Rebuilds indexes that are missing or were built by an older tokenizer
+    BackfillIndexes,
+}
+
+impl Migration {
+    const ALL: [Migration; 3] = [Migration::SplitPaperContents, Migration::MoveFilesToBlobs, Migration::BackfillIndexes];
+}
+
+/// Number of papers migrated by each batch
+const MIGRATION_BATCH_SIZE: usize = 100;
+/// Number of files moved to the blob store by each batch, as they may be large
+const FILE_MIGRATION_BATCH_SIZE: usize = 4;
+
+impl State {
+    /// Starts the first migration the state needs, unless one is already under way.
+    ///
+    /// Papers are readable throughout: metadata and contents are read from whichever
+    /// `PaperDao` version a paper is stored in, and raw PDFs are read as they are.
+    fn start_migration(&mut self) {
+        if self.migration.is_none() {
+            self.migration = self.next_migration(None).map(|migration| (migration, None));
+        }
+    }
+
+    /// Returns the first migration after `done` the state needs
+    fn next_migration(&self, done: Option<Migration>) -> Option<Migration> {
+        Migration::ALL.into_iter()
+            .filter(|migration| done.is_none_or(|done| *migration > done))
+            .find(|migration| match migration {
+                Migration::SplitPaperContents => !self.paper_contents_split,
+                Migration::MoveFilesToBlobs => !self.paper_files_in_blobs,
+                Migration::BackfillIndexes => !self.indexes_complete(),
+            })
+    }
+
+    /// Runs a batch of the migration under way, moving on to the next migration once it
+    /// is done, and returns whether any batch remains
+    fn migrate_batch(&mut self) -> bool {
+        let Some((migration, after)) = self.migration else {
+            return false;
+        };
+        let next = match migration {
+            Migration::SplitPaperContents => self.split_paper_contents(after, MIGRATION_BATCH_SIZE),
+            Migration::MoveFilesToBlobs => self.move_files_to_blobs(after, FILE_MIGRATION_BATCH_SIZE),
+            Migration::BackfillIndexes => self.backfill_indexes(after, MIGRATION_BATCH_SIZE),
+        };
+        self.migration = match next {
+            Some(next) => Some((migration, Some(next))),
+            None => self.next_migration(Some(migration)).map(|migration| (migration, None)),
+        };
+        self.migration.is_some()
+    }
+
+    /// Moves the contents of the papers after `after` that hold them inline to their own
+    /// maps, leaving only their metadata in `papers`, and returns the last paper moved
+    /// unless none remain.
+    fn split_paper_contents(&mut self, after: Option<PaperId>, limit: usize) -> Option<PaperId> {
+        let ids = self.paper_ids_after(after, limit);
+        for id in &ids {
+            let Some(dao) = self.papers.get(id) else {
+                continue;
+            };
+            if let (metadata, Some(content)) = PaperMetadata::from_dao(dao, *id) {
+                insert_contents(self, *id, content);
+                self.papers.insert(*id, metadata.into());
+            }
+        }
+
+        let next = ids.last().copied().filter(|_| ids.len() == limit);
+        self.paper_contents_split = next.is_none();
+        next
+    }
+
+    /// Moves the raw PDFs of the papers after `after` to the blob store, and returns the
+    /// last paper looked at unless none remain.
+    fn move_files_to_blobs(&mut self, after: Option<PaperId>, limit: usize) -> Option<PaperId> {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let ids: Vec<_> = self.paper_files.range((start, Bound::Unbounded))
+            .take(limit)
+            .map(|(id, file)| (id, matches!(file, StoredFile::Raw(_))))
+            .collect();
+        for (id, _) in ids.iter().filter(|(_, raw)| *raw) {
+            if let Some(StoredFile::Raw(file)) = self.paper_files.get(id) {
+                let blob = BlobFile::of(&file);
+                store_blob(self, &blob, &file.content);
+                self.paper_files.insert(*id, StoredFile::Blob(blob));
+            }
+        }
+
+        let next = ids.last().map(|(id, _)| *id).filter(|_| ids.len() == limit);
+        self.paper_files_in_blobs = next.is_none();
+        next
+    }
+
+    /// Whether every index covers every paper, with the full-text index built by the
+    /// current tokenizer
+    fn indexes_complete(&self) -> bool {
+        let papers = self.papers.len();
+        self.paper_statuses.len() == papers
+            && self.search_lengths.len() == papers
+            && self.search_tokenizer == TOKENIZER_VERSION
+            && self.papers_by_created.len() == papers
+            && self.papers_listed_by_status
+            && self.paper_indexes_in_stable_memory
+            && (papers == 0 || !self.author_citations.is_empty())
+    }
+
+    /// Rebuilds the indexes of the papers after `after`, and returns the last paper
+    /// indexed unless none remain.
+    fn backfill_indexes(&mut self, after: Option<PaperId>, limit: usize) -> Option<PaperId> {
+        // Indexing borrows the whole state, so papers are looked up one at a time
+        let ids = self.paper_ids_after(after, limit);
+        for id in &ids {
+            let Some(paper) = read_paper(self, id) else {
+                continue;
+            };
+            index_revision(self, *id, &paper);
+            index_search(self, *id, &paper);
+            index_listing(self, *id, &paper.into_parts().0);
+        }
+
+        let next = ids.last().copied().filter(|_| ids.len() == limit);
+        if next.is_none() {
+            self.search_tokenizer = TOKENIZER_VERSION;
+            self.papers_listed_by_status = true;
+            self.paper_indexes_in_stable_memory = true;
+        }
+        next
+    }
+
+    /// Returns up to `limit` paper revisions after `after`
+    fn paper_ids_after(&self, after: Option<PaperId>, limit: usize) -> Vec<PaperId> {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        self.paper_lead_authors.range((start, Bound::Unbounded))
+            .take(limit)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+/// Schedules the next batch of the migration under way, if any.
+///
+/// Timers do not survive upgrades, so this runs after each upgrade.
+pub fn schedule_migration() {
+    if STATE.with_borrow(|s| s.migration.is_some()) {
+        ic_cdk_timers::set_timer(Duration::ZERO, || {
+            if STATE.with_borrow_mut(|s| s.migrate_batch()) {
+                schedule_migration();
+            }
+        });
+    }
+}
+
+/// Interval between collections of unreferenced blobs
+const BLOB_COLLECTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Number of blobs removed by each collection, to bound its cost
+const BLOB_COLLECTION_LIMIT: usize = 16;
+
+/// Schedules the periodic removal of blobs no paper references.
+///
+/// Timers do not survive upgrades, so this runs on both install and upgrade.
+pub fn schedule_blob_collection() {
+    ic_cdk_timers::set_timer_interval(BLOB_COLLECTION_INTERVAL, || {
+        STATE.with_borrow_mut(|s| collect_blobs(s, BLOB_COLLECTION_LIMIT));
+    });
+}
+
+/// Interval between sweeps of expired uploads
+const UPLOAD_SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Schedules the periodic removal of expired uploads and their chunks.
+///
+/// Timers do not survive upgrades, so this runs on both install and upgrade.
+pub fn schedule_upload_sweep() {
+    ic_cdk_timers::set_timer_interval(UPLOAD_SWEEP_INTERVAL, || {
+        UploadService::new(StableUploadRepository::new()).remove_expired(now(), UploadService::<StableUploadRepository>::SWEEP_LIMIT);
+    });
+}
+
 thread_local! {
     pub static STATE: RefCell<State> = RefCell::new(State::default());
 }
@@ -1,13 +1,17 @@
 use domain::{
     paper::{
         entity::dao::PaperDao,
-        value_object::PaperId,
-    }, user::{
+        index::PaperIndexKey,
+        search::{PostingKey, SearchDocument},
+        upload::UploadChunkKey,
+        value_object::{content::StoredFile, BlobChunkKey, BlobEntry, BlobHash, PaperCategory, PaperId, PaperStatus},
+    }, review::entity::dao::ReviewDao,
+    user::{
         entity::dao::UserDao,
         value_object::UserPrincipal,
     },
 };
-use crate::log::Log;
+use crate::{infrastructure::paper::sort_index::SortIndex, log::Log};
 use ic_cdk::api::print;
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
@@ -20,6 +24,32 @@ const LOG_INDEX: MemoryId = MemoryId::new(1);
 const LOG_DATA: MemoryId = MemoryId::new(2);
 const USERS: MemoryId = MemoryId::new(3);
 const PAPERS: MemoryId = MemoryId::new(4);
+const REVIEWS: MemoryId = MemoryId::new(5);
+const SEARCH_POSTINGS: MemoryId = MemoryId::new(6);
+const SEARCH_DOCUMENTS: MemoryId = MemoryId::new(7);
+const PAPERS_BY_AUTHOR: MemoryId = MemoryId::new(8);
+const PAPERS_BY_CATEGORY: MemoryId = MemoryId::new(9);
+const PAPERS_BY_TAG: MemoryId = MemoryId::new(10);
+// Memory 11 held an index of papers by creation month, which `PAPERS` already is; it is not reused
+const PAPER_TEXTS: MemoryId = MemoryId::new(12);
+const PAPER_FILES: MemoryId = MemoryId::new(13);
+const UPLOAD_CHUNKS: MemoryId = MemoryId::new(14);
+const BLOBS: MemoryId = MemoryId::new(15);
+const BLOB_CHUNKS: MemoryId = MemoryId::new(16);
+const SEARCH_LENGTHS: MemoryId = MemoryId::new(17);
+const PAPER_STATUSES: MemoryId = MemoryId::new(18);
+const PAPER_CO_AUTHORS: MemoryId = MemoryId::new(19);
+const PAPER_REFERENCES: MemoryId = MemoryId::new(20);
+const PAPER_CITING: MemoryId = MemoryId::new(21);
+const PAPERS_BY_CREATED_KEYS: MemoryId = MemoryId::new(22);
+const PAPERS_BY_CREATED_LISTED: MemoryId = MemoryId::new(23);
+const PAPERS_BY_UPDATED_KEYS: MemoryId = MemoryId::new(24);
+const PAPERS_BY_UPDATED_LISTED: MemoryId = MemoryId::new(25);
+const PAPERS_BY_CITATIONS_KEYS: MemoryId = MemoryId::new(26);
+const PAPERS_BY_CITATIONS_LISTED: MemoryId = MemoryId::new(27);
+const PAPERS_BY_TITLE_KEYS: MemoryId = MemoryId::new(28);
+const PAPERS_BY_TITLE_LISTED: MemoryId = MemoryId::new(29);
+const AUTHOR_CITATIONS: MemoryId = MemoryId::new(30);
 
 pub(super) type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -58,3 +88,133 @@ pub(super) fn init_papers() -> StableBTreeMap<PaperId, PaperDao<UserPrincipal>,
         MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS)),
     )
 }
+
+pub(super) fn init_reviews() -> StableBTreeMap<PaperId, ReviewDao<UserPrincipal>, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(REVIEWS)),
+    )
+}
+
+pub(super) fn init_search_postings() -> StableBTreeMap<PostingKey, u32, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(SEARCH_POSTINGS)),
+    )
+}
+
+pub(super) fn init_search_lengths() -> StableBTreeMap<PaperId, u32, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(SEARCH_LENGTHS)),
+    )
+}
+
+pub(super) fn init_search_documents() -> StableBTreeMap<PaperId, SearchDocument, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(SEARCH_DOCUMENTS)),
+    )
+}
+
+pub(super) fn init_papers_by_author() -> StableBTreeMap<PaperIndexKey<UserPrincipal>, (), Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_AUTHOR)),
+    )
+}
+
+pub(super) fn init_papers_by_category() -> StableBTreeMap<PaperIndexKey<PaperCategory>, (), Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_CATEGORY)),
+    )
+}
+
+pub(super) fn init_papers_by_tag() -> StableBTreeMap<PaperIndexKey<String>, (), Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_TAG)),
+    )
+}
+
+pub(super) fn init_paper_texts() -> StableBTreeMap<PaperId, String, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_TEXTS)),
+    )
+}
+
+pub(super) fn init_paper_files() -> StableBTreeMap<PaperId, StoredFile, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_FILES)),
+    )
+}
+
+pub(super) fn init_upload_chunks() -> StableBTreeMap<UploadChunkKey, Vec<u8>, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(UPLOAD_CHUNKS)),
+    )
+}
+
+pub(super) fn init_blobs() -> StableBTreeMap<BlobHash, BlobEntry, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(BLOBS)),
+    )
+}
+
+pub(super) fn init_blob_chunks() -> StableBTreeMap<BlobChunkKey, Vec<u8>, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(BLOB_CHUNKS)),
+    )
+}
+
+pub(super) fn init_paper_statuses() -> StableBTreeMap<PaperId, PaperStatus, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_STATUSES)),
+    )
+}
+
+pub(super) fn init_paper_co_authors() -> StableBTreeMap<(PaperId, u32), UserPrincipal, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_CO_AUTHORS)),
+    )
+}
+
+pub(super) fn init_paper_references() -> StableBTreeMap<PaperIndexKey<PaperId>, (), Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_REFERENCES)),
+    )
+}
+
+pub(super) fn init_paper_citing() -> StableBTreeMap<PaperIndexKey<PaperId>, (), Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPER_CITING)),
+    )
+}
+
+pub(super) fn init_papers_by_created() -> SortIndex {
+    SortIndex::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_CREATED_KEYS)),
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_CREATED_LISTED)),
+    )
+}
+
+pub(super) fn init_papers_by_updated() -> SortIndex {
+    SortIndex::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_UPDATED_KEYS)),
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_UPDATED_LISTED)),
+    )
+}
+
+pub(super) fn init_papers_by_citations() -> SortIndex {
+    SortIndex::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_CITATIONS_KEYS)),
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_CITATIONS_LISTED)),
+    )
+}
+
+pub(super) fn init_papers_by_title() -> SortIndex {
+    SortIndex::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_TITLE_KEYS)),
+        MEMORY_MANAGER.with(|m| m.borrow().get(PAPERS_BY_TITLE_LISTED)),
+    )
+}
+
+pub(super) fn init_author_citations() -> StableBTreeMap<PaperIndexKey<UserPrincipal>, u32, Memory> {
+    StableBTreeMap::init(
+        MEMORY_MANAGER.with(|m| m.borrow().get(AUTHOR_CITATIONS)),
+    )
+}
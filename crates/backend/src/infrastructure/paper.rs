@@ -1 +1,4 @@
+pub(crate) mod blob_store;
 pub mod repository;
+pub(crate) mod sort_index;
+pub mod upload_repository;
@@ -0,0 +1,201 @@
+use crate::infrastructure::State;
+use domain::paper::{value_object::content::BlobFile, BlobChunkKey, BlobEntry, BlobHash};
+
+/// Stores the content of a file in the blob store and adds a reference to it.
+///
+/// A file identical to a stored one is not written again; the stored blob gains a reference.
+pub(crate) fn store_blob(s: &mut State, file: &BlobFile, content: &[u8]) {
+    if reference_blob(s, &file.hash) {
+        return;
+    }
+    for (index, chunk) in content.chunks(BlobEntry::CHUNK_SIZE).enumerate() {
+        s.blob_chunks.insert(BlobChunkKey { hash: file.hash, index: index as u32 }, chunk.to_vec());
+    }
+    s.blobs.insert(file.hash, BlobEntry { size: file.size, references: 1 });
+    s.unreferenced_blobs.remove(&file.hash);
+}
+
+/// Adds a reference to a stored blob, and returns false if no such blob is stored.
+pub(crate) fn reference_blob(s: &mut State, hash: &BlobHash) -> bool {
+    let Some(entry) = s.blobs.get(hash) else {
+        return false;
+    };
+    s.blobs.insert(*hash, BlobEntry { references: entry.references + 1, ..entry });
+    s.unreferenced_blobs.remove(hash);
+    true
+}
+
+/// Drops a reference to a blob, leaving it to be collected once it has none left.
+pub(crate) fn release_blob(s: &mut State, hash: &BlobHash) {
+    let Some(entry) = s.blobs.get(hash) else {
+        return;
+    };
+    let references = entry.references.saturating_sub(1);
+    s.blobs.insert(*hash, BlobEntry { references, ..entry });
+    if references == 0 {
+        s.unreferenced_blobs.insert(*hash);
+    }
+}
+
+/// Reads up to `len` bytes of a blob from `offset`, walking only the chunks they are in.
+pub(crate) fn read_blob(s: &State, hash: &BlobHash, offset: u64, len: u64) -> Vec<u8> {
+    let chunk_size = BlobEntry::CHUNK_SIZE as u64;
+    let end = offset.saturating_add(len);
+    let first = BlobChunkKey { hash: *hash, index: (offset / chunk_size).try_into().unwrap_or(u32::MAX) };
+
+    let mut content = Vec::new();
+    for (key, chunk) in s.blob_chunks.range(first..=BlobChunkKey { hash: *hash, index: u32::MAX }) {
+        let start = key.index as u64 * chunk_size;
+        if start >= end {
+            break;
+        }
+        let from = offset.saturating_sub(start).min(chunk.len() as u64) as usize;
+        let to = (end - start).min(chunk.len() as u64) as usize;
+        content.extend_from_slice(&chunk[from..to]);
+    }
+    content
+}
+
+/// Removes up to `limit` blobs without references, with their chunks, and returns
+/// the number removed.
+pub(crate) fn collect_blobs(s: &mut State, limit: usize) -> usize {
+    let hashes: Vec<_> = s.unreferenced_blobs.iter().take(limit).copied().collect();
+    let mut removed = 0;
+    for hash in hashes {
+        s.unreferenced_blobs.remove(&hash);
+        // Blobs referenced again since they were released are kept
+        if s.blobs.get(&hash).is_some_and(|entry| entry.references > 0) {
+            continue;
+        }
+
+        let chunks: Vec<_> = s.blob_chunks.range(BlobChunkKey::range(hash)).map(|(key, _)| key).collect();
+        for key in chunks {
+            s.blob_chunks.remove(&key);
+        }
+        s.blobs.remove(&hash);
+        removed += 1;
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        paper::repository::{insert_contents, StablePaperRepository},
+        STATE,
+    };
+    use domain::paper::{repository::PaperRepository, value_object::content::{RawFile, StoredFile}, ContentFileSource, PaperContents, PaperId};
+
+    fn id(version: u16) -> PaperId {
+        PaperId::new(300, 1, version).unwrap()
+    }
+
+    fn pdf(content: &[u8]) -> PaperContents {
+        let file = RawFile { name: "paper.pdf".to_string(), content: content.to_vec() };
+        PaperContents::new(None, Some(ContentFileSource::Raw(file)))
+    }
+
+    // The contents as the repository reads them back, referencing the stored file
+    fn stored(s: &State, paper_id: PaperId) -> PaperContents {
+        let pdf = s.paper_files.get(&paper_id).map(|file| match file {
+            StoredFile::Blob(blob) => ContentFileSource::Blob(blob),
+            _ => panic!("expected a file in the blob store"),
+        });
+        PaperContents::new(None, pdf)
+    }
+
+    fn references(s: &State, content: &[u8]) -> Option<u32> {
+        s.blobs.get(&BlobHash::of(content)).map(|entry| entry.references)
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut s = State::default();
+        let content = vec![7; BlobEntry::CHUNK_SIZE + 1];
+        insert_contents(&mut s, id(1), pdf(&content));
+        insert_contents(&mut s, PaperId::new(300, 2, 1).unwrap(), pdf(&content));
+
+        assert_eq!(references(&s, &content), Some(2));
+        assert_eq!(s.blobs.len(), 1);
+        assert_eq!(s.blob_chunks.len(), 2);
+        let Some(ContentFileSource::Blob(blob)) = stored(&s, id(1)).pdf else { unreachable!() };
+        assert_eq!(read_blob(&s, &blob.hash, 0, blob.size), content);
+    }
+
+    #[test]
+    fn test_read_range() {
+        let mut s = State::default();
+        let content: Vec<u8> = (0..BlobEntry::CHUNK_SIZE * 2 + 10).map(|index| index as u8).collect();
+        insert_contents(&mut s, id(1), pdf(&content));
+        let hash = BlobHash::of(&content);
+
+        // Ranges within a chunk, across chunks, and past the end of the blob
+        let middle = BlobEntry::CHUNK_SIZE as u64 - 5;
+        assert_eq!(read_blob(&s, &hash, 3, 4), &content[3..7]);
+        assert_eq!(read_blob(&s, &hash, middle, 10), &content[middle as usize..middle as usize + 10]);
+        assert_eq!(read_blob(&s, &hash, content.len() as u64 - 4, 100), &content[content.len() - 4..]);
+        assert!(read_blob(&s, &hash, content.len() as u64, 100).is_empty());
+        assert!(read_blob(&s, &hash, 3, 0).is_empty());
+    }
+
+    #[test]
+    fn test_references_across_revisions() {
+        let mut s = State::default();
+        insert_contents(&mut s, id(1), pdf(b"first"));
+        // A revision starts from the contents of the paper, and saving it again keeps its file
+        let contents = stored(&s, id(1));
+        insert_contents(&mut s, id(2), contents.clone());
+        insert_contents(&mut s, id(2), contents);
+        assert_eq!(references(&s, b"first"), Some(2));
+
+        // Saving the same file again, as clients do, neither stores nor references it again
+        insert_contents(&mut s, id(2), pdf(b"first"));
+        assert_eq!(references(&s, b"first"), Some(2));
+
+        insert_contents(&mut s, id(2), pdf(b"second"));
+        assert_eq!(references(&s, b"first"), Some(1));
+        assert_eq!(references(&s, b"second"), Some(1));
+        assert!(s.unreferenced_blobs.is_empty());
+    }
+
+    #[test]
+    fn test_release_on_replace() {
+        let mut s = State::default();
+        insert_contents(&mut s, id(1), pdf(b"first"));
+        insert_contents(&mut s, id(1), pdf(b"second"));
+        assert_eq!(references(&s, b"first"), Some(0));
+        assert!(s.unreferenced_blobs.contains(&BlobHash::of(b"first")));
+
+        insert_contents(&mut s, id(1), PaperContents::default());
+        assert_eq!(references(&s, b"second"), Some(0));
+        assert_eq!(collect_blobs(&mut s, 10), 2);
+        assert!(s.blobs.is_empty());
+        assert!(s.blob_chunks.is_empty());
+    }
+
+    #[test]
+    fn test_release_on_remove() {
+        STATE.with_borrow_mut(|s| insert_contents(s, id(1), pdf(b"first")));
+        StablePaperRepository::new().remove(&id(1));
+
+        STATE.with_borrow_mut(|s| {
+            assert_eq!(references(s, b"first"), Some(0));
+            assert!(s.unreferenced_blobs.contains(&BlobHash::of(b"first")));
+        });
+    }
+
+    #[test]
+    fn test_collect_skips_referenced_again() {
+        let mut s = State::default();
+        insert_contents(&mut s, id(1), pdf(b"first"));
+        insert_contents(&mut s, id(1), PaperContents::default());
+        insert_contents(&mut s, id(2), pdf(b"first"));
+
+        assert_eq!(collect_blobs(&mut s, 10), 0);
+        assert!(s.unreferenced_blobs.is_empty());
+        assert_eq!(references(&s, b"first"), Some(1));
+        let Some(ContentFileSource::Blob(blob)) = stored(&s, id(2)).pdf else { unreachable!() };
+        assert_eq!(read_blob(&s, &blob.hash, 0, blob.size), b"first");
+    }
+}
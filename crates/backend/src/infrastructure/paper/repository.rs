@@ -1,15 +1,26 @@
-use crate::infrastructure::STATE;
+use crate::infrastructure::{
+    paper::{
+        blob_store::{read_blob, reference_blob, release_blob, store_blob},
+        sort_index::SortIndex,
+    },
+    Memory, State, STATE,
+};
 use candid::CandidType;
 use chrono::{DateTime, Datelike};
 use domain::{
     paper::{
-        PaperId, PaperSummary,
-        entity::model::Paper,
+        AuthorCitationMetrics, BlobHash, ContentFileSource, ListingCursor, PaperCategory, PaperContents, PaperId, PaperSort, PaperStatus, PaperSummary, PaperVersion, SortKey,
+        entity::model::{Paper, PaperMetadata},
+        index::PaperIndexKey,
         repository::PaperRepository,
+        search::{PostingKey, SearchDocument},
+        value_object::content::{BlobFile, StoredFile},
     },
     user::UserPrincipal,
 };
+use ic_stable_structures::{StableBTreeMap, Storable};
 use serde::{Deserialize, Serialize};
+use std::{collections::{BTreeSet, HashMap}, iter, ops::{Bound, RangeInclusive}};
 
 #[derive(Clone, Copy)]
 pub struct StablePaperRepository;
@@ -25,14 +36,32 @@ impl PaperRepository for StablePaperRepository {
     type UserPrimaryKey = UserPrincipal;
 
     fn get(&self, paper_id: &PaperId) -> Option<Paper<UserPrincipal>> {
-        STATE.with_borrow(|s| s.papers.get(paper_id)).map(|a| Paper::from_dao(a, *paper_id))
+        STATE.with_borrow(|s| read_paper(s, paper_id))
+    }
+
+    fn get_metadata(&self, paper_id: &PaperId) -> Option<PaperMetadata<UserPrincipal>> {
+        STATE.with_borrow(|s| s.papers.get(paper_id)).map(|a| PaperMetadata::from_dao(a, *paper_id).0)
+    }
+
+    fn get_text(&self, paper_id: &PaperId) -> Option<String> {
+        STATE.with_borrow(|s| match s.paper_texts.get(paper_id) {
+            // Papers not yet migrated from before `PaperDao` V6 hold their text inline
+            None if !s.paper_contents_split => read_paper(s, paper_id)?.content.text,
+            text => text,
+        })
+    }
+
+    fn read_blob(&self, hash: &BlobHash, offset: u64, len: u64) -> Option<Vec<u8>> {
+        STATE.with_borrow(|s| s.blobs.contains_key(hash).then(|| read_blob(s, hash, offset, len)))
     }
 
     fn get_summary(&self, paper_id: &PaperId) -> Option<domain::paper::PaperSummary<Self::UserPrimaryKey>> {
-        let author = STATE.with_borrow(|s| s.paper_lead_authors.get(paper_id).copied())?;
-        Some(PaperSummary {
-            id: *paper_id,
-            lead_author: author,
+        STATE.with_borrow(|s| {
+            Some(PaperSummary {
+                id: *paper_id,
+                lead_author: s.paper_lead_authors.get(paper_id).copied()?,
+                status: s.paper_statuses.get(paper_id)?,
+            })
         })
     }
 
@@ -44,28 +73,193 @@ impl PaperRepository for StablePaperRepository {
         STATE.with_borrow(|s| s.papers.contains_key(paper_id))
     }
 
+    fn latest_version(&self, paper_id: &PaperId) -> Option<PaperId> {
+        STATE.with_borrow(|s| {
+            s.paper_lead_authors
+                .range(version_range(paper_id))
+                .next_back()
+                .map(|(id, _)| *id)
+        })
+    }
+
+    fn iter_versions(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| {
+            s.paper_lead_authors
+                .range(version_range(paper_id))
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn iter_ids(&self, after: Option<&PaperId>, limit: usize) -> impl Iterator<Item = PaperId> {
+        let start = after.map_or(Bound::Unbounded, |after| Bound::Excluded(*after));
+        STATE.with_borrow(|s| {
+            s.paper_lead_authors
+                .range((start, Bound::Unbounded))
+                .take(limit)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn iter_invitations(&self, user: &UserPrincipal) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| {
+            s.co_author_invitations.get(user)
+                .map(|papers| papers.iter().copied().collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+        })
+    }
+
+    fn iter_authors(&self, paper_id: &PaperId) -> impl Iterator<Item = UserPrincipal> {
+        STATE.with_borrow(|s| revision_authors(s, paper_id).into_iter())
+    }
+
+    fn iter_referenced(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| linked_papers(&s.paper_references, paper_id).into_iter())
+    }
+
+    fn iter_citing(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| linked_papers(&s.paper_citing, paper_id).into_iter())
+    }
+
+    fn get_author_metrics(&self, user: &UserPrincipal) -> AuthorCitationMetrics {
+        STATE.with_borrow(|s| s.author_metrics.get(user).copied().unwrap_or_default())
+    }
+
+    fn iter_postings(&self, term: &str, limit: usize) -> impl Iterator<Item = (PaperId, u32)> {
+        STATE.with_borrow(|s| {
+            s.search_postings.range(PostingKey::range(term))
+                .take(limit)
+                .map(|(key, frequency)| (key.paper_id, frequency))
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn get_indexed_length(&self, paper_id: &PaperId) -> Option<u32> {
+        STATE.with_borrow(|s| s.search_lengths.get(paper_id))
+    }
+
+    fn index_statistics(&self) -> (u64, u64) {
+        STATE.with_borrow(|s| (s.search_document_count, s.search_total_length))
+    }
+
+    fn iter_sorted(&self, sort: PaperSort, status: PaperStatus, after: Option<&ListingCursor>, limit: usize) -> impl Iterator<Item = ListingCursor> {
+        STATE.with_borrow(|s| {
+            sort_index(s, sort).after(status, after.map(|after| (after.key.clone(), after.paper_id)), sort.is_descending(), limit)
+                .into_iter()
+                .map(|(key, paper_id)| ListingCursor { sort, key, paper_id })
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn get_listing_cursor(&self, sort: PaperSort, paper_id: &PaperId) -> Option<ListingCursor> {
+        STATE.with_borrow(|s| {
+            let key = sort_index(s, sort).get(paper_id)?;
+            Some(ListingCursor { sort, key, paper_id: *paper_id })
+        })
+    }
+
+    fn iter_by_author(&self, user: &UserPrincipal, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| indexed_papers(&s.papers_by_author, *user, months, limit).into_iter())
+    }
+
+    fn iter_by_category(&self, category: &PaperCategory, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| indexed_papers(&s.papers_by_category, category.clone(), months, limit).into_iter())
+    }
+
+    fn iter_by_tag(&self, tag: &str, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| indexed_papers(&s.papers_by_tag, tag.to_lowercase(), months, limit).into_iter())
+    }
+
+    fn iter_by_month(&self, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| {
+            // Identifiers order by creation month first
+            s.papers.keys_range(PaperId::created_in(months))
+                .take(limit)
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn is_by_author(&self, user: &UserPrincipal, paper_id: &PaperId) -> bool {
+        STATE.with_borrow(|s| s.papers_by_author.contains_key(&PaperIndexKey { value: *user, paper_id: *paper_id }))
+    }
+
+    fn has_category(&self, category: &PaperCategory, paper_id: &PaperId) -> bool {
+        STATE.with_borrow(|s| s.papers_by_category.contains_key(&PaperIndexKey { value: category.clone(), paper_id: *paper_id }))
+    }
+
+    fn has_tag(&self, tag: &str, paper_id: &PaperId) -> bool {
+        STATE.with_borrow(|s| s.papers_by_tag.contains_key(&PaperIndexKey { value: tag.to_lowercase(), paper_id: *paper_id }))
+    }
+
     fn iter_summary(&self) -> impl Iterator<Item = PaperSummary<Self::UserPrimaryKey>> {
         STATE.with_borrow(|s| {
             s.paper_lead_authors.iter()
-                .map(|(id, lead_author)| PaperSummary { id: *id, lead_author: *lead_author })
+                .filter_map(|(id, lead_author)| Some(PaperSummary {
+                    id: *id,
+                    lead_author: *lead_author,
+                    status: s.paper_statuses.get(id)?,
+                }))
                 .collect::<Vec<_>>()
                 .into_iter()
         })
     }
 
-    fn insert(&mut self, paper_id: PaperId, paper: Paper<UserPrincipal>) -> Option<Paper<UserPrincipal>> {
+    fn insert(&mut self, paper_id: PaperId, paper: Paper<UserPrincipal>) -> Option<PaperMetadata<UserPrincipal>> {
         STATE.with_borrow_mut(|s| {
+            let previous_authors = revision_authors(s, &paper_id);
             s.paper_titles.insert(paper_id, paper.title.clone());
             s.paper_lead_authors.insert(paper_id, paper.lead_author);
-            s.papers.insert(paper_id, paper.into()).map(|a| Paper::from_dao(a, paper_id))
+            index_revision(s, paper_id, &paper);
+            index_search(s, paper_id, &paper);
+
+            let (metadata, content) = paper.into_parts();
+            index_sort_keys(s, paper_id, &metadata);
+            index_citation_count(s, &paper_id, previous_authors);
+            let listing = ListingEntry::new(&metadata);
+            let invited = metadata.invited_co_authors.clone();
+            let previous = s.papers.insert(paper_id, metadata.into()).map(|a| PaperMetadata::from_dao(a, paper_id).0);
+            insert_contents(s, paper_id, content);
+
+            if let Some(previous) = &previous {
+                unindex_invitations(&mut s.co_author_invitations, &paper_id, &previous.invited_co_authors);
+                ListingEntry::new(previous).unindex(s, &paper_id);
+            }
+            listing.index(s, paper_id);
+            for user in invited {
+                s.co_author_invitations.entry(user).or_default().insert(paper_id);
+            }
+            previous
         })
     }
 
-    fn remove(&mut self, paper_id: &PaperId) -> Option<Paper<UserPrincipal>> {
+    fn remove(&mut self, paper_id: &PaperId) -> Option<PaperMetadata<UserPrincipal>> {
         STATE.with_borrow_mut(|s| {
+            let previous_authors = revision_authors(s, paper_id);
             s.paper_titles.remove(paper_id);
             s.paper_lead_authors.remove(paper_id);
-            s.papers.remove(paper_id).map(|a| Paper::from_dao(a, *paper_id))
+            unindex_revision(s, paper_id);
+            unindex_search(s, paper_id);
+            for sort in SORTS {
+                sort_index_mut(s, sort).remove(paper_id);
+            }
+            list_latest(s, paper_id);
+            // Other revisions of the paper lose the citations of this one
+            index_citation_count(s, paper_id, previous_authors);
+            s.paper_texts.remove(paper_id);
+            if let Some(StoredFile::Blob(blob)) = s.paper_files.remove(paper_id) {
+                release_blob(s, &blob.hash);
+            }
+            let (paper, _) = PaperMetadata::from_dao(s.papers.remove(paper_id)?, *paper_id);
+            unindex_invitations(&mut s.co_author_invitations, paper_id, &paper.invited_co_authors);
+            ListingEntry::new(&paper).unindex(s, paper_id);
+            Some(paper)
         })
     }
 
@@ -97,6 +291,312 @@ impl PaperRepository for StablePaperRepository {
     }
 }
 
+/// Reads a paper with its contents, which papers stored before `PaperDao` V6 hold inline.
+///
+/// A PDF in the blob store is referenced rather than read, see [`StablePaperRepository::read_blob`].
+pub(crate) fn read_paper(s: &State, paper_id: &PaperId) -> Option<Paper<UserPrincipal>> {
+    let (metadata, content) = PaperMetadata::from_dao(s.papers.get(paper_id)?, *paper_id);
+    let content = content.unwrap_or_else(|| {
+        let pdf = s.paper_files.get(paper_id).map(|file| match file {
+            StoredFile::Raw(file) => ContentFileSource::Raw(file),
+            StoredFile::Http(url) => ContentFileSource::Http(url),
+            StoredFile::Blob(blob) => ContentFileSource::Blob(blob),
+        });
+        PaperContents::new(s.paper_texts.get(paper_id), pdf)
+    });
+    Some(Paper::from_parts(metadata, content))
+}
+
+/// Stores the text and PDF of a paper apart from its metadata, with raw PDFs in the blob store.
+pub(crate) fn insert_contents(s: &mut State, paper_id: PaperId, content: PaperContents) {
+    match content.text {
+        Some(text) => s.paper_texts.insert(paper_id, text),
+        None => s.paper_texts.remove(&paper_id),
+    };
+    let previous = match s.paper_files.get(&paper_id) {
+        Some(StoredFile::Blob(blob)) => Some(blob.hash),
+        _ => None,
+    };
+    // The new file is referenced before the previous one is released, so a file shared
+    // with it never drops to no references; a file kept as is gains no reference
+    let file = match content.pdf {
+        Some(ContentFileSource::Raw(file)) => {
+            let blob = BlobFile::of(&file);
+            if previous != Some(blob.hash) {
+                store_blob(s, &blob, &file.content);
+            }
+            Some(StoredFile::Blob(blob))
+        }
+        Some(ContentFileSource::Blob(blob)) => {
+            // Clients send references back, so the size is taken from the stored blob
+            let stored = s.blobs.get(&blob.hash).map(|entry| BlobFile { size: entry.size, ..blob });
+            stored.filter(|blob| previous == Some(blob.hash) || reference_blob(s, &blob.hash)).map(StoredFile::Blob)
+        }
+        Some(ContentFileSource::Http(url)) => Some(StoredFile::Http(url)),
+        None => None,
+    };
+    let kept = matches!(&file, Some(StoredFile::Blob(blob)) if previous == Some(blob.hash));
+    match file {
+        Some(file) => s.paper_files.insert(paper_id, file),
+        None => s.paper_files.remove(&paper_id),
+    };
+    if let Some(hash) = previous.filter(|_| !kept) {
+        release_blob(s, &hash);
+    }
+}
+
+/// Records the status, co-authors and citations of a paper revision, which are looked up
+/// without decoding it.
+pub(crate) fn index_revision(s: &mut State, paper_id: PaperId, paper: &Paper<UserPrincipal>) {
+    s.paper_statuses.insert(paper_id, paper.status);
+    remove_co_authors(s, &paper_id);
+    for (position, co_author) in paper.co_authors.iter().enumerate() {
+        s.paper_co_authors.insert((paper_id, position as u32), *co_author);
+    }
+    link_papers(&mut s.paper_references, paper_id, paper.referenced_papers());
+    link_papers(&mut s.paper_citing, paper_id, paper.citing_papers());
+}
+
+fn unindex_revision(s: &mut State, paper_id: &PaperId) {
+    s.paper_statuses.remove(paper_id);
+    remove_co_authors(s, paper_id);
+    link_papers(&mut s.paper_references, *paper_id, BTreeSet::new());
+    link_papers(&mut s.paper_citing, *paper_id, BTreeSet::new());
+}
+
+fn remove_co_authors(s: &mut State, paper_id: &PaperId) {
+    let keys: Vec<_> = s.paper_co_authors.keys_range(co_author_range(paper_id)).collect();
+    for key in keys {
+        s.paper_co_authors.remove(&key);
+    }
+}
+
+/// Range covering the co-authors of the paper revision identified by `paper_id`.
+fn co_author_range(paper_id: &PaperId) -> RangeInclusive<(PaperId, u32)> {
+    (*paper_id, 0)..=(*paper_id, u32::MAX)
+}
+
+/// Returns the papers filed under a paper in a citation index.
+fn linked_papers(index: &StableBTreeMap<PaperIndexKey<PaperId>, (), Memory>, paper_id: &PaperId) -> Vec<PaperId> {
+    index.keys_range(PaperIndexKey::range(*paper_id, PaperId::MIN..=PaperId::MAX))
+        .map(|key| key.paper_id)
+        .collect()
+}
+
+/// Files exactly `linked` under a paper in a citation index, writing only what changed.
+fn link_papers(index: &mut StableBTreeMap<PaperIndexKey<PaperId>, (), Memory>, paper_id: PaperId, linked: BTreeSet<PaperId>) {
+    let previous: BTreeSet<_> = linked_papers(index, &paper_id).into_iter().collect();
+    for removed in previous.difference(&linked) {
+        index.remove(&PaperIndexKey { value: paper_id, paper_id: *removed });
+    }
+    for added in linked.difference(&previous) {
+        index.insert(PaperIndexKey { value: paper_id, paper_id: *added }, ());
+    }
+}
+
+/// Replaces the postings of a paper in the full-text index with those of its current text.
+pub(crate) fn index_search(s: &mut State, paper_id: PaperId, paper: &Paper<UserPrincipal>) {
+    let text = paper.content.text.as_deref().unwrap_or_default();
+    let document = SearchDocument::new(paper.title.as_str(), &paper.ab, text, &paper.tags);
+    // Most updates, such as new citations, leave the text alone
+    if s.search_documents.get(&paper_id).is_some_and(|indexed| indexed == document) {
+        index_search_length(s, paper_id, document.length);
+        return;
+    }
+
+    unindex_search(s, &paper_id);
+    for (term, frequency) in &document.terms {
+        s.search_postings.insert(PostingKey { term: term.clone(), paper_id }, *frequency);
+    }
+    index_search_length(s, paper_id, document.length);
+    s.search_documents.insert(paper_id, document);
+}
+
+/// Records the length of a paper in the full-text index, keeping its totals up to date.
+fn index_search_length(s: &mut State, paper_id: PaperId, length: u32) {
+    match s.search_lengths.insert(paper_id, length) {
+        Some(previous) => s.search_total_length -= previous as u64,
+        None => s.search_document_count += 1,
+    }
+    s.search_total_length += length as u64;
+}
+
+/// Drops the postings of a paper from the full-text index.
+fn unindex_search(s: &mut State, paper_id: &PaperId) {
+    if let Some(length) = s.search_lengths.remove(paper_id) {
+        s.search_document_count -= 1;
+        s.search_total_length -= length as u64;
+    }
+    if let Some(document) = s.search_documents.remove(paper_id) {
+        for term in document.terms.into_keys() {
+            s.search_postings.remove(&PostingKey { term, paper_id: *paper_id });
+        }
+    }
+}
+
+/// Drops `paper_id` from the invitation index of each of `users`.
+fn unindex_invitations(index: &mut HashMap<UserPrincipal, BTreeSet<PaperId>>, paper_id: &PaperId, users: &[UserPrincipal]) {
+    for user in users {
+        if let Some(papers) = index.get_mut(user) {
+            papers.remove(paper_id);
+            if papers.is_empty() {
+                index.remove(user);
+            }
+        }
+    }
+}
+
+/// Adds a paper to the listing indexes.
+pub(crate) fn index_listing(s: &mut State, paper_id: PaperId, paper: &PaperMetadata<UserPrincipal>) {
+    index_sort_keys(s, paper_id, paper);
+    index_citation_count(s, &paper_id, Vec::new());
+    ListingEntry::new(paper).index(s, paper_id);
+}
+
+fn index_sort_keys(s: &mut State, paper_id: PaperId, paper: &PaperMetadata<UserPrincipal>) {
+    s.papers_by_created.insert(paper_id, SortKey::Number(paper.created_at));
+    s.papers_by_updated.insert(paper_id, SortKey::Number(paper.updated_at));
+    s.papers_by_title.insert(paper_id, SortKey::Text(paper.title.as_str().to_lowercase()));
+    list_latest(s, &paper_id);
+}
+
+/// Lists the latest revision of a paper under its status, and none of its other revisions
+fn list_latest(s: &mut State, paper_id: &PaperId) {
+    let versions: Vec<_> = s.paper_lead_authors.range(version_range(paper_id)).map(|(id, _)| *id).collect();
+    let latest = versions.last().copied();
+    for version in versions {
+        let status = s.paper_statuses.get(&version).filter(|_| Some(version) == latest);
+        for sort in SORTS {
+            sort_index_mut(s, sort).list(version, status);
+        }
+    }
+}
+
+/// Recounts the papers citing any revision of a paper, which every revision is listed by
+/// and which counts towards the metrics of every author of a revision.
+///
+/// `previous_authors` are the authors of the revision before it changed, who may no
+/// longer be authors of the paper. Counts match `CitationMetricsService::citation_count`.
+fn index_citation_count(s: &mut State, paper_id: &PaperId, previous_authors: Vec<UserPrincipal>) {
+    let versions: Vec<_> = s.paper_lead_authors.range(version_range(paper_id)).map(|(id, _)| *id).collect();
+    let citing: BTreeSet<_> = versions.iter()
+        .flat_map(|version| linked_papers(&s.paper_citing, version))
+        .filter(|citing| !citing.is_same_paper(paper_id))
+        .map(|citing| citing.first_version())
+        .collect();
+    let authors: BTreeSet<_> = versions.iter().flat_map(|version| revision_authors(s, version)).collect();
+    for version in versions {
+        s.papers_by_citations.insert(version, SortKey::Number(citing.len() as u64));
+    }
+
+    let paper = paper_id.first_version();
+    for author in previous_authors.into_iter().filter(|author| !authors.contains(author)) {
+        if s.author_citations.remove(&PaperIndexKey { value: author, paper_id: paper }).is_some() {
+            update_author_metrics(s, author);
+        }
+    }
+    for author in authors {
+        let count = citing.len() as u32;
+        if s.author_citations.insert(PaperIndexKey { value: author, paper_id: paper }, count) != Some(count) {
+            update_author_metrics(s, author);
+        }
+    }
+}
+
+fn revision_authors(s: &State, paper_id: &PaperId) -> Vec<UserPrincipal> {
+    s.paper_lead_authors.get(paper_id)
+        .copied()
+        .into_iter()
+        .chain(s.paper_co_authors.range(co_author_range(paper_id)).map(|(_, co_author)| co_author))
+        .collect()
+}
+
+fn update_author_metrics(s: &mut State, author: UserPrincipal) {
+    let counts: Vec<_> = s.author_citations.range(PaperIndexKey::range(author, PaperId::MIN..=PaperId::MAX))
+        .map(|(_, count)| count)
+        .collect();
+    if counts.is_empty() {
+        s.author_metrics.remove(&author);
+    } else {
+        s.author_metrics.insert(author, AuthorCitationMetrics::from_counts(counts));
+    }
+}
+
+/// Every order papers are listed in
+const SORTS: [PaperSort; 4] = [PaperSort::Newest, PaperSort::RecentlyUpdated, PaperSort::MostCited, PaperSort::Title];
+
+fn sort_index(s: &State, sort: PaperSort) -> &SortIndex {
+    match sort {
+        PaperSort::Newest => &s.papers_by_created,
+        PaperSort::RecentlyUpdated => &s.papers_by_updated,
+        PaperSort::MostCited => &s.papers_by_citations,
+        PaperSort::Title => &s.papers_by_title,
+    }
+}
+
+fn sort_index_mut(s: &mut State, sort: PaperSort) -> &mut SortIndex {
+    match sort {
+        PaperSort::Newest => &mut s.papers_by_created,
+        PaperSort::RecentlyUpdated => &mut s.papers_by_updated,
+        PaperSort::MostCited => &mut s.papers_by_citations,
+        PaperSort::Title => &mut s.papers_by_title,
+    }
+}
+
+fn indexed_papers<T: Storable + Ord + Clone>(index: &StableBTreeMap<PaperIndexKey<T>, (), Memory>, value: T, months: RangeInclusive<u16>, limit: usize) -> Vec<PaperId> {
+    index.range(PaperIndexKey::range(value, PaperId::created_in(months)))
+        .take(limit)
+        .map(|(key, _)| key.paper_id)
+        .collect()
+}
+
+/// Fields of a paper that listings are filtered on
+struct ListingEntry {
+    authors: Vec<UserPrincipal>,
+    categories: Vec<PaperCategory>,
+    tags: Vec<String>,
+}
+
+impl ListingEntry {
+    fn new(paper: &PaperMetadata<UserPrincipal>) -> Self {
+        Self {
+            authors: iter::once(paper.lead_author).chain(paper.co_authors.iter().copied()).collect(),
+            categories: paper.categories.clone(),
+            tags: paper.tags.iter().map(|tag| tag.to_lowercase()).collect(),
+        }
+    }
+
+    fn index(self, s: &mut State, paper_id: PaperId) {
+        for value in self.authors {
+            s.papers_by_author.insert(PaperIndexKey { value, paper_id }, ());
+        }
+        for value in self.categories {
+            s.papers_by_category.insert(PaperIndexKey { value, paper_id }, ());
+        }
+        for value in self.tags {
+            s.papers_by_tag.insert(PaperIndexKey { value, paper_id }, ());
+        }
+    }
+
+    fn unindex(self, s: &mut State, paper_id: &PaperId) {
+        let paper_id = *paper_id;
+        for value in self.authors {
+            s.papers_by_author.remove(&PaperIndexKey { value, paper_id });
+        }
+        for value in self.categories {
+            s.papers_by_category.remove(&PaperIndexKey { value, paper_id });
+        }
+        for value in self.tags {
+            s.papers_by_tag.remove(&PaperIndexKey { value, paper_id });
+        }
+    }
+}
+
+/// Range covering every revision of the paper identified by `paper_id`.
+fn version_range(paper_id: &PaperId) -> RangeInclusive<PaperId> {
+    paper_id.first_version()..=paper_id.with_version(PaperVersion::new(u16::MAX).unwrap())
+}
+
 #[derive(Debug, Clone, Copy, Default, CandidType, Serialize, Deserialize)]
 pub struct PaperCounter {
     pub last_generated_months: u16,
@@ -0,0 +1,150 @@
+use crate::infrastructure::Memory;
+use domain::paper::{PaperId, PaperStatus, SortKey};
+use ic_stable_structures::StableBTreeMap;
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+/// Every status a revision may be listed under, in order
+const STATUSES: [PaperStatus; 4] = [PaperStatus::Draft, PaperStatus::Published, PaperStatus::UnderReview, PaperStatus::Archived];
+
+/// Position of a listed revision: the status it is listed under, its key and its identifier
+type Listed = (PaperStatus, SortKey, PaperId);
+
+/// Paper revisions ordered by a sort key, for listings.
+///
+/// Every revision has a key, but only the latest revision of each paper is listed, and
+/// under its status, so that a listing walks nothing but the papers it may show.
+pub(crate) struct SortIndex {
+    keys: StableBTreeMap<PaperId, SortKey, Memory>,
+    /// Listed revisions by status, then by key
+    listed: StableBTreeMap<Listed, (), Memory>,
+}
+
+impl SortIndex {
+    pub fn init(keys: Memory, listed: Memory) -> Self {
+        Self {
+            keys: StableBTreeMap::init(keys),
+            listed: StableBTreeMap::init(listed),
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.keys.len()
+    }
+
+    pub fn get(&self, paper_id: &PaperId) -> Option<SortKey> {
+        self.keys.get(paper_id)
+    }
+
+    /// Sets the key of a revision, replacing its previous one
+    pub fn insert(&mut self, paper_id: PaperId, key: SortKey) {
+        let status = self.unlist(&paper_id);
+        self.keys.insert(paper_id, key);
+        self.list(paper_id, status);
+    }
+
+    /// Lists a revision under a status, or stops listing it
+    pub fn list(&mut self, paper_id: PaperId, status: Option<PaperStatus>) {
+        self.unlist(&paper_id);
+        if let (Some(status), Some(key)) = (status, self.keys.get(&paper_id)) {
+            self.listed.insert((status, key, paper_id), ());
+        }
+    }
+
+    pub fn remove(&mut self, paper_id: &PaperId) {
+        self.unlist(paper_id);
+        self.keys.remove(paper_id);
+    }
+
+    /// Returns up to `limit` revisions listed under the status following `after`, by increasing or decreasing key
+    pub fn after(&self, status: PaperStatus, after: Option<(SortKey, PaperId)>, descending: bool, limit: usize) -> Vec<(SortKey, PaperId)> {
+        let (mut start, mut end) = Self::status_range(status);
+        if let Some((key, paper_id)) = after {
+            let after = Excluded((status, key, paper_id));
+            match descending {
+                false => start = after,
+                true => end = after,
+            }
+        }
+        let entries = self.listed.range((start, end)).map(|((_, key, paper_id), _)| (key, paper_id));
+        match descending {
+            false => entries.take(limit).collect(),
+            true => entries.rev().take(limit).collect(),
+        }
+    }
+
+    /// Stops listing a revision, returning the status it was listed under
+    fn unlist(&mut self, paper_id: &PaperId) -> Option<PaperStatus> {
+        let key = self.keys.get(paper_id)?;
+        STATUSES.into_iter().find(|status| self.listed.remove(&(*status, key.clone(), *paper_id)).is_some())
+    }
+
+    /// Returns the bounds of the revisions listed under the status
+    fn status_range(status: PaperStatus) -> (Bound<Listed>, Bound<Listed>) {
+        // Numbers order before texts, so no key is lower than zero
+        let first = |status| (status, SortKey::Number(0), PaperId::MIN);
+        let end = STATUSES.into_iter()
+            .find(|next| *next > status)
+            .map_or(Unbounded, |next| Excluded(first(next)));
+        (Included(first(status)), end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::memory::init_papers_by_created;
+
+    fn id(number: u32) -> PaperId {
+        PaperId::new(0, number, 1).unwrap()
+    }
+
+    fn listed(index: &SortIndex, status: PaperStatus, after: Option<(u64, u32)>, descending: bool) -> Vec<(u64, u32)> {
+        let after = after.map(|(key, number)| (SortKey::Number(key), id(number)));
+        index.after(status, after, descending, 10).into_iter()
+            .map(|(key, paper_id)| match key {
+                SortKey::Number(key) => (key, paper_id.number()),
+                SortKey::Text(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_after() {
+        let mut index = init_papers_by_created();
+        for number in 1..=4 {
+            index.insert(id(number), SortKey::Number(number as u64 * 10));
+            let status = if number == 4 { PaperStatus::Draft } else { PaperStatus::Published };
+            index.list(id(number), Some(status));
+        }
+
+        assert_eq!(listed(&index, PaperStatus::Published, None, false), vec![(10, 1), (20, 2), (30, 3)]);
+        assert_eq!(listed(&index, PaperStatus::Published, None, true), vec![(30, 3), (20, 2), (10, 1)]);
+        assert_eq!(listed(&index, PaperStatus::Published, Some((20, 2)), false), vec![(30, 3)]);
+        assert_eq!(listed(&index, PaperStatus::Published, Some((20, 2)), true), vec![(10, 1)]);
+        assert_eq!(listed(&index, PaperStatus::Draft, None, true), vec![(40, 4)]);
+        assert!(listed(&index, PaperStatus::Archived, None, false).is_empty());
+    }
+
+    #[test]
+    fn test_insert_and_unlist() {
+        let mut index = init_papers_by_created();
+        for number in 1..=3 {
+            index.insert(id(number), SortKey::Number(number as u64 * 10));
+            index.list(id(number), Some(PaperStatus::Published));
+        }
+
+        // A new key keeps the revision listed under its status
+        index.insert(id(1), SortKey::Number(50));
+        assert_eq!(listed(&index, PaperStatus::Published, None, false), vec![(20, 2), (30, 3), (50, 1)]);
+
+        index.list(id(2), Some(PaperStatus::Archived));
+        index.list(id(3), None);
+        assert_eq!(listed(&index, PaperStatus::Published, None, false), vec![(50, 1)]);
+        assert_eq!(listed(&index, PaperStatus::Archived, None, false), vec![(20, 2)]);
+        assert_eq!(index.get(&id(3)), Some(SortKey::Number(30)));
+
+        index.remove(&id(1));
+        assert!(listed(&index, PaperStatus::Published, None, false).is_empty());
+        assert_eq!(index.len(), 2);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::infrastructure::STATE;
+use domain::{
+    paper::{
+        repository::UploadRepository,
+        upload::{UploadChunkKey, UploadSession},
+    },
+    user::UserPrincipal,
+};
+
+#[derive(Clone, Copy)]
+pub struct StableUploadRepository;
+
+impl StableUploadRepository {
+    /// Creates a new instance of `StableUploadRepository`.
+    pub fn new() -> Self {
+        StableUploadRepository {}
+    }
+}
+
+impl UploadRepository for StableUploadRepository {
+    type UserPrimaryKey = UserPrincipal;
+
+    fn get(&self, upload_id: u64) -> Option<UploadSession<UserPrincipal>> {
+        STATE.with_borrow(|s| s.uploads.get(&upload_id).cloned())
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (u64, UploadSession<UserPrincipal>)> {
+        STATE.with_borrow(|s| {
+            s.uploads.iter()
+                .map(|(upload_id, session)| (*upload_id, session.clone()))
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn open_bytes(&self) -> u64 {
+        STATE.with_borrow(|s| s.upload_bytes)
+    }
+
+    fn insert(&mut self, upload_id: u64, session: UploadSession<UserPrincipal>) {
+        STATE.with_borrow_mut(|s| {
+            s.upload_bytes += session.size;
+            if let Some(previous) = s.uploads.insert(upload_id, session) {
+                s.upload_bytes -= previous.size;
+            }
+        });
+    }
+
+    fn insert_chunk(&mut self, upload_id: u64, offset: u64, bytes: Vec<u8>) {
+        STATE.with_borrow_mut(|s| s.upload_chunks.insert(UploadChunkKey { upload_id, offset }, bytes));
+    }
+
+    fn iter_chunks(&self, upload_id: u64) -> impl Iterator<Item = Vec<u8>> {
+        STATE.with_borrow(|s| {
+            s.upload_chunks.range(UploadChunkKey::range(upload_id))
+                .map(|(_, bytes)| bytes)
+                .collect::<Vec<_>>()
+                .into_iter()
+        })
+    }
+
+    fn remove(&mut self, upload_id: u64) -> Option<UploadSession<UserPrincipal>> {
+        STATE.with_borrow_mut(|s| {
+            let chunks: Vec<_> = s.upload_chunks.range(UploadChunkKey::range(upload_id)).map(|(key, _)| key).collect();
+            for key in chunks {
+                s.upload_chunks.remove(&key);
+            }
+            let session = s.uploads.remove(&upload_id)?;
+            s.upload_bytes -= session.size;
+            Some(session)
+        })
+    }
+
+    fn generate_id(&mut self) -> u64 {
+        STATE.with_borrow_mut(|s| {
+            s.upload_counter += 1;
+            s.upload_counter
+        })
+    }
+}
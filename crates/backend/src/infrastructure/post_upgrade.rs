@@ -14,8 +14,13 @@ pub fn post_upgrade() {
     memory.read(4, &mut state_bytes);
 
     // Deserialize and set the state.
-    let state = ciborium::de::from_reader(&*state_bytes).expect("failed to decode state");
+    let state: State = ciborium::de::from_reader(&*state_bytes).expect("failed to decode state");
     STATE.with(|s| {
-        *s.borrow_mut() = state
+        let mut s = s.borrow_mut();
+        *s = state;
+        s.start_migration();
     });
+    schedule_migration();
+    schedule_blob_collection();
+    schedule_upload_sweep();
 }
@@ -0,0 +1,73 @@
+use crate::infrastructure::STATE;
+use domain::{
+    paper::PaperId,
+    review::{entity::model::Review, repository::ReviewRepository},
+    user::UserPrincipal,
+};
+
+#[derive(Clone, Copy)]
+pub struct StableReviewRepository;
+
+impl StableReviewRepository {
+    /// Creates a new instance of `StableReviewRepository`.
+    pub fn new() -> Self {
+        StableReviewRepository {}
+    }
+}
+
+impl ReviewRepository for StableReviewRepository {
+    type UserPrimaryKey = UserPrincipal;
+
+    fn get(&self, paper_id: &PaperId) -> Option<Review<UserPrincipal>> {
+        STATE.with_borrow(|s| s.reviews.get(paper_id)).map(|r| Review::from_dao(r, *paper_id))
+    }
+
+    fn insert(&mut self, paper_id: PaperId, review: Review<UserPrincipal>) -> Option<Review<UserPrincipal>> {
+        STATE.with_borrow_mut(|s| {
+            for round in &review.rounds {
+                for assignment in &round.assignments {
+                    s.review_assignments.entry(assignment.reviewer).or_default().insert(paper_id);
+                }
+            }
+            s.reviews.insert(paper_id, review.into()).map(|r| Review::from_dao(r, paper_id))
+        })
+    }
+
+    fn remove(&mut self, paper_id: &PaperId) -> Option<Review<UserPrincipal>> {
+        STATE.with_borrow_mut(|s| {
+            let review = s.reviews.remove(paper_id).map(|r| Review::from_dao(r, *paper_id))?;
+            for round in &review.rounds {
+                for assignment in &round.assignments {
+                    if let Some(papers) = s.review_assignments.get_mut(&assignment.reviewer) {
+                        papers.remove(paper_id);
+                        if papers.is_empty() {
+                            s.review_assignments.remove(&assignment.reviewer);
+                        }
+                    }
+                }
+            }
+            Some(review)
+        })
+    }
+
+    fn iter_assigned(&self, reviewer: &UserPrincipal) -> impl Iterator<Item = PaperId> {
+        STATE.with_borrow(|s| {
+            s.review_assignments.get(reviewer)
+                .map(|papers| papers.iter().copied().collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+        })
+    }
+
+    fn is_editor(&self, user: &UserPrincipal) -> bool {
+        STATE.with_borrow(|s| s.editors.contains(user))
+    }
+
+    fn add_editor(&mut self, user: UserPrincipal) -> bool {
+        STATE.with_borrow_mut(|s| s.editors.insert(user))
+    }
+
+    fn remove_editor(&mut self, user: &UserPrincipal) -> bool {
+        STATE.with_borrow_mut(|s| s.editors.remove(user))
+    }
+}
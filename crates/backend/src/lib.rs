@@ -8,4 +8,6 @@ mod use_case;
 #[ic_cdk::init]
 fn init() {
     init::init();
+    infrastructure::schedule_blob_collection();
+    infrastructure::schedule_upload_sweep();
 }
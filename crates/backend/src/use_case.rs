@@ -1,2 +1,4 @@
 pub mod paper;
+pub mod review;
+pub mod upload;
 pub mod user;
@@ -1,88 +1,208 @@
 use domain::{
     paper::{
-        entity::model::Paper,
+        citation_graph::CitationGraphService,
+        citation_metrics::CitationMetricsService,
+        entity::model::{Paper, PaperChanges, PaperMetadata},
+        listing_service::{ListingPage, ListingService, PaperFilter},
+        network::{CitationNetworkPage, CoAuthorshipNetworkPage, NetworkService},
+        reference_integrity::ReferenceIntegrityService,
         repository::PaperRepository,
-        service::PaperService,
-        PaperId,
+        search_service::{SearchPage, SearchService},
+        service::{PaperService, PaperServiceError},
+        value_object::content::RawFile,
+        AuthorCitationMetrics, BlobEntry, BlobHash, BrokenReference, BrokenReferenceReason, Citation, CitationContext, CoAuthorRole, ContentFileSource, ListingCursor, PaperAction, PaperCategory, PaperContents, PaperId, PaperPermissionError, PaperRole, PaperSort, PaperStatus,
+        PaperStatusTransition, PaperStatusTransitionError, PaperTitle,
     },
-    user::{repository::UserRepository, service::UserService, UserPrimaryKey},
+    review::{repository::ReviewRepository, service::ReviewService},
+    user::{repository::UserRepository, service::{UserService, UserServiceError}, UserId, UserPrimaryKey},
 };
-use std::marker::PhantomData;
 use std::str::FromStr;
 
-pub struct PaperUseCase<A: PaperRepository, U: UserRepository, UK: UserPrimaryKey> {
+/// Users paired with the role they hold, or were invited with, on a paper
+pub type CoAuthorRoles<UK> = Vec<(UK, CoAuthorRole)>;
+
+pub struct PaperUseCase<A: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> {
     repository: A,
     user_repository: U,
-    _marker: PhantomData<UK>,
+    review_repository: V,
+    /// The caller, if it administers the canister
+    admin: Option<UK>,
 }
 
-impl<R: PaperRepository, U: UserRepository, UK: UserPrimaryKey> PaperUseCase<R, U, UK>
+impl<R: PaperRepository, U: UserRepository, V: ReviewRepository, UK: UserPrimaryKey> PaperUseCase<R, U, V, UK>
 where
     R: PaperRepository<UserPrimaryKey = UK> + Clone,
     U: UserRepository<PrimaryKey = UK> + Clone,
+    V: ReviewRepository<UserPrimaryKey = UK> + Clone,
     UK: UserPrimaryKey,
 {
-    pub fn new(repository: R, user_repository: U) -> Self {
+    pub fn new(repository: R, user_repository: U, review_repository: V, admin: Option<UK>) -> Self {
         Self {
             repository,
             user_repository,
-            _marker: PhantomData,
+            review_repository,
+            admin,
         }
     }
 
-    // Return domain model
-    pub fn get_paper(&self, paper_id_str: &str) -> Result<(Paper<UK>, PaperId), String> {
-        let paper_id = PaperId::from_str(paper_id_str)
-            .map_err(|e| format!("Invalid paper ID: {}", e))?;
+    // Checks whether the caller must not see who wrote the paper.
+    // Only authors and editors see the authors of a paper under double-blind review.
+    pub fn hides_authors_from(&self, paper: &Paper<UK>, caller: &UK) -> bool {
+        let review_service = ReviewService::new(self.review_repository.clone());
+        review_service.hides_identities(&paper.id)
+            && !paper.is_author(caller)
+            && !review_service.is_editor(caller)
+    }
+
+    // Checks whether the paper's authors are hidden from the public listing
+    pub fn hides_authors(&self, paper_id: &PaperId) -> bool {
+        ReviewService::new(self.review_repository.clone()).hides_identities(paper_id)
+    }
+
+    // Return domain model if the caller may read the paper in its current status
+    pub fn get_paper(&self, paper_id_str: &str, caller: &UK) -> Result<(Paper<UK>, PaperId), PaperServiceError> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let paper = self.find_paper(&paper_id)?;
 
-        let paper = self.repository.get(&paper_id)
-            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+        if !paper.status.is_public() {
+            self.authorize(&paper, caller, PaperAction::Read)?;
+        }
 
         Ok((paper, paper_id))
     }
 
-    // Checks for authorship and then returns a domain model.
-    pub fn get_paper_as_author(&self, paper_id_str: &str, caller: UK) -> Result<(Paper<UK>, PaperId), String> {
-        let paper_id = PaperId::from_str(paper_id_str)
-            .map_err(|e| format!("Invalid paper ID: {}", e))?;
+    // Reads a range of a file in the blob store, at most `BlobEntry::MAX_READ` bytes of it.
+    // Files are addressed by the digest of their content, which only readers of a paper holding them are shown.
+    pub fn read_blob(&self, hash: &str, offset: u64, len: u64) -> Result<Vec<u8>, PaperServiceError> {
+        let hash = BlobHash::from_str(hash)
+            .map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
 
-        // Author Authority Check
-        let summary = self.repository.get_summary(&paper_id);
-        if let Some(summary) = summary {
-            if summary.lead_author != caller {
-                return Err(format!("User is not the lead author of paper: {}", paper_id));
-            }
-        }
+        self.repository.read_blob(&hash, offset, len.min(BlobEntry::MAX_READ))
+            .ok_or_else(|| PaperServiceError::NotFound(hash.to_string()))
+    }
 
-        let paper = self.repository.get(&paper_id)
-            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+    // Return the paper without its contents if the caller may read it, without decoding the files
+    pub fn get_paper_metadata(&self, paper_id: &PaperId, caller: &UK) -> Option<Paper<UK>> {
+        let paper = Paper::from_parts(self.repository.get_metadata(paper_id)?, PaperContents::default());
 
+        (paper.status.is_public() || self.authorize(&paper, caller, PaperAction::Read).is_ok()).then_some(paper)
+    }
+
+    // Checks the caller may read the paper and then returns a domain model.
+    pub fn get_paper_as_author(&self, paper_id_str: &str, caller: UK) -> Result<(Paper<UK>, PaperId), PaperServiceError> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let paper = self.find_paper(&paper_id)?;
+
+        self.authorize(&paper, &caller, PaperAction::Read)?;
         Ok((paper, paper_id))
     }
 
-    // Obtaining paper summary information
+    // Checks whether the caller may read the paper, without decoding public papers
+    fn can_read(&self, paper_id: &PaperId, caller: &UK) -> bool {
+        self.repository.get_summary(paper_id).is_some_and(|summary| summary.status.is_public())
+            || self.get_paper(&paper_id.to_string(), caller).is_ok()
+    }
+
+    // Resolves the caller's role on the paper and checks it allows the action
+    pub fn authorize(&self, paper: &Paper<UK>, user: &UK, action: PaperAction) -> Result<PaperRole, PaperServiceError> {
+        let role = self.role_of(paper, user)
+            .ok_or(PaperPermissionError::NoRole(action))?;
+
+        role.check(action, paper.status)?;
+        Ok(role)
+    }
+
+    // Reviewers hold their role on the papers they were assigned to, while editors
+    // hold theirs on any paper while it is under review
+    pub fn role_of(&self, paper: &Paper<UK>, user: &UK) -> Option<PaperRole> {
+        let review_service = ReviewService::new(self.review_repository.clone());
+        if self.admin.as_ref() == Some(user) {
+            Some(PaperRole::Admin)
+        } else if let Some(role) = paper.author_role(user) {
+            Some(role)
+        } else if review_service.get(&paper.id).is_ok_and(|review| review.is_reviewer(user)) {
+            Some(PaperRole::Reviewer)
+        } else if paper.status == PaperStatus::UnderReview && review_service.is_editor(user) {
+            Some(PaperRole::Editor)
+        } else {
+            None
+        }
+    }
+
+    fn parse_paper_id(paper_id_str: &str) -> Result<PaperId, PaperServiceError> {
+        PaperId::from_str(paper_id_str)
+            .map_err(|e| PaperServiceError::InvalidId(e.to_string()))
+    }
+
+    fn find_paper(&self, paper_id: &PaperId) -> Result<Paper<UK>, PaperServiceError> {
+        self.repository.get(paper_id)
+            .ok_or_else(|| PaperServiceError::NotFound(paper_id.to_string()))
+    }
+
+    // Obtaining summary information of published papers
     pub fn get_all_paper_summaries(&self) -> Vec<(String, String, UK, String)> {
         let mut results = Vec::new();
 
         let summaries = self.repository.iter_summary();
         for summary in summaries {
-            let (id, author) = (summary.id, summary.lead_author);
-            let title = self.repository.get_title(&id);
-            let author_name = self.user_repository.get_name_by_primary_key(&author);
-
-            if let (Some(title), Some(author_name)) = (title, author_name) {
-                results.push((
-                    id.to_string(),
-                    title.to_string(),
-                    author,
-                    author_name.to_string(),
-                ));
+            // Drafts, papers under review and archived papers stay out of public listings
+            if summary.status != PaperStatus::Published {
+                continue;
             }
+
+            // Superseded revisions are reachable through the revision history only
+            if self.repository.latest_version(&summary.id) != Some(summary.id) {
+                continue;
+            }
+
+            results.extend(self.get_paper_summary(&summary.id));
         }
 
         results
     }
 
+    // Identifier, title, lead author and lead author name of a paper revision
+    pub fn get_paper_summary(&self, paper_id: &PaperId) -> Option<(String, String, UK, String)> {
+        let summary = self.repository.get_summary(paper_id)?;
+        let title = self.repository.get_title(paper_id)?;
+        let author_name = self.user_repository.get_name_by_primary_key(&summary.lead_author)?;
+
+        Some((paper_id.to_string(), title.to_string(), summary.lead_author, author_name.to_string()))
+    }
+
+    // Text content of a paper revision, without decoding its PDF
+    pub fn get_paper_text(&self, paper_id: &PaperId) -> Option<String> {
+        self.repository.get_text(paper_id)
+    }
+
+    // Page of the latest revisions of papers matching a filter, in the given order.
+    // Only published and archived papers are listed, and filtering by author skips papers
+    // whose authors are hidden by a double-blind review.
+    pub fn list_papers(&self, filter: PaperFilter<String>, sort: PaperSort, page_size: u32, cursor: Option<&str>) -> Result<ListingPage, PaperServiceError> {
+        if !filter.status.is_public() {
+            return Err(PaperServiceError::Rejected("Only published and archived papers can be listed".to_string()));
+        }
+        let after = cursor.map(|cursor| {
+            let cursor = ListingCursor::from_str(cursor).map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
+            if cursor.sort != sort {
+                return Err(PaperServiceError::Rejected("The cursor belongs to a listing in another order".to_string()));
+            }
+            Ok(cursor)
+        }).transpose()?;
+
+        let filter = PaperFilter {
+            status: filter.status,
+            category: filter.category,
+            tag: filter.tag,
+            author: filter.author.map(|user_id_str| self.resolve_user(&user_id_str)).transpose()?,
+            created_from: filter.created_from,
+            created_until: filter.created_until,
+        };
+
+        let service = ListingService::new(self.repository.clone());
+        Ok(service.page(&filter, sort, after.as_ref(), page_size as usize, |id| !self.hides_authors(id)))
+    }
+
     // Drafting
     pub fn create_draft(&mut self, user: UK) -> Result<PaperId, String> {
         let mut service = PaperService::new(self.repository.clone());
@@ -96,50 +216,407 @@ where
     }
 
     // Thesis Updates
-    pub fn update_paper(&mut self, user: UK, id_str: &str, model: Paper<UK>) -> Result<(), String> {
-        let id = PaperId::from_str(id_str)
-            .map_err(|e| format!("Invalid paper ID: {}", e))?;
+    pub fn update_paper(&mut self, user: UK, id_str: &str, model: Paper<UK>) -> Result<u64, PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let current = self.find_paper(&id)?;
+        self.authorize(&current, &user, PaperAction::Update)?;
+
+        // Status changes go through the same permission as explicit transitions,
+        // and authors cannot smuggle an editorial decision in through the status field
+        let transition = current.status.transition_to(model.status)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to update paper: {}", e)))?;
+        if let Some(transition) = transition {
+            self.authorize(&current, &user, PaperAction::Publish)?;
+            if transition.is_editorial() {
+                return Err(PaperServiceError::Rejected(format!("Failed to update paper: {}", PaperStatusTransitionError::EditorialDecision(transition))));
+            }
+        }
+
+        let mut service = PaperService::new(self.repository.clone());
+        service.update(model).map_err(|e| match e {
+            PaperServiceError::Rejected(e) => PaperServiceError::Rejected(format!("Failed to update paper: {}", e)),
+            e => e,
+        })
+    }
+
+    // Partial updates leaving server-owned fields untouched
+    pub fn patch_paper(&mut self, user: UK, id_str: &str, changes: PaperChanges, seen_updated_at: u64) -> Result<u64, PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let current = self.find_paper(&id)?;
+        self.authorize(&current, &user, PaperAction::Update)?;
 
         let mut service = PaperService::new(self.repository.clone());
-        if !service.check_is_author(&user, &id) {
-            return Err("User is not an author of the paper".to_string());
+        service.patch(&id, changes, seen_updated_at).map_err(|e| match e {
+            PaperServiceError::Rejected(e) => PaperServiceError::Rejected(format!("Failed to update paper: {}", e)),
+            e => e,
+        })
+    }
+
+    // Attaching a file uploaded in chunks as the PDF of a paper
+    pub fn attach_pdf(&mut self, user: UK, id_str: &str, file: RawFile, seen_updated_at: u64) -> Result<u64, PaperServiceError> {
+        let changes = PaperChanges { pdf: Some(Some(ContentFileSource::Raw(file))), ..Default::default() };
+        self.patch_paper(user, id_str, changes, seen_updated_at)
+    }
+
+    // Moving a paper along the status transition table
+    pub fn transition_paper_status(&mut self, user: UK, id_str: &str, transition: PaperStatusTransition) -> Result<PaperStatus, PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let paper = self.find_paper(&id)?;
+        self.authorize(&paper, &user, PaperAction::Publish)?;
+
+        if transition.is_editorial() {
+            return Err(PaperServiceError::Rejected(PaperStatusTransitionError::EditorialDecision(transition).to_string()));
         }
 
-        service.update(model).map_err(|e| format!("Failed to update paper: {}", e))
+        let mut service = PaperService::new(self.repository.clone());
+        service.transition_status(&id, transition)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to change paper status: {}", e)))
     }
 
-    // Get a reference to the user repository
-    pub fn get_user_repository(&self) -> &U {
-        &self.user_repository
+    // Publishing a new revision of a published paper
+    pub fn publish_revision(&mut self, user: UK, id_str: &str, model: Paper<UK>) -> Result<PaperId, PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        if model.id != id {
+            return Err(PaperServiceError::Rejected("Paper ID does not match the paper contents".to_string()));
+        }
+        let paper = self.find_paper(&id)?;
+        self.authorize(&paper, &user, PaperAction::Publish)?;
+
+        let mut service = PaperService::new(self.repository.clone());
+        let revision_id = service.publish_revision(model)?;
+
+        // Point the authors' paper lists at the new head
+        let revision = self.find_paper(&revision_id)?;
+        let mut user_service = UserService::new(self.user_repository.clone());
+        for author in std::iter::once(&revision.lead_author).chain(revision.co_authors.iter()) {
+            user_service.replace_paper(author, &id, &revision_id)
+                .map_err(|e| PaperServiceError::Rejected(format!("Failed to update author's paper list: {}", e)))?;
+        }
+
+        Ok(revision_id)
     }
 
-    pub fn delete_paper(&mut self, user: UK, paper_id_str: &str) -> Result<(), String> {
-        let paper_id = PaperId::from_str(paper_id_str)
-            .map_err(|e| format!("Invalid paper ID: {}", e))?;
+    // Listing every revision of a paper the caller may read, oldest first
+    pub fn get_revisions(&self, paper_id_str: &str, caller: &UK) -> Result<Vec<Paper<UK>>, PaperServiceError> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
 
-        // Check if the paper exists
-        if !self.repository.contains(&paper_id) {
-            return Err(format!("Paper not found: {}", paper_id));
+        let service = PaperService::new(self.repository.clone());
+        let revisions: Vec<_> = service.get_revisions(&paper_id)
+            .into_iter()
+            .filter(|paper| paper.status.is_public() || self.authorize(paper, caller, PaperAction::Read).is_ok())
+            .collect();
+        if revisions.is_empty() {
+            return Err(PaperServiceError::NotFound(paper_id.to_string()));
         }
 
-        // Verify the caller is the lead author of the paper
-        let summary = self.repository.get_summary(&paper_id)
-            .ok_or_else(|| format!("Paper summary not found: {}", paper_id))?;
+        Ok(revisions)
+    }
+
+    // Inviting a user to co-author a paper
+    pub fn invite_co_author(&mut self, user: UK, id_str: &str, invitee_id_str: &str, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let invitee_id = UserId::new(invitee_id_str)
+            .map_err(|_| PaperServiceError::UserNotFound(invitee_id_str.to_string()))?;
+        let invitee = self.user_repository.get_primary_key(&invitee_id)
+            .ok_or_else(|| PaperServiceError::UserNotFound(invitee_id.to_string()))?;
+        let paper = self.find_paper(&id)?;
+        self.authorize(&paper, &user, PaperAction::ManageAuthors)?;
+
+        let mut service = PaperService::new(self.repository.clone());
+        service.invite_co_author(&id, invitee, role)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to invite co-author: {}", e)))
+    }
+
+    // Accepting an invitation adds the paper to the invitee's profile
+    pub fn accept_invitation(&mut self, user: UK, id_str: &str) -> Result<(), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+
+        let mut service = PaperService::new(self.repository.clone());
+        service.accept_invitation(&id, &user)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to accept invitation: {}", e)))?;
+
+        let mut user_service = UserService::new(self.user_repository.clone());
+        user_service.add_paper_as_co_author(&user, &id)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to add paper as co-author: {}", e)))
+    }
+
+    pub fn decline_invitation(&mut self, user: UK, id_str: &str) -> Result<(), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+
+        let mut service = PaperService::new(self.repository.clone());
+        service.withdraw_invitation(&id, &user)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to decline invitation: {}", e)))
+    }
+
+    // Removing a co-author or cancelling their invitation, or a co-author leaving the paper
+    pub fn remove_co_author(&mut self, user: UK, id_str: &str, co_author_id_str: &str) -> Result<(), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let co_author = self.resolve_user(co_author_id_str)?;
+        let paper = self.find_paper(&id)?;
+        if co_author != user {
+            self.authorize(&paper, &user, PaperAction::ManageAuthors)?;
+        }
 
-        if summary.lead_author != user {
-            return Err("Only the lead author can delete the paper".to_string());
+        let mut service = PaperService::new(self.repository.clone());
+        if paper.is_invited(&co_author) {
+            return service.withdraw_invitation(&id, &co_author)
+                .map_err(|e| PaperServiceError::Rejected(format!("Failed to cancel invitation: {}", e)));
         }
 
-        // Remove the paper from the author's list
+        service.remove_co_author(&id, &co_author)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to remove co-author: {}", e)))?;
+
+        let mut user_service = UserService::new(self.user_repository.clone());
+        Self::unlist_co_authored(&mut user_service, &co_author, &id)
+    }
+
+    pub fn set_co_author_role(&mut self, user: UK, id_str: &str, co_author_id_str: &str, role: CoAuthorRole) -> Result<(), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let co_author = self.resolve_user(co_author_id_str)?;
+        let paper = self.find_paper(&id)?;
+        self.authorize(&paper, &user, PaperAction::ManageAuthors)?;
+
+        let mut service = PaperService::new(self.repository.clone());
+        service.set_co_author_role(&id, &co_author, role)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to change co-author role: {}", e)))
+    }
+
+    // Co-authors and pending invitations of a paper with their roles
+    pub fn get_co_author_roles(&self, user: UK, id_str: &str) -> Result<(CoAuthorRoles<UK>, CoAuthorRoles<UK>), PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let paper = self.find_paper(&id)?;
+        self.authorize(&paper, &user, PaperAction::ManageAuthors)?;
+
+        let with_role = |users: &[UK]| users.iter()
+            .map(|key| (*key, paper.co_author_roles.get(key).copied().unwrap_or_default()))
+            .collect::<Vec<_>>();
+        Ok((with_role(&paper.co_authors), with_role(&paper.invited_co_authors)))
+    }
+
+    // Papers the user has been invited to co-author
+    pub fn get_invitations(&self, user: UK) -> Vec<PaperMetadata<UK>> {
+        let service = PaperService::new(self.repository.clone());
+
+        service.get_invitations(&user)
+            .into_iter()
+            .filter_map(|id| self.repository.get_metadata(&id))
+            .collect()
+    }
+
+    // Resolve a user from either a UserId or a `p_`-prefixed primary key
+    fn resolve_user(&self, user_id_str: &str) -> Result<UK, PaperServiceError> {
+        let key = if let Some(primary_key_str) = user_id_str.strip_prefix("p_") {
+            primary_key_str.parse().ok()
+        } else {
+            UserId::new(user_id_str).ok().and_then(|id| self.user_repository.get_primary_key(&id))
+        };
+
+        key.ok_or_else(|| PaperServiceError::UserNotFound(user_id_str.to_string()))
+    }
+
+    // Title of a paper revision, without decoding the paper
+    pub fn get_title(&self, paper_id: &PaperId) -> Option<PaperTitle> {
+        self.repository.get_title(paper_id)
+    }
+
+    // Get a reference to the user repository
+    pub fn get_user_repository(&self) -> &U {
+        &self.user_repository
+    }
+
+    pub fn delete_paper(&mut self, user: UK, paper_id_str: &str) -> Result<(), PaperServiceError> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+
+        // Only the latest revision carries the current authorship
+        let latest_id = self.repository.latest_version(&paper_id)
+            .ok_or_else(|| PaperServiceError::NotFound(paper_id.to_string()))?;
+        let latest = self.find_paper(&latest_id)?;
+        self.authorize(&latest, &user, PaperAction::Delete)?;
+
+        // Remove the paper from the authors' lists, which track the latest revision
         let mut user_service = UserService::new(self.user_repository.clone());
         user_service
-            .remove_paper_as_lead_author(&user, &paper_id)
-            .map_err(|e| format!("Failed to remove paper from user's list: {}", e))?;
+            .remove_paper_as_lead_author(&latest.lead_author, &latest_id)
+            .map_err(|e| PaperServiceError::Rejected(format!("Failed to remove paper from user's list: {}", e)))?;
+
+        for co_author in &latest.co_authors {
+            Self::unlist_co_authored(&mut user_service, co_author, &latest_id)?;
+        }
 
-        // Delete the paper from the repository
-        self.repository.remove(&paper_id)
-            .ok_or_else(|| format!("Failed to delete paper: {}", paper_id))?;
+        // Delete every revision of the paper, withdrawing its citations of other papers
+        let mut service = PaperService::new(self.repository.clone());
+        let versions: Vec<_> = self.repository.iter_versions(&paper_id).collect();
+        for version in versions {
+            service.remove(&version)
+                .map_err(|e| PaperServiceError::Rejected(format!("Failed to delete paper {}: {}", version, e)))?;
+        }
 
         Ok(())
     }
+
+    // Removes a paper from a co-author's profile.
+    // Co-authors added before invitations existed never had the paper on their profile.
+    fn unlist_co_authored(user_service: &mut UserService<U>, co_author: &UK, paper_id: &PaperId) -> Result<(), PaperServiceError> {
+        match user_service.remove_paper_as_co_author(co_author, paper_id) {
+            Ok(()) | Err(UserServiceError::PaperNotListed) => Ok(()),
+            Err(e) => Err(PaperServiceError::Rejected(format!("Failed to remove paper from co-author's list: {}", e))),
+        }
+    }
+
+    // Number of papers citing any revision of a paper
+    pub fn get_citation_count(&self, paper_id_str: &str, caller: &UK) -> Result<u32, PaperServiceError> {
+        let (paper, _) = self.get_paper(paper_id_str, caller)?;
+
+        Ok(CitationMetricsService::new(self.repository.clone()).citation_count(&paper.id))
+    }
+
+    // Papers citing a paper directly or transitively, with their distance in hops
+    // and, for direct citations, how they cite the paper
+    pub fn get_cited_by_within(&self, paper_id_str: &str, hops: u8, caller: &UK) -> Result<Vec<(PaperId, u8, Vec<CitationContext>)>, PaperServiceError> {
+        let (paper, _) = self.get_paper(paper_id_str, caller)?;
+
+        let service = CitationMetricsService::new(self.repository.clone());
+        let graph = CitationGraphService::new(self.repository.clone());
+        Ok(service.cited_by_within(&paper.id, hops).into_iter().map(|(citing, hops)| {
+            let contexts = if hops == 1 { graph.citation_contexts(&citing, &paper.id) } else { Vec::new() };
+            (citing, hops, contexts)
+        }).collect())
+    }
+
+    // Shortest chain of citations leading from a paper back to itself
+    pub fn find_citation_cycle(&self, paper_id_str: &str, caller: &UK) -> Result<Option<Vec<PaperId>>, PaperServiceError> {
+        let (paper, _) = self.get_paper(paper_id_str, caller)?;
+
+        CitationMetricsService::new(self.repository.clone()).find_cycle(&paper.id)
+            .map_err(|e| PaperServiceError::Rejected(e.to_string()))
+    }
+
+    // Citation metrics over the papers a user lead-authored or co-authored
+    pub fn get_author_metrics(&self, user_id_str: &str) -> Result<AuthorCitationMetrics, PaperServiceError> {
+        let user_key = self.resolve_user(user_id_str)?;
+        if !self.user_repository.contains(&user_key) {
+            return Err(PaperServiceError::UserNotFound(user_id_str.to_string()));
+        }
+
+        Ok(CitationMetricsService::new(self.repository.clone()).author_metrics(&user_key))
+    }
+
+    // Page of the network of public papers and their citations, around a paper if one is given
+    pub fn get_citation_network(&self, center: Option<(&str, u8)>, cursor: Option<&str>, caller: &UK) -> Result<CitationNetworkPage<UK>, PaperServiceError> {
+        let center = self.network_center(center, caller)?;
+        let after = cursor.map(Self::parse_paper_id).transpose()?;
+
+        let service = NetworkService::new(self.repository.clone());
+        Ok(service.citation_page(center.as_ref().map(|(id, hops)| (id, *hops)), after.as_ref()))
+    }
+
+    // Page of the network of authors of public papers, around the authors of a paper if one is given.
+    // Papers whose authors are hidden by a double-blind review are left out.
+    pub fn get_co_authorship_network(&self, center: Option<(&str, u8)>, cursor: Option<&str>, caller: &UK) -> Result<CoAuthorshipNetworkPage<UK>, PaperServiceError> {
+        let center = self.network_center(center, caller)?;
+        let after = cursor.map(Self::parse_paper_id).transpose()?;
+
+        let service = NetworkService::new(self.repository.clone());
+        Ok(service.co_authorship_page(center.as_ref().map(|(id, hops)| (id, *hops)), after.as_ref(), |id| !self.hides_authors(id)))
+    }
+
+    // The paper a network is centered on must be readable by the caller
+    fn network_center(&self, center: Option<(&str, u8)>, caller: &UK) -> Result<Option<(PaperId, u8)>, PaperServiceError> {
+        center.map(|(paper_id_str, hops)| {
+            let (paper, _) = self.get_paper(paper_id_str, caller)?;
+            Ok((paper.id, hops))
+        }).transpose()
+    }
+
+    // Page of the public papers matching a full-text query, best first.
+    // Filtering by lead author skips papers whose authors are hidden by a double-blind review.
+    // Overly long queries are rejected rather than cut, which would change what they match.
+    pub fn search_papers(
+        &self,
+        query: &str,
+        categories: &[PaperCategory],
+        tags: &[String],
+        lead_author: Option<&str>,
+        page: u32,
+    ) -> Result<SearchPage<UK>, PaperServiceError> {
+        if query.chars().count() > SearchService::<R>::MAX_QUERY_LENGTH {
+            return Err(PaperServiceError::Rejected(format!(
+                "Search queries are limited to {} characters", SearchService::<R>::MAX_QUERY_LENGTH,
+            )));
+        }
+        let lead_author = lead_author.map(|user_id_str| self.resolve_user(user_id_str)).transpose()?;
+        let filter = |paper: &PaperMetadata<UK>| {
+            (categories.is_empty() || paper.categories.iter().any(|category| categories.contains(category)))
+                && tags.iter().all(|tag| paper.tags.iter().any(|paper_tag| paper_tag.to_lowercase() == tag.to_lowercase()))
+                && lead_author.is_none_or(|author| paper.lead_author == author && !self.hides_authors(&paper.id))
+        };
+
+        Ok(SearchService::new(self.repository.clone()).search(query, filter, page))
+    }
+
+    // References of a paper that the caller cannot follow, flagged as deleted or unavailable
+    pub fn get_broken_references(&self, paper: &Paper<UK>, caller: &UK) -> Vec<BrokenReference> {
+        let service = ReferenceIntegrityService::new(self.repository.clone());
+        let deleted = service.dangling_references(paper);
+
+        paper.references.iter().enumerate().filter_map(|(index, reference)| {
+            let Citation::Paper(paper_id) = reference.citation else {
+                return None;
+            };
+            let reason = if deleted.contains(&(index, paper_id)) {
+                match self.repository.latest_version(&paper_id) {
+                    Some(_) => BrokenReferenceReason::RevisionDeleted,
+                    None => BrokenReferenceReason::Deleted,
+                }
+            } else if !self.can_read(&paper_id, caller) {
+                BrokenReferenceReason::Unavailable
+            } else {
+                return None;
+            };
+            Some(BrokenReference { index: index as u32, paper_id, reason })
+        }).collect()
+    }
+
+    // Papers the caller may edit whose references cannot all be followed
+    pub fn get_papers_with_broken_references(&self, caller: &UK) -> Vec<(Paper<UK>, Vec<BrokenReference>)> {
+        let Some(user) = self.user_repository.get_by_primary_key(caller) else {
+            return Vec::new();
+        };
+
+        user.lead_authored_papers.into_iter()
+            .chain(user.co_authored_papers)
+            .filter_map(|paper_id| self.repository.get(&paper_id))
+            .filter(|paper| self.authorize(paper, caller, PaperAction::Update).is_ok())
+            .filter_map(|paper| {
+                let broken = self.get_broken_references(&paper, caller);
+                (!broken.is_empty()).then_some((paper, broken))
+            })
+            .collect()
+    }
+
+    // Repairing the references of a paper to deleted papers.
+    // References to papers the caller cannot read are kept, as they may become readable again.
+    pub fn fix_broken_references(&mut self, user: UK, id_str: &str, seen_updated_at: u64) -> Result<u64, PaperServiceError> {
+        let id = Self::parse_paper_id(id_str)?;
+        let current = self.find_paper(&id)?;
+        self.authorize(&current, &user, PaperAction::Update)?;
+        // The repair is worked out from the paper as read here, which must be what the caller saw
+        if current.updated_at != seen_updated_at {
+            return Err(PaperServiceError::Conflict { paper_id: id.to_string(), updated_at: current.updated_at });
+        }
+
+        let service = ReferenceIntegrityService::new(self.repository.clone());
+        let Some(references) = service.repaired_references(&current) else {
+            return Ok(current.updated_at);
+        };
+
+        let changes = PaperChanges { references: Some(references), ..Default::default() };
+        self.patch_paper(user, id_str, changes, seen_updated_at)
+    }
+
+    // Rebuilding the citations of the next batch of papers from the references pointing at them
+    pub fn rebuild_citations(&mut self, cursor: Option<&str>) -> Result<(usize, Option<PaperId>), PaperServiceError> {
+        let after = cursor.map(Self::parse_paper_id).transpose()?;
+        Ok(CitationGraphService::new(self.repository.clone()).rebuild(after.as_ref(), CitationGraphService::<R>::BATCH_SIZE))
+    }
 }
@@ -0,0 +1,170 @@
+use domain::{
+    paper::{repository::PaperRepository, service::PaperService, PaperId, PaperStatus},
+    review::{
+        entity::model::Review,
+        repository::ReviewRepository,
+        service::ReviewService,
+        EditorialDecision, ReviewMode, ReviewRecommendation, ReviewReport, ReviewScore,
+    },
+    user::{repository::UserRepository, UserId, UserPrimaryKey},
+};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use util::time::now;
+
+pub struct ReviewUseCase<V: ReviewRepository, P: PaperRepository, U: UserRepository, UK: UserPrimaryKey> {
+    repository: V,
+    paper_repository: P,
+    user_repository: U,
+    _marker: PhantomData<UK>,
+}
+
+impl<V: ReviewRepository, P: PaperRepository, U: UserRepository, UK: UserPrimaryKey> ReviewUseCase<V, P, U, UK>
+where
+    V: ReviewRepository<UserPrimaryKey = UK> + Clone,
+    P: PaperRepository<UserPrimaryKey = UK> + Clone,
+    U: UserRepository<PrimaryKey = UK> + Clone,
+    UK: UserPrimaryKey,
+{
+    pub fn new(repository: V, paper_repository: P, user_repository: U) -> Self {
+        Self {
+            repository,
+            paper_repository,
+            user_repository,
+            _marker: PhantomData,
+        }
+    }
+
+    // Resolve a user from either a UserId or a `p_`-prefixed primary key
+    fn resolve_user(&self, user_id_str: &str) -> Result<UK, String> {
+        let key = if let Some(primary_key_str) = user_id_str.strip_prefix("p_") {
+            primary_key_str.parse().ok()
+        } else {
+            UserId::new(user_id_str).ok().and_then(|id| self.user_repository.get_primary_key(&id))
+        };
+
+        key.filter(|key| self.user_repository.contains(key))
+            .ok_or_else(|| format!("User not found: {}", user_id_str))
+    }
+
+    fn parse_paper_id(paper_id_str: &str) -> Result<PaperId, String> {
+        PaperId::from_str(paper_id_str)
+            .map_err(|e| format!("Invalid paper ID: {}", e))
+    }
+
+    // Authors, reviewers and editors can follow the review of a paper.
+    // Also returns whether the reviewers must be hidden from the caller.
+    pub fn get_review(&self, caller: UK, paper_id_str: &str) -> Result<(Review<UK>, bool), String> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let paper = self.paper_repository.get(&paper_id)
+            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+
+        let service = ReviewService::new(self.repository.clone());
+        let review = service.get(&paper_id).map_err(|e| e.to_string())?;
+
+        let is_author = paper.is_author(&caller);
+        if !is_author && !review.is_reviewer(&caller) && !service.is_editor(&caller) {
+            return Err("User is not allowed to view the review of this paper".to_string());
+        }
+
+        let hides_reviewers = is_author && review.hides_identities();
+        Ok((review, hides_reviewers))
+    }
+
+    // Reviews the caller has been assigned to, most recent paper first
+    pub fn get_assigned_reviews(&self, caller: UK) -> Vec<Review<UK>> {
+        let service = ReviewService::new(self.repository.clone());
+
+        service.assigned_papers(&caller)
+            .into_iter()
+            .rev()
+            .filter_map(|paper_id| service.get(&paper_id).ok())
+            .collect()
+    }
+
+    pub fn set_mode(&mut self, caller: UK, paper_id_str: &str, mode: ReviewMode) -> Result<(), String> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let paper = self.paper_repository.get(&paper_id)
+            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+
+        let mut service = ReviewService::new(self.repository.clone());
+        service.set_mode(&paper, mode, caller)
+            .map_err(|e| format!("Failed to change review mode: {}", e))
+    }
+
+    pub fn assign_reviewer(&mut self, caller: UK, paper_id_str: &str, reviewer_id_str: &str) -> Result<(), String> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let reviewer = self.resolve_user(reviewer_id_str)?;
+        let paper = self.paper_repository.get(&paper_id)
+            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+
+        let mut service = ReviewService::new(self.repository.clone());
+        service.assign_reviewer(&paper, reviewer, caller)
+            .map_err(|e| format!("Failed to assign reviewer: {}", e))
+    }
+
+    pub fn submit_report(
+        &mut self,
+        caller: UK,
+        paper_id_str: &str,
+        score: u8,
+        recommendation: ReviewRecommendation,
+        comments: String,
+    ) -> Result<(), String> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let score = ReviewScore::new(score)
+            .map_err(|e| format!("Invalid review score: {}", e))?;
+        let paper = self.paper_repository.get(&paper_id)
+            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+
+        let report = ReviewReport {
+            reviewer: caller,
+            score,
+            recommendation,
+            comments,
+            submitted_at: now(),
+        };
+
+        let mut service = ReviewService::new(self.repository.clone());
+        service.submit_report(&paper, report)
+            .map_err(|e| format!("Failed to submit review report: {}", e))
+    }
+
+    // Closing the review round and moving the paper accordingly
+    pub fn decide(&mut self, caller: UK, paper_id_str: &str, decision: EditorialDecision) -> Result<PaperStatus, String> {
+        let paper_id = Self::parse_paper_id(paper_id_str)?;
+        let paper = self.paper_repository.get(&paper_id)
+            .ok_or_else(|| format!("Paper not found: {}", paper_id))?;
+
+        // Check the paper can take the transition before the round is closed
+        paper.clone().transition(decision.transition())
+            .map_err(|e| format!("Failed to decide review: {}", e))?;
+
+        let mut service = ReviewService::new(self.repository.clone());
+        service.decide(&paper, decision, caller)
+            .map_err(|e| format!("Failed to decide review: {}", e))?;
+
+        let mut paper_service = PaperService::new(self.paper_repository.clone());
+        paper_service.transition_status(&paper_id, decision.transition())
+            .map_err(|e| format!("Failed to change paper status: {}", e))
+    }
+
+    pub fn add_editor(&mut self, user_id_str: &str) -> Result<bool, String> {
+        let user = self.resolve_user(user_id_str)?;
+
+        let mut service = ReviewService::new(self.repository.clone());
+        Ok(service.add_editor(user))
+    }
+
+    pub fn remove_editor(&mut self, user_id_str: &str) -> Result<bool, String> {
+        let user = self.resolve_user(user_id_str)?;
+
+        let mut service = ReviewService::new(self.repository.clone());
+        Ok(service.remove_editor(&user))
+    }
+
+    // Get a reference to the user repository
+    pub fn get_user_repository(&self) -> &U {
+        &self.user_repository
+    }
+}
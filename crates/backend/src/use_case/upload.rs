@@ -0,0 +1,50 @@
+use domain::{
+    paper::{
+        repository::UploadRepository,
+        upload::UploadError,
+        upload_service::UploadService,
+        value_object::content::RawFile,
+    },
+    user::UserPrimaryKey,
+};
+use std::marker::PhantomData;
+use util::time::now;
+
+pub struct UploadUseCase<L: UploadRepository, UK: UserPrimaryKey> {
+    repository: L,
+    _marker: PhantomData<UK>,
+}
+
+impl<L: UploadRepository, UK: UserPrimaryKey> UploadUseCase<L, UK>
+where
+    L: UploadRepository<UserPrimaryKey = UK> + Clone,
+    UK: UserPrimaryKey,
+{
+    pub fn new(repository: L) -> Self {
+        Self {
+            repository,
+            _marker: PhantomData,
+        }
+    }
+
+    // Opening an upload owned by the caller
+    pub fn begin_upload(&mut self, user: UK, name: &str, size: u64, sha256: &str) -> Result<u64, UploadError> {
+        let mut service = UploadService::new(self.repository.clone());
+        service.begin(user, name, size, sha256, now())
+    }
+
+    pub fn upload_chunk(&mut self, user: &UK, upload_id: u64, offset: u64, bytes: Vec<u8>) -> Result<u64, UploadError> {
+        let mut service = UploadService::new(self.repository.clone());
+        service.append(user, upload_id, offset, bytes, now())
+    }
+
+    // The uploaded file, once complete and matching its digest
+    pub fn get_uploaded_file(&self, user: &UK, upload_id: u64) -> Result<RawFile, UploadError> {
+        UploadService::new(self.repository.clone()).assemble(user, upload_id, now())
+    }
+
+    // Dropping an upload once its file has been attached or refused
+    pub fn close_upload(&mut self, upload_id: u64) {
+        UploadService::new(self.repository.clone()).finish(upload_id);
+    }
+}
@@ -5,31 +5,50 @@ use domain::{
         service::UserService,
         UserId, UserPrimaryKey, UserPrincipal
     },
-    paper::repository::PaperRepository,
+    paper::{repository::PaperRepository, PaperId, PaperStatus},
+    review::{repository::ReviewRepository, service::ReviewService},
 };
 use std::marker::PhantomData;
 use std::str::FromStr;
 
-pub struct UserUseCase<R: UserRepository, P: PaperRepository, K: UserPrimaryKey> {
+pub struct UserUseCase<R: UserRepository, P: PaperRepository, V: ReviewRepository, K: UserPrimaryKey> {
     repository: R,
     paper_repository: P,
+    review_repository: V,
     _marker: PhantomData<K>,
 }
 
-impl<R, P, K> UserUseCase<R, P, K>
+impl<R, P, V, K> UserUseCase<R, P, V, K>
 where
     R: UserRepository<PrimaryKey = UserPrincipal> + Clone,
     P: PaperRepository + Clone,
+    V: ReviewRepository<UserPrimaryKey = UserPrincipal> + Clone,
     K: UserPrimaryKey,
 {
-    pub fn new(repository: R, paper_repository: P) -> Self {
+    pub fn new(repository: R, paper_repository: P, review_repository: V) -> Self {
         Self {
             repository,
             paper_repository,
+            review_repository,
             _marker: PhantomData,
         }
     }
 
+    // Get user model as shown to other users, listing published papers only
+    pub fn get_public_user(&self, user_id: &str) -> Result<User, String> {
+        let mut user = self.get_user(user_id)?;
+
+        let review_service = ReviewService::new(self.review_repository.clone());
+        let is_listed = |paper: &PaperId| {
+            self.paper_repository.get_summary(paper).is_some_and(|summary| summary.status == PaperStatus::Published)
+                && !review_service.hides_identities(paper)
+        };
+        user.lead_authored_papers.retain(is_listed);
+        user.co_authored_papers.retain(is_listed);
+
+        Ok(user)
+    }
+
     // Get user model from user ID
     pub fn get_user(&self, user_id: &str) -> Result<User, String> {
         if let Some(principal_str) = user_id.strip_prefix("p_") {
@@ -72,7 +91,14 @@ where
     }
 
     // User Information Update
-    pub fn update_user(&mut self, principal: UserPrincipal, user: User) -> Result<(), String> {
+    pub fn update_user(&mut self, principal: UserPrincipal, mut user: User) -> Result<(), String> {
+        let current = self.repository.get_by_primary_key(&principal)
+            .ok_or_else(|| format!("User not found: {}", principal))?;
+
+        // Paper lists only change through paper creation and co-author invitations
+        user.lead_authored_papers = current.lead_authored_papers;
+        user.co_authored_papers = current.co_authored_papers;
+
         let mut user_repository = self.repository.clone();
         user_repository.update(&principal, user)
             .map_err(|e| e.to_string())
@@ -1,7 +1,11 @@
 pub mod paper;
+pub mod review;
 pub mod user;
 
 pub use paper::value_object::{id::*, title::*};
 pub use user::value_object::{id::*, name::*};
 #[cfg(feature = "canister")]
 pub use user::value_object::principal::*;
+
+#[cfg(test)]
+mod testing;
@@ -1,11 +1,44 @@
 #[cfg(feature = "dto")]
+pub mod bibliography;
+#[cfg(feature = "entity")]
+pub mod citation_graph;
+#[cfg(feature = "entity")]
+pub mod citation_metrics;
+#[cfg(feature = "dto")]
 pub mod entity;
+#[cfg(feature = "dto")]
+pub mod export;
+#[cfg(feature = "value-object")]
+pub mod import;
+#[cfg(feature = "value-object")]
+pub mod index;
+#[cfg(feature = "entity")]
+pub mod listing_service;
+#[cfg(feature = "entity")]
+pub mod network;
+#[cfg(feature = "value-object")]
+pub mod network_export;
+#[cfg(feature = "entity")]
+pub mod reference_integrity;
 #[cfg(feature = "entity")]
 pub mod repository;
+#[cfg(feature = "value-object")]
+pub mod search;
+#[cfg(feature = "entity")]
+pub mod search_service;
 #[cfg(feature = "entity")]
 pub mod service;
 #[cfg(feature = "value-object")]
+pub mod upload;
+#[cfg(feature = "entity")]
+pub mod upload_service;
+#[cfg(feature = "value-object")]
 pub mod value_object;
 
 #[cfg(feature = "value-object")]
 pub use value_object::*;
+
+/// Name under which papers of this platform are published, which also prefixes
+/// their keys in exported bibliographies
+#[cfg(feature = "value-object")]
+pub(crate) const PUBLISHER: &str = "arche";
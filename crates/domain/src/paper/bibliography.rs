@@ -0,0 +1,335 @@
+use crate::paper::{
+    entity::dto::Paper,
+    export::{Record, RecordKind},
+    CitationStyle, PaperId, Reference,
+};
+
+impl CitationStyle {
+    /// Renders a paper as an entry of a bibliography
+    pub fn format_paper(&self, paper: &Paper) -> String {
+        self.format(&Record::from_paper(paper), None)
+    }
+
+    /// Renders the references of a paper as a bibliography, one entry per reference.
+    ///
+    /// `resolve` looks up the papers of this platform that are referenced, as for
+    /// [`crate::paper::CitationFormat::export_references`]. IEEE entries are numbered in
+    /// citation order, while APA and Chicago entries are sorted alphabetically.
+    pub fn format_references(&self, references: &[Reference], resolve: impl Fn(&PaperId) -> Option<Paper>) -> Vec<String> {
+        let mut entries: Vec<_> = references.iter()
+            .enumerate()
+            .map(|(index, reference)| self.format(&Record::from_reference(index, reference, &resolve), Some(index + 1)))
+            .collect();
+
+        if self.is_alphabetical() {
+            entries.sort_by_key(|entry| entry.to_lowercase());
+        }
+        entries
+    }
+
+    fn format(&self, record: &Record, number: Option<usize>) -> String {
+        match self {
+            CitationStyle::Apa => format_apa(record),
+            CitationStyle::Ieee => {
+                let entry = format_ieee(record);
+                match number {
+                    Some(number) => format!("[{}] {}", number, entry),
+                    None => entry,
+                }
+            }
+            CitationStyle::Chicago => format_chicago(record),
+        }
+    }
+}
+
+/// `Author, A. A., & Author, B. B. (Year). Title (No. 1). Publisher. URL`
+fn format_apa(record: &Record) -> String {
+    if let Some(entry) = format_bare(record) {
+        return entry;
+    }
+
+    let year = format!("({}).", year(record).unwrap_or_else(|| "n.d.".to_string()));
+    let title = record.title.as_deref().map(|title| match report_number(record) {
+        Some(number) => sentence(&format!("{} (No. {})", title, number)),
+        None => sentence(title),
+    });
+
+    let mut parts = Vec::new();
+    if record.authors.is_empty() {
+        parts.extend(title);
+        parts.push(year);
+    } else {
+        let names: Vec<_> = record.authors.iter().map(|author| {
+            let name = Name::parse(author);
+            if name.given.is_empty() { name.family.to_string() } else { format!("{}, {}", name.family, name.initials()) }
+        }).collect();
+        parts.push(sentence(&join_names(&names, ", & ", ", & ")));
+        parts.push(year);
+        parts.extend(title);
+    }
+    parts.extend(record.publisher.as_deref().map(sentence));
+    parts.extend(record.url.clone());
+    parts.join(" ")
+}
+
+/// `A. Author and B. Author, "Title," Publisher, no. 1, Year. [Online]. Available: URL`
+///
+/// The number in brackets is added by the caller.
+fn format_ieee(record: &Record) -> String {
+    if let Some(entry) = format_bare(record) {
+        return entry;
+    }
+
+    let names: Vec<_> = record.authors.iter().map(|author| {
+        let name = Name::parse(author);
+        if name.given.is_empty() { name.family.to_string() } else { format!("{} {}", name.initials(), name.family) }
+    }).collect();
+    // IEEE abbreviates lists of more than six authors
+    let mut entry = if names.len() > 6 {
+        format!("{} et al.", names[0])
+    } else {
+        join_names(&names, " and ", ", and ")
+    };
+
+    let details: Vec<_> = record.publisher.clone().into_iter()
+        .chain(report_number(record).map(|number| format!("no. {}", number)))
+        .chain(year(record))
+        .collect();
+    let details = details.join(", ");
+
+    match &record.title {
+        Some(title) => {
+            if !entry.is_empty() {
+                entry.push_str(", ");
+            }
+            if record.kind == RecordKind::Book {
+                entry.push_str(&sentence(title));
+                if !details.is_empty() {
+                    entry.push(' ');
+                    entry.push_str(&sentence(&details));
+                }
+            } else if details.is_empty() {
+                entry.push_str(&format!("\"{}\"", sentence(title)));
+            } else {
+                entry.push_str(&format!("\"{},\" {}.", title.trim_end_matches('.'), details));
+            }
+        }
+        None => {
+            if !details.is_empty() {
+                entry.push_str(", ");
+                entry.push_str(&details);
+            }
+            entry = sentence(&entry);
+        }
+    }
+
+    if let Some(url) = &record.url {
+        entry.push_str(&format!(" [Online]. Available: {}", url));
+    }
+    entry
+}
+
+/// `Author, Aa, and Bb Author. Year. "Title." No. 1. Publisher. URL.`
+fn format_chicago(record: &Record) -> String {
+    if let Some(entry) = format_bare(record) {
+        return entry;
+    }
+
+    let year = sentence(&year(record).unwrap_or_else(|| "n.d.".to_string()));
+    // Only titles of works contained in a larger one go in quotation marks
+    let title = record.title.as_deref().map(|title| match record.kind {
+        RecordKind::Paper | RecordKind::Article => format!("\"{}\"", sentence(title)),
+        _ => sentence(title),
+    });
+
+    let mut parts = Vec::new();
+    if record.authors.is_empty() {
+        parts.extend(title);
+        parts.push(year);
+    } else {
+        // Only the first author is inverted, as the bibliography is sorted by it
+        let names: Vec<_> = record.authors.iter().enumerate().map(|(index, author)| {
+            let name = Name::parse(author);
+            match (index, name.given.is_empty()) {
+                (_, true) => name.family.to_string(),
+                (0, false) => format!("{}, {}", name.family, name.given.join(" ")),
+                (_, false) => format!("{} {}", name.given.join(" "), name.family),
+            }
+        }).collect();
+        // The comma closing an inverted name also separates a pair of authors
+        let inverted = names.first().is_some_and(|name| name.contains(','));
+        let pair = if inverted { ", and " } else { " and " };
+        parts.push(sentence(&join_names(&names, pair, ", and ")));
+        parts.push(year);
+        parts.extend(title);
+    }
+    parts.extend(report_number(record).map(|number| format!("No. {}.", number)));
+    parts.extend(record.publisher.as_deref().map(sentence));
+    parts.extend(record.url.as_deref().map(|url| format!("{}.", url)));
+    parts.join(" ")
+}
+
+/// Renders works known only by a note or a link, which no style has rules for
+fn format_bare(record: &Record) -> Option<String> {
+    if record.title.is_some() || !record.authors.is_empty() {
+        return None;
+    }
+
+    let parts: Vec<_> = record.note.as_deref().map(sentence).into_iter()
+        .chain(record.url.clone())
+        .collect();
+    Some(parts.join(" "))
+}
+
+fn year(record: &Record) -> Option<String> {
+    record.issued.map(|(year, _, _)| year.to_string())
+}
+
+/// Returns the number of a report, unless its title already names it
+fn report_number(record: &Record) -> Option<&str> {
+    let number = record.number.as_deref().filter(|_| record.kind == RecordKind::Report)?;
+    let named = record.title.as_deref().is_some_and(|title| title.contains(number));
+    (!named).then_some(number)
+}
+
+/// Ends a part of an entry with a period unless it already ends with punctuation
+fn sentence(text: &str) -> String {
+    let text = text.trim();
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{}.", text)
+    }
+}
+
+/// Joins names with `pair` between two of them, or commas and `last` before the last of more
+fn join_names(names: &[String], pair: &str, last: &str) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [first, second] => format!("{}{}{}", first, pair, second),
+        [rest @ .., final_name] => format!("{}{}{}", rest.join(", "), last, final_name),
+    }
+}
+
+/// A personal name split into family and given names.
+///
+/// Names are either written `Given Family` or `Family, Given`. Single-word names,
+/// such as user names, are kept whole as the family name.
+struct Name<'a> {
+    family: &'a str,
+    given: Vec<&'a str>,
+}
+
+impl<'a> Name<'a> {
+    fn parse(name: &'a str) -> Self {
+        if let Some((family, given)) = name.split_once(',') {
+            return Name { family: family.trim(), given: given.split_whitespace().collect() };
+        }
+
+        let mut parts: Vec<_> = name.split_whitespace().collect();
+        let family = parts.pop().unwrap_or_default();
+        Name { family, given: parts }
+    }
+
+    /// Returns the given names as initials, such as `D. E.`
+    fn initials(&self) -> String {
+        self.given.iter()
+            .filter_map(|given| given.chars().next())
+            .map(|initial| format!("{}.", initial))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{paper::{Citation, Isbn, PaperContents, PaperStatus, PaperTitle, RfcNumber, WorkMetadata}, user::UserName};
+    use std::str::FromStr;
+
+    fn paper() -> Paper {
+        Paper {
+            id: "2025-01-0001".to_string(),
+            lead_author: ("ada".to_string(), UserName::new("Ada Lovelace").unwrap()),
+            co_authors: vec![("charles".to_string(), UserName::new("Charles Babbage").unwrap())],
+            title: PaperTitle::new("Notes on the Analytical Engine").unwrap(),
+            ab: String::new(),
+            content: PaperContents::default(),
+            categories: Vec::new(),
+            tags: Vec::new(),
+            status: PaperStatus::Published,
+            // 2025-01-15T00:00:00Z
+            created_at: 1_736_899_200_000_000_000,
+            updated_at: 1_736_899_200_000_000_000,
+            cover_image: None,
+            references: Vec::new(),
+            citations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_paper() {
+        assert_eq!(
+            CitationStyle::Apa.format_paper(&paper()),
+            "Lovelace, A., & Babbage, C. (2025). Notes on the Analytical Engine. arche.",
+        );
+        assert_eq!(
+            CitationStyle::Ieee.format_paper(&paper()),
+            "A. Lovelace and C. Babbage, \"Notes on the Analytical Engine,\" arche, 2025.",
+        );
+        assert_eq!(
+            CitationStyle::Chicago.format_paper(&paper()),
+            "Lovelace, Ada, and Charles Babbage. 2025. \"Notes on the Analytical Engine.\" arche.",
+        );
+    }
+
+    #[test]
+    fn test_format_references() {
+        let cited = PaperId::from_str("2025-01-0001").unwrap();
+        let work = WorkMetadata {
+            title: Some("The Art of Computer Programming".to_string()),
+            authors: vec!["Knuth, Donald E.".to_string()],
+            year: Some(1968),
+        };
+        let references = vec![
+            Reference::new(Citation::Isbn { isbn: Isbn::new("978-0-201-89683-1").unwrap(), work }),
+            Reference::new(Citation::Rfc { number: RfcNumber::new("RFC 9110").unwrap(), work: WorkMetadata::default() }),
+            Reference::new(Citation::Paper(cited)),
+            Reference::new(Citation::Paper(PaperId::from_str("2025-02-0002").unwrap())),
+            Reference::new(Citation::Other("Personal communication".to_string())),
+        ];
+        let resolve = |id: &PaperId| (*id == cited).then(paper);
+
+        assert_eq!(CitationStyle::Ieee.format_references(&references, resolve), vec![
+            "[1] D. E. Knuth, The Art of Computer Programming. 1968. [Online]. Available: https://openlibrary.org/isbn/9780201896831",
+            "[2] \"RFC 9110,\" IETF. [Online]. Available: https://www.rfc-editor.org/rfc/rfc9110",
+            "[3] A. Lovelace and C. Babbage, \"Notes on the Analytical Engine,\" arche, 2025.",
+            "[4] Unavailable arche paper 2025-02-0002.",
+            "[5] Personal communication.",
+        ]);
+
+        assert_eq!(CitationStyle::Apa.format_references(&references, resolve), vec![
+            "Knuth, D. E. (1968). The Art of Computer Programming. https://openlibrary.org/isbn/9780201896831",
+            "Lovelace, A., & Babbage, C. (2025). Notes on the Analytical Engine. arche.",
+            "Personal communication.",
+            "RFC 9110. (n.d.). IETF. https://www.rfc-editor.org/rfc/rfc9110",
+            "Unavailable arche paper 2025-02-0002.",
+        ]);
+
+        let chicago = CitationStyle::Chicago.format_references(&references, resolve);
+        assert_eq!(chicago[0], "Knuth, Donald E. 1968. The Art of Computer Programming. https://openlibrary.org/isbn/9780201896831.");
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Name::parse("Grace Brewster Murray Hopper");
+        assert_eq!((name.family, name.initials().as_str()), ("Hopper", "G. B. M."));
+
+        let name = Name::parse("alice");
+        assert_eq!((name.family, name.given.len()), ("alice", 0));
+
+        let names = ["A".to_string(), "B".to_string(), "C".to_string()];
+        assert_eq!(join_names(&names, " and ", ", and "), "A, B, and C");
+    }
+}
@@ -0,0 +1,209 @@
+use crate::paper::{entity::model::Paper, repository::PaperRepository, Citation, CitationContext, PaperId};
+use std::collections::BTreeSet;
+
+/// Keeps `Paper::citations` in step with the references of the papers citing it.
+///
+/// A paper only counts as citing another while it is visible to the public, so
+/// publishing, unpublishing and deleting a paper all update the papers it references.
+pub struct CitationGraphService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> CitationGraphService<R>
+where
+    R: PaperRepository,
+{
+    /// Revisions visited by one call to `rebuild` from the canister
+    pub const BATCH_SIZE: usize = 100;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns the papers whose citations should list `paper`
+    pub fn cited_papers(paper: &Paper<R::UserPrimaryKey>) -> BTreeSet<PaperId> {
+        if paper.status.is_public() {
+            paper.referenced_papers()
+        } else {
+            BTreeSet::new()
+        }
+    }
+
+    /// Returns the papers whose citations should list the stored revision `paper_id`,
+    /// read from the repository indexes
+    pub fn stored_cited_papers(&self, paper_id: &PaperId) -> BTreeSet<PaperId> {
+        if self.repository.get_summary(paper_id).is_some_and(|summary| summary.status.is_public()) {
+            self.repository.iter_referenced(paper_id).collect()
+        } else {
+            BTreeSet::new()
+        }
+    }
+
+    /// Returns how `citing` cites any revision of `cited`, once per reference to it.
+    ///
+    /// The references are read from the latest revision of `citing` listed in the
+    /// citations of `cited`, so only public revisions are considered.
+    pub fn citation_contexts(&self, citing: &PaperId, cited: &PaperId) -> Vec<CitationContext> {
+        let latest = self.repository.iter_versions(cited)
+            .flat_map(|version| self.repository.iter_citing(&version).collect::<Vec<_>>())
+            .filter(|revision| revision.is_same_paper(citing))
+            .max_by_key(|revision| revision.version());
+        let Some(paper) = latest.and_then(|revision| self.repository.get(&revision)) else {
+            return Vec::new();
+        };
+
+        paper.references.into_iter()
+            .filter(|reference| matches!(reference.citation, Citation::Paper(id) if id.is_same_paper(cited)))
+            .map(|reference| reference.context)
+            .collect()
+    }
+
+    /// Updates the papers cited by `citing` after its cited papers changed from `before` to `after`.
+    pub fn sync(&mut self, citing: &PaperId, before: &BTreeSet<PaperId>, after: &BTreeSet<PaperId>) {
+        for cited in before.difference(after) {
+            self.update_citations(cited, |citations| {
+                citations.retain(|citation| citation != &Citation::Paper(*citing));
+            });
+        }
+        for cited in after.difference(before) {
+            self.update_citations(cited, |citations| {
+                if !citations.contains(&Citation::Paper(*citing)) {
+                    citations.push(Citation::Paper(*citing));
+                }
+            });
+        }
+    }
+
+    /// Rebuilds the citations of the next `limit` revisions after `after` from the
+    /// references pointing at them, and adds each public one among them to the
+    /// citations of the papers it references.
+    ///
+    /// Running batches until no cursor is returned rebuilds the whole graph.
+    /// Returns the number of papers whose citations had to be corrected, and the
+    /// revision to continue after, absent once every revision was visited.
+    pub fn rebuild(&mut self, after: Option<&PaperId>, limit: usize) -> (usize, Option<PaperId>) {
+        let batch: Vec<_> = self.repository.iter_ids(after, limit).collect();
+
+        let mut corrected = 0;
+        for paper_id in &batch {
+            let Some(mut paper) = self.repository.get(paper_id) else {
+                continue;
+            };
+
+            let citations: Vec<_> = paper.citations.iter()
+                .filter(|citation| match citation {
+                    Citation::Paper(citing) => self.stored_cited_papers(citing).contains(paper_id),
+                    _ => true,
+                })
+                .cloned()
+                .collect();
+            if citations != paper.citations {
+                paper.citations = citations;
+                self.repository.insert(*paper_id, paper);
+                corrected += 1;
+            }
+
+            for cited in self.stored_cited_papers(paper_id) {
+                let mut added = false;
+                self.update_citations(&cited, |citations| {
+                    if !citations.contains(&Citation::Paper(*paper_id)) {
+                        citations.push(Citation::Paper(*paper_id));
+                        added = true;
+                    }
+                });
+                corrected += added as usize;
+            }
+        }
+
+        let next = if batch.len() < limit { None } else { batch.last().copied() };
+        (corrected, next)
+    }
+
+    fn update_citations(&mut self, paper_id: &PaperId, update: impl FnOnce(&mut Vec<Citation>)) {
+        // References may point at papers that no longer exist
+        let Some(mut paper) = self.repository.get(paper_id) else {
+            return;
+        };
+
+        let before = paper.citations.len();
+        update(&mut paper.citations);
+        if paper.citations.len() != before {
+            self.repository.insert(*paper_id, paper);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        paper::{PaperStatus, Reference},
+        testing::{id, paper, MemoryPaperRepository},
+    };
+
+    fn citing(number: u32, status: PaperStatus, cited: &[u32]) -> Paper<u8> {
+        let mut paper = paper(id(number), 1, status);
+        paper.references = cited.iter().map(|cited| Reference::new(Citation::Paper(id(*cited)))).collect();
+        paper
+    }
+
+    fn citations(repository: &MemoryPaperRepository, number: u32) -> Vec<Citation> {
+        repository.get(&id(number)).unwrap().citations
+    }
+
+    #[test]
+    fn test_sync() {
+        let repository = MemoryPaperRepository::with([
+            paper(id(1), 1, PaperStatus::Published),
+            paper(id(2), 1, PaperStatus::Published),
+        ]);
+        let mut service = CitationGraphService::new(repository.clone());
+
+        service.sync(&id(3), &BTreeSet::new(), &BTreeSet::from([id(1), id(2), id(9)]));
+        assert_eq!(citations(&repository, 1), vec![Citation::Paper(id(3))]);
+        assert_eq!(citations(&repository, 2), vec![Citation::Paper(id(3))]);
+
+        // Adding a citation that is already listed does not duplicate it
+        service.sync(&id(3), &BTreeSet::new(), &BTreeSet::from([id(1)]));
+        assert_eq!(citations(&repository, 1), vec![Citation::Paper(id(3))]);
+
+        service.sync(&id(3), &BTreeSet::from([id(1), id(2)]), &BTreeSet::from([id(2)]));
+        assert!(citations(&repository, 1).is_empty());
+        assert_eq!(citations(&repository, 2), vec![Citation::Paper(id(3))]);
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let mut stale = paper(id(1), 1, PaperStatus::Published);
+        stale.citations = vec![Citation::Paper(id(3)), Citation::Paper(id(9))];
+        let repository = MemoryPaperRepository::with([
+            stale,
+            paper(id(2), 1, PaperStatus::Published),
+            citing(3, PaperStatus::Draft, &[1]),
+            citing(4, PaperStatus::Published, &[1, 2]),
+            citing(5, PaperStatus::Published, &[2]),
+        ]);
+        let mut service = CitationGraphService::new(repository.clone());
+
+        let mut batches = 0;
+        let mut after = None;
+        loop {
+            let (_, next) = service.rebuild(after.as_ref(), 2);
+            batches += 1;
+            match next {
+                Some(next) => after = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(batches, 3);
+        assert_eq!(citations(&repository, 1), vec![Citation::Paper(id(4))]);
+        assert_eq!(citations(&repository, 2), vec![Citation::Paper(id(4)), Citation::Paper(id(5))]);
+        assert!(citations(&repository, 3).is_empty());
+
+        // A consistent graph needs no corrections
+        assert_eq!(service.rebuild(None, 10), (0, None));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::paper::{repository::PaperRepository, AuthorCitationMetrics, PaperId};
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, VecDeque};
+use thiserror::Error;
+
+/// Error returned when a walk of the citation graph gives up before reaching an answer
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Gave up looking for a citation cycle after visiting {0} papers")]
+pub struct CycleSearchExhausted(pub usize);
+
+/// Analytics over the citation graph.
+///
+/// Metrics are counted per paper rather than per revision: citations of any revision
+/// count towards the paper, and a citing paper counts once however many of its
+/// revisions cite it. Papers are identified by their first revision. Queries only walk
+/// the citation indexes kept up to date by the repository, so no paper is decoded.
+pub struct CitationMetricsService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> CitationMetricsService<R>
+where
+    R: PaperRepository,
+{
+    /// Maximum depth of transitive citation queries
+    pub const MAX_HOPS: u8 = 5;
+
+    /// Maximum number of papers a search for a citation cycle visits
+    pub const MAX_CYCLE_VISITS: usize = 1000;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns the papers citing any revision of the paper
+    pub fn citing_papers(&self, paper_id: &PaperId) -> BTreeSet<PaperId> {
+        self.repository.iter_versions(paper_id)
+            .flat_map(|version| self.repository.iter_citing(&version).collect::<Vec<_>>())
+            .filter(|citing| !citing.is_same_paper(paper_id))
+            .map(|citing| citing.first_version())
+            .collect()
+    }
+
+    pub fn citation_count(&self, paper_id: &PaperId) -> u32 {
+        self.citing_papers(paper_id).len() as u32
+    }
+
+    /// Returns the papers citing the paper directly or through a chain of at most
+    /// `hops` citations, with the length of the shortest such chain.
+    ///
+    /// `hops` is capped at [`Self::MAX_HOPS`].
+    pub fn cited_by_within(&self, paper_id: &PaperId, hops: u8) -> BTreeMap<PaperId, u8> {
+        let hops = hops.min(Self::MAX_HOPS);
+        let origin = paper_id.first_version();
+
+        let mut distances = BTreeMap::new();
+        let mut queue = VecDeque::from([(origin, 0)]);
+        while let Some((paper, distance)) = queue.pop_front() {
+            if distance == hops {
+                continue;
+            }
+            for citing in self.citing_papers(&paper) {
+                if citing != origin && !distances.contains_key(&citing) {
+                    distances.insert(citing, distance + 1);
+                    queue.push_back((citing, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Finds the shortest chain of citations leading from the paper back to itself.
+    ///
+    /// The returned papers start with the paper itself; each one cites the next,
+    /// and the last one cites the first. The search visits at most [`Self::MAX_CYCLE_VISITS`]
+    /// papers, and fails rather than walk further.
+    pub fn find_cycle(&self, paper_id: &PaperId) -> Result<Option<Vec<PaperId>>, CycleSearchExhausted> {
+        let origin = paper_id.first_version();
+
+        // Walk "cited by" links, which close a cycle exactly when references do
+        let mut cited: BTreeMap<PaperId, PaperId> = BTreeMap::new();
+        let mut queue = VecDeque::from([origin]);
+        let mut visits = 0;
+        while let Some(paper) = queue.pop_front() {
+            if visits == Self::MAX_CYCLE_VISITS {
+                return Err(CycleSearchExhausted(visits));
+            }
+            visits += 1;
+            for citing in self.citing_papers(&paper) {
+                if citing == origin {
+                    let mut cycle = vec![origin];
+                    let mut current = paper;
+                    while current != origin {
+                        cycle.push(current);
+                        current = cited[&current];
+                    }
+                    return Ok(Some(cycle));
+                }
+                if let Entry::Vacant(entry) = cited.entry(citing) {
+                    entry.insert(paper);
+                    queue.push_back(citing);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the citation metrics of an author over the papers they wrote,
+    /// which the repository keeps up to date
+    pub fn author_metrics(&self, user: &R::UserPrimaryKey) -> AuthorCitationMetrics {
+        self.repository.get_author_metrics(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        paper::{entity::model::Paper, Citation, PaperStatus},
+        testing::{id, paper, MemoryPaperRepository},
+    };
+
+    /// Returns paper `number` listing `citing` in its citations
+    fn cited_by(number: u32, citing: &[u32]) -> Paper<u8> {
+        let mut paper = paper(id(number), number as u8, PaperStatus::Published);
+        paper.citations = citing.iter().map(|citing| Citation::Paper(id(*citing))).collect();
+        paper
+    }
+
+    #[test]
+    fn test_cited_by_within() {
+        // 2 cites 1, 3 cites 2, ..., 8 cites 7, and 3 also cites 1 directly
+        let repository = MemoryPaperRepository::with([
+            cited_by(1, &[2, 3]),
+            cited_by(2, &[3]),
+            cited_by(3, &[4]),
+            cited_by(4, &[5]),
+            cited_by(5, &[6]),
+            cited_by(6, &[7]),
+            cited_by(7, &[8]),
+            cited_by(8, &[]),
+        ]);
+        let service = CitationMetricsService::new(repository);
+
+        assert!(service.cited_by_within(&id(1), 0).is_empty());
+        assert_eq!(service.cited_by_within(&id(1), 1), BTreeMap::from([(id(2), 1), (id(3), 1)]));
+        assert_eq!(service.cited_by_within(&id(1), 2), BTreeMap::from([(id(2), 1), (id(3), 1), (id(4), 2)]));
+
+        // Hops beyond the maximum are capped
+        let within = service.cited_by_within(&id(1), u8::MAX);
+        assert_eq!(within.len(), 6);
+        assert_eq!(within.get(&id(7)), Some(&CitationMetricsService::<MemoryPaperRepository>::MAX_HOPS));
+        assert!(!within.contains_key(&id(8)));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 1 cites 3, 3 cites 2 and 2 cites 1, while 4 only cites 1
+        let repository = MemoryPaperRepository::with([
+            cited_by(1, &[2, 4]),
+            cited_by(2, &[3]),
+            cited_by(3, &[1]),
+            cited_by(4, &[]),
+        ]);
+        let service = CitationMetricsService::new(repository);
+
+        assert_eq!(service.find_cycle(&id(1)), Ok(Some(vec![id(1), id(3), id(2)])));
+        assert_eq!(service.find_cycle(&id(2)), Ok(Some(vec![id(2), id(1), id(3)])));
+        assert_eq!(service.find_cycle(&id(4)), Ok(None));
+    }
+
+    #[test]
+    fn test_find_cycle_gives_up() {
+        // A chain of citations longer than the search may walk, with no cycle
+        const MAX: usize = CitationMetricsService::<MemoryPaperRepository>::MAX_CYCLE_VISITS;
+        let chain = |length: u32| MemoryPaperRepository::with((1..=length).map(|number| {
+            let citing: &[u32] = if number < length { &[number + 1] } else { &[] };
+            cited_by(number, citing)
+        }));
+
+        let service = CitationMetricsService::new(chain(MAX as u32));
+        assert_eq!(service.find_cycle(&id(1)), Ok(None));
+        let service = CitationMetricsService::new(chain(MAX as u32 + 1));
+        assert_eq!(service.find_cycle(&id(1)), Err(CycleSearchExhausted(MAX)));
+    }
+
+    #[test]
+    fn test_find_cycle_across_revisions() {
+        // 2 cites the second revision of 1, while the first revision cites 2
+        let revision = PaperId::new(0, 1, 2).unwrap();
+        let mut second = paper(revision, 1, PaperStatus::Published);
+        second.citations = vec![Citation::Paper(id(2))];
+        let repository = MemoryPaperRepository::with([cited_by(1, &[]), second, cited_by(2, &[1])]);
+        let service = CitationMetricsService::new(repository);
+
+        assert_eq!(service.find_cycle(&revision), Ok(Some(vec![id(1), id(2)])));
+        assert_eq!(service.citation_count(&id(1)), 1);
+    }
+}
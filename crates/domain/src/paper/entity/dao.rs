@@ -3,9 +3,22 @@ use serde::{Deserialize, Serialize};
 use crate::user::UserPrimaryKey;
 
 mod v1;
-pub(crate) use v1::V1;
+mod v2;
+mod v3;
+mod v4;
+mod v5;
+mod v6;
+pub(crate) use v1::{CitationV1, V1};
+pub(crate) use v2::V2;
+pub(crate) use v3::V3;
+pub(crate) use v4::V4;
+pub(crate) use v5::{CitationV5, ReferenceV5, V5};
+pub(crate) use v6::V6;
 
-/// Represents a technical paper in the system
+/// Represents a technical paper in the system.
+///
+/// Versions up to V5 hold the contents of the paper inline; from V6 on they are
+/// stored apart, so reading the metadata of a paper does not decode its PDF.
 #[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
 pub struct PaperDao<K: UserPrimaryKey> {
     pub(crate) version: PaperDaoVersion<K>,
@@ -36,4 +49,9 @@ mod ic_stable {
 #[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
 pub(crate) enum PaperDaoVersion<K: UserPrimaryKey> {
     V1(V1<K>),
+    V2(V2<K>),
+    V3(V3<K>),
+    V4(V4<K>),
+    V5(V5<K>),
+    V6(V6<K>),
 }
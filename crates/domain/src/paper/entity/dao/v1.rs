@@ -1,4 +1,4 @@
-use crate::{paper::{PaperCategory, PaperStatus, PaperTitle, Citation, PaperContents}, user::UserPrimaryKey};
+use crate::{paper::{Citation, PaperId, PaperCategory, PaperStatus, PaperTitle, PaperContents}, user::UserPrimaryKey};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -27,7 +27,25 @@ pub struct V1<K: UserPrimaryKey> {
     /// Optional URL to cover image in
     pub cover_image: Option<String>,
     /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
-    pub references: Vec<Citation>,
+    pub references: Vec<CitationV1>,
     /// List of identifiers for all papers that have cited this paper.
-    pub citations: Vec<Citation>,
+    pub citations: Vec<CitationV1>,
+}
+
+/// Citation as stored by V1 to V3, before external works could be identified
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub enum CitationV1 {
+    Paper(PaperId),
+    Url(String),
+    Other(String),
+}
+
+impl From<CitationV1> for Citation {
+    fn from(citation: CitationV1) -> Self {
+        match citation {
+            CitationV1::Paper(id) => Citation::Paper(id),
+            CitationV1::Url(url) => Citation::Url(url),
+            CitationV1::Other(text) => Citation::Other(text),
+        }
+    }
 }
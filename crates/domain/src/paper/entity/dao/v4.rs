@@ -0,0 +1,96 @@
+use crate::{paper::{ArxivId, Citation, CitationContext, CoAuthorRole, Doi, GitHubRepository, Isbn, PaperCategory, PaperContents, PaperId, PaperStatus, PaperTitle, Reference, RfcNumber, WorkMetadata}, user::UserPrimaryKey};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct V4<K: UserPrimaryKey> {
+    /// The lead author of the paper
+    pub lead_author: K,
+    /// Co-authors of the paper, if any
+    pub co_authors: Vec<K>,
+    /// Users invited as co-authors who have not answered yet
+    pub invited_co_authors: Vec<K>,
+    /// Roles of co-authors and invitees; users missing here are editors
+    pub co_author_roles: Vec<(K, CoAuthorRole)>,
+    /// Title of the paper
+    pub title: PaperTitle,
+    /// Abstract of the paper
+    pub ab: String,
+    /// Main content of the paper
+    pub content: PaperContents,
+    /// Categories this paper belongs to
+    pub categories: Vec<PaperCategory>,
+    /// Tags for better searchability
+    pub tags: Vec<String>,
+    /// Current status of the paper
+    pub status: PaperStatus,
+    /// When the paper was created
+    pub created_at: u64,
+    /// When the paper was last updated in nanoseconds since epoch
+    pub updated_at: u64,
+    /// Optional URL to cover image in
+    pub cover_image: Option<String>,
+    /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
+    pub references: Vec<CitationV4>,
+    /// List of identifiers for all papers that have cited this paper.
+    pub citations: Vec<CitationV4>,
+}
+
+/// Bibliographic details as stored by V4, which kept the locator with the cited work
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct WorkMetadataV4 {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+    pub locator: Option<String>,
+}
+
+/// Citation as stored by V4, before references carried a context
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub enum CitationV4 {
+    Paper(PaperId),
+    Url(String),
+    Other(String),
+    Doi { doi: Doi, work: WorkMetadataV4 },
+    Arxiv { id: ArxivId, work: WorkMetadataV4 },
+    Isbn { isbn: Isbn, work: WorkMetadataV4 },
+    Rfc { number: RfcNumber, work: WorkMetadataV4 },
+    GitHub { repository: GitHubRepository, work: WorkMetadataV4 },
+}
+
+impl From<CitationV4> for Reference {
+    fn from(citation: CitationV4) -> Self {
+        // The locator now belongs to the context of the reference
+        let split = |work: WorkMetadataV4| (
+            WorkMetadata { title: work.title, authors: work.authors, year: work.year },
+            CitationContext { locator: work.locator, ..Default::default() },
+        );
+
+        let (citation, context) = match citation {
+            CitationV4::Paper(id) => (Citation::Paper(id), CitationContext::default()),
+            CitationV4::Url(url) => (Citation::Url(url), CitationContext::default()),
+            CitationV4::Other(text) => (Citation::Other(text), CitationContext::default()),
+            CitationV4::Doi { doi, work } => {
+                let (work, context) = split(work);
+                (Citation::Doi { doi, work }, context)
+            }
+            CitationV4::Arxiv { id, work } => {
+                let (work, context) = split(work);
+                (Citation::Arxiv { id, work }, context)
+            }
+            CitationV4::Isbn { isbn, work } => {
+                let (work, context) = split(work);
+                (Citation::Isbn { isbn, work }, context)
+            }
+            CitationV4::Rfc { number, work } => {
+                let (work, context) = split(work);
+                (Citation::Rfc { number, work }, context)
+            }
+            CitationV4::GitHub { repository, work } => {
+                let (work, context) = split(work);
+                (Citation::GitHub { repository, work }, context)
+            }
+        };
+        Reference { citation, context }
+    }
+}
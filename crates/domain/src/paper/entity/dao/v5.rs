@@ -0,0 +1,166 @@
+use crate::{paper::{ArxivId, Citation, CitationContext, CitationIntent, CoAuthorRole, Doi, GitHubRepository, Isbn, PaperCategory, PaperContents, PaperId, PaperStatus, PaperTitle, Reference, RfcNumber, WorkMetadata}, user::UserPrimaryKey};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct V5<K: UserPrimaryKey> {
+    /// The lead author of the paper
+    pub lead_author: K,
+    /// Co-authors of the paper, if any
+    pub co_authors: Vec<K>,
+    /// Users invited as co-authors who have not answered yet
+    pub invited_co_authors: Vec<K>,
+    /// Roles of co-authors and invitees; users missing here are editors
+    pub co_author_roles: Vec<(K, CoAuthorRole)>,
+    /// Title of the paper
+    pub title: PaperTitle,
+    /// Abstract of the paper
+    pub ab: String,
+    /// Main content of the paper
+    pub content: PaperContents,
+    /// Categories this paper belongs to
+    pub categories: Vec<PaperCategory>,
+    /// Tags for better searchability
+    pub tags: Vec<String>,
+    /// Current status of the paper
+    pub status: PaperStatus,
+    /// When the paper was created
+    pub created_at: u64,
+    /// When the paper was last updated in nanoseconds since epoch
+    pub updated_at: u64,
+    /// Optional URL to cover image in
+    pub cover_image: Option<String>,
+    /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
+    pub references: Vec<ReferenceV5>,
+    /// List of identifiers for all papers that have cited this paper.
+    pub citations: Vec<CitationV5>,
+}
+
+/// Bibliographic details as stored by V5 and V6
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct WorkMetadataV5 {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+}
+
+/// Citation as stored by V5 and V6
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub enum CitationV5 {
+    Paper(PaperId),
+    Url(String),
+    Other(String),
+    Doi { doi: Doi, work: WorkMetadataV5 },
+    Arxiv { id: ArxivId, work: WorkMetadataV5 },
+    Isbn { isbn: Isbn, work: WorkMetadataV5 },
+    Rfc { number: RfcNumber, work: WorkMetadataV5 },
+    GitHub { repository: GitHubRepository, work: WorkMetadataV5 },
+}
+
+/// Why a paper cites a work, as stored by V5 and V6
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug)]
+pub enum CitationIntentV5 {
+    Background,
+    UsesMethod,
+    Extends,
+    Disputes,
+}
+
+/// How a paper cites a work, as stored by V5 and V6
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct CitationContextV5 {
+    pub locator: Option<String>,
+    pub quote: Option<String>,
+    pub intent: Option<CitationIntentV5>,
+}
+
+/// Reference as stored by V5 and V6
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct ReferenceV5 {
+    pub citation: CitationV5,
+    pub context: CitationContextV5,
+}
+
+impl From<WorkMetadataV5> for WorkMetadata {
+    fn from(work: WorkMetadataV5) -> Self {
+        WorkMetadata { title: work.title, authors: work.authors, year: work.year }
+    }
+}
+
+impl From<WorkMetadata> for WorkMetadataV5 {
+    fn from(work: WorkMetadata) -> Self {
+        WorkMetadataV5 { title: work.title, authors: work.authors, year: work.year }
+    }
+}
+
+impl From<CitationV5> for Citation {
+    fn from(citation: CitationV5) -> Self {
+        match citation {
+            CitationV5::Paper(id) => Citation::Paper(id),
+            CitationV5::Url(url) => Citation::Url(url),
+            CitationV5::Other(text) => Citation::Other(text),
+            CitationV5::Doi { doi, work } => Citation::Doi { doi, work: work.into() },
+            CitationV5::Arxiv { id, work } => Citation::Arxiv { id, work: work.into() },
+            CitationV5::Isbn { isbn, work } => Citation::Isbn { isbn, work: work.into() },
+            CitationV5::Rfc { number, work } => Citation::Rfc { number, work: work.into() },
+            CitationV5::GitHub { repository, work } => Citation::GitHub { repository, work: work.into() },
+        }
+    }
+}
+
+impl From<Citation> for CitationV5 {
+    fn from(citation: Citation) -> Self {
+        match citation {
+            Citation::Paper(id) => CitationV5::Paper(id),
+            Citation::Url(url) => CitationV5::Url(url),
+            Citation::Other(text) => CitationV5::Other(text),
+            Citation::Doi { doi, work } => CitationV5::Doi { doi, work: work.into() },
+            Citation::Arxiv { id, work } => CitationV5::Arxiv { id, work: work.into() },
+            Citation::Isbn { isbn, work } => CitationV5::Isbn { isbn, work: work.into() },
+            Citation::Rfc { number, work } => CitationV5::Rfc { number, work: work.into() },
+            Citation::GitHub { repository, work } => CitationV5::GitHub { repository, work: work.into() },
+        }
+    }
+}
+
+impl From<CitationIntentV5> for CitationIntent {
+    fn from(intent: CitationIntentV5) -> Self {
+        match intent {
+            CitationIntentV5::Background => CitationIntent::Background,
+            CitationIntentV5::UsesMethod => CitationIntent::UsesMethod,
+            CitationIntentV5::Extends => CitationIntent::Extends,
+            CitationIntentV5::Disputes => CitationIntent::Disputes,
+        }
+    }
+}
+
+impl From<CitationIntent> for CitationIntentV5 {
+    fn from(intent: CitationIntent) -> Self {
+        match intent {
+            CitationIntent::Background => CitationIntentV5::Background,
+            CitationIntent::UsesMethod => CitationIntentV5::UsesMethod,
+            CitationIntent::Extends => CitationIntentV5::Extends,
+            CitationIntent::Disputes => CitationIntentV5::Disputes,
+        }
+    }
+}
+
+impl From<ReferenceV5> for Reference {
+    fn from(reference: ReferenceV5) -> Self {
+        let context = reference.context;
+        Reference {
+            citation: reference.citation.into(),
+            context: CitationContext { locator: context.locator, quote: context.quote, intent: context.intent.map(Into::into) },
+        }
+    }
+}
+
+impl From<Reference> for ReferenceV5 {
+    fn from(reference: Reference) -> Self {
+        let context = reference.context;
+        ReferenceV5 {
+            citation: reference.citation.into(),
+            context: CitationContextV5 { locator: context.locator, quote: context.quote, intent: context.intent.map(Into::into) },
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{paper::{CoAuthorRole, PaperCategory, PaperStatus, PaperTitle}, user::UserPrimaryKey};
+use super::{CitationV5, ReferenceV5};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a paper; its text and PDF are stored apart, under the same identifier
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct V6<K: UserPrimaryKey> {
+    /// The lead author of the paper
+    pub lead_author: K,
+    /// Co-authors of the paper, if any
+    pub co_authors: Vec<K>,
+    /// Users invited as co-authors who have not answered yet
+    pub invited_co_authors: Vec<K>,
+    /// Roles of co-authors and invitees; users missing here are editors
+    pub co_author_roles: Vec<(K, CoAuthorRole)>,
+    /// Title of the paper
+    pub title: PaperTitle,
+    /// Abstract of the paper
+    pub ab: String,
+    /// Categories this paper belongs to
+    pub categories: Vec<PaperCategory>,
+    /// Tags for better searchability
+    pub tags: Vec<String>,
+    /// Current status of the paper
+    pub status: PaperStatus,
+    /// When the paper was created
+    pub created_at: u64,
+    /// When the paper was last updated in nanoseconds since epoch
+    pub updated_at: u64,
+    /// Optional URL to cover image in
+    pub cover_image: Option<String>,
+    /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
+    pub references: Vec<ReferenceV5>,
+    /// List of identifiers for all papers that have cited this paper.
+    pub citations: Vec<CitationV5>,
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::{paper::{PaperCategory, PaperStatus, PaperTitle, Citation, PaperContents, PaperId}, user::{UserId, UserName}};
+use crate::{paper::{PaperCategory, PaperStatus, PaperTitle, Citation, PaperContents, PaperId, Reference}, user::{UserId, UserName}};
 use serde::Deserialize;
 #[cfg(feature = "entity")]
 use crate::{
@@ -31,15 +31,45 @@ pub struct Paper {
     /// When the paper was created
     pub created_at: u64,
     /// When the paper was last updated in nanoseconds since epoch
+    ///
+    /// `update_paper` only accepts the paper if this still matches the stored value.
     pub updated_at: u64,
     /// Optional URL to cover image in
     pub cover_image: Option<String>,
-    /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
-    pub references: Vec<Citation>,
+    /// Works referenced by this paper, each with how it is cited, enabling meaningful cross-linking to related content.
+    pub references: Vec<Reference>,
     /// List of identifiers for all papers that have cited this paper.
     pub citations: Vec<Citation>,
 }
 
+impl Paper {
+    /// Placeholder shown instead of an author whose identity is hidden
+    pub fn anonymous_author() -> (String, UserName) {
+        (String::new(), UserName::new("Anonymous").unwrap_or_default())
+    }
+
+    /// Strips an archived paper down to what identifies it.
+    ///
+    /// The public keeps seeing who wrote what and when, but no longer the text itself.
+    pub fn tombstone(mut self) -> Self {
+        self.ab.clear();
+        self.content = PaperContents::default();
+        self.cover_image = None;
+        self.references.clear();
+        self
+    }
+
+    /// Hides the authors of the paper, as done for reviewers during a double-blind review.
+    ///
+    /// The lead author is replaced by an anonymous placeholder with an empty ID and
+    /// co-authors are dropped, so a masked paper cannot be converted back into a model.
+    pub fn masked(mut self) -> Self {
+        self.lead_author = Self::anonymous_author();
+        self.co_authors.clear();
+        self
+    }
+}
+
 #[cfg(feature = "entity")]
 impl Paper {
     pub fn from_model<T: UserRepository>(paper: model::Paper<T::PrimaryKey>, user_repo: &T, id: PaperId) -> Option<Self> {
@@ -97,12 +127,14 @@ impl Paper {
             id,
             lead_author,
             co_authors,
+            invited_co_authors: Vec::new(),
+            co_author_roles: Default::default(),
             title: self.title.clone(),
             ab: self.ab.clone(),
             content: self.content.clone(),
             categories: self.categories.clone(),
             tags: self.tags.clone(),
-            status: self.status.clone(),
+            status: self.status,
             created_at: self.created_at,
             updated_at: self.updated_at,
             cover_image: self.cover_image.clone(),
@@ -1,7 +1,77 @@
-use crate::{paper::{repository::PaperRepository, Citation, PaperCategory, PaperContents, PaperId, PaperStatus, PaperTitle}, user::UserPrimaryKey};
-use super::dao::{PaperDao, PaperDaoVersion, V1};
+use crate::{paper::{repository::PaperRepository, Citation, CoAuthorRole, ContentFileSource, PaperRole, PaperCategory, PaperContents, PaperId, PaperStatus, PaperStatusTransition, PaperStatusTransitionError, PaperTitle, PaperTitleError, Reference, ReferenceError}, user::UserPrimaryKey};
+use super::dao::{PaperDao, PaperDaoVersion, V6};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
 use util::time::now;
 
+/// Errors raised when changing the authorship of a paper
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CoAuthorError {
+    #[error("User is already an author of this paper")]
+    AlreadyAuthor,
+    #[error("User has already been invited to this paper")]
+    AlreadyInvited,
+    #[error("User has not been invited to this paper")]
+    NotInvited,
+    #[error("User is not a co-author of this paper")]
+    NotCoAuthor,
+    #[error("User is neither a co-author of nor invited to this paper")]
+    NotCoAuthorOrInvited,
+}
+
+/// Errors raised when a patch would leave a paper invalid
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PaperPatchError {
+    #[error(transparent)]
+    Title(#[from] PaperTitleError),
+    #[error("Tags cannot be blank")]
+    BlankTag,
+    #[error("Tag is listed more than once: {0}")]
+    DuplicateTag(String),
+    #[error("A paper cannot reference itself")]
+    SelfReference,
+    #[error("Invalid reference: {0}")]
+    InvalidReference(#[from] ReferenceError),
+    #[error("A {0} paper must keep an abstract and some content")]
+    MissingContent(PaperStatus),
+}
+
+/// Errors raised when a paper cannot be published as a revision
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PaperRevisionError {
+    #[error("Only published papers can be revised")]
+    NotPublished,
+    #[error("Only the latest revision of a paper can be revised")]
+    NotLatest,
+    #[error("The paper has reached its last revision")]
+    VersionOverflow,
+    #[error("Invalid reference: {0}")]
+    InvalidReference(#[from] ReferenceError),
+    #[error(transparent)]
+    Transition(#[from] PaperStatusTransitionError),
+}
+
+/// Field-level changes to a paper; fields left as `None` keep their current value
+#[derive(Clone, Debug, Default)]
+pub struct PaperChanges {
+    pub title: Option<String>,
+    pub ab: Option<String>,
+    /// `Some(None)` removes the text content
+    pub text: Option<Option<String>>,
+    pub tags: Option<Vec<String>>,
+    pub categories: Option<Vec<PaperCategory>>,
+    pub references: Option<Vec<Reference>>,
+    /// `Some(None)` removes the cover image
+    pub cover_image: Option<Option<String>>,
+    /// `Some(None)` removes the PDF
+    pub pdf: Option<Option<ContentFileSource>>,
+}
+
+/// Validates references and drops blank parts of their context
+fn normalize_references(references: Vec<Reference>) -> Result<Vec<Reference>, ReferenceError> {
+    references.into_iter().map(Reference::normalized).collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct Paper<K: UserPrimaryKey> {
     /// The unique identifier of the paper
@@ -10,6 +80,10 @@ pub struct Paper<K: UserPrimaryKey> {
     pub lead_author: K,
     /// Co-authors of the paper, if any
     pub co_authors: Vec<K>,
+    /// Users invited as co-authors who have not answered yet
+    pub invited_co_authors: Vec<K>,
+    /// Roles of co-authors and invitees; users missing here are editors
+    pub co_author_roles: BTreeMap<K, CoAuthorRole>,
     /// Title of the paper
     pub title: PaperTitle,
     /// Abstract of the paper
@@ -29,11 +103,31 @@ pub struct Paper<K: UserPrimaryKey> {
     /// Optional URL to cover image in
     pub cover_image: Option<String>,
     /// Contains identifiers of papers that are referenced by this paper, enabling meaningful cross-linking to related content.
-    pub references: Vec<Citation>,
+    pub references: Vec<Reference>,
     /// List of identifiers for all papers that have cited this paper.
     pub citations: Vec<Citation>,
 }
 
+/// A paper without its contents, for listings and other views that never show them
+#[derive(Clone, Debug)]
+pub struct PaperMetadata<K: UserPrimaryKey> {
+    pub id: PaperId,
+    pub lead_author: K,
+    pub co_authors: Vec<K>,
+    pub invited_co_authors: Vec<K>,
+    pub co_author_roles: BTreeMap<K, CoAuthorRole>,
+    pub title: PaperTitle,
+    pub ab: String,
+    pub categories: Vec<PaperCategory>,
+    pub tags: Vec<String>,
+    pub status: PaperStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub cover_image: Option<String>,
+    pub references: Vec<Reference>,
+    pub citations: Vec<Citation>,
+}
+
 impl<K: UserPrimaryKey> Paper<K> {
     /// Creates a new draft paper
     pub fn new_draft(
@@ -47,6 +141,8 @@ impl<K: UserPrimaryKey> Paper<K> {
             id,
             lead_author,
             co_authors: Vec::new(),
+            invited_co_authors: Vec::new(),
+            co_author_roles: BTreeMap::new(),
             title: PaperTitle::new("New Paper").unwrap(),
             ab: String::new(),
             content: PaperContents::default(),
@@ -62,36 +158,311 @@ impl<K: UserPrimaryKey> Paper<K> {
     }
 
     /// Publishes a draft paper
-    pub fn publish(&mut self) -> Result<(), String> {
+    pub fn publish(&mut self) -> Result<(), PaperStatusTransitionError> {
+        self.transition(PaperStatusTransition::Publish)
+    }
+
+    /// Takes a published paper back to draft
+    pub fn unpublish(&mut self) -> Result<(), PaperStatusTransitionError> {
+        self.transition(PaperStatusTransition::Retract)
+    }
+
+    /// Moves the paper along the status transition table.
+    ///
+    /// Transitions that make the paper visible to others require it to have
+    /// an abstract and some content.
+    pub fn transition(&mut self, transition: PaperStatusTransition) -> Result<(), PaperStatusTransitionError> {
+        let status = self.status.apply(transition)?;
+        self.check_transition_guard(transition)?;
+
+        self.status = status;
+        self.touch();
+        Ok(())
+    }
+
+    /// Applies field-level changes after validating all of them.
+    ///
+    /// Server-owned fields such as authorship, status, `created_at` and `citations`
+    /// cannot be changed this way. Nothing is changed if any field is invalid.
+    pub fn apply_changes(&mut self, changes: PaperChanges) -> Result<(), PaperPatchError> {
+        let title = changes.title.map(PaperTitle::new).transpose()?;
+
+        let tags = changes.tags.map(|tags| {
+            let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+            for tag in tags {
+                let tag = tag.trim();
+                if tag.is_empty() {
+                    return Err(PaperPatchError::BlankTag);
+                }
+                if normalized.iter().any(|existing| existing == tag) {
+                    return Err(PaperPatchError::DuplicateTag(tag.to_string()));
+                }
+                normalized.push(tag.to_string());
+            }
+            Ok(normalized)
+        }).transpose()?;
+
+        let references = changes.references.map(|references| {
+            if references.iter().any(|reference| matches!(reference.citation, Citation::Paper(id) if id.is_same_paper(&self.id))) {
+                return Err(PaperPatchError::SelfReference);
+            }
+            normalize_references(references).map_err(PaperPatchError::from)
+        }).transpose()?;
+
+        // Visible papers passed the content guard of their transition and must keep satisfying it
         if self.status != PaperStatus::Draft {
-            return Err("Only draft papers can be published".to_string());
+            let ab = changes.ab.as_deref().unwrap_or(&self.ab);
+            let text = changes.text.as_ref().unwrap_or(&self.content.text);
+            let has_text = text.as_deref().is_some_and(|text| !text.trim().is_empty());
+            let has_pdf = changes.pdf.as_ref().unwrap_or(&self.content.pdf).is_some();
+            if ab.trim().is_empty() || !(has_text || has_pdf) {
+                return Err(PaperPatchError::MissingContent(self.status));
+            }
+        }
+
+        if let Some(title) = title {
+            self.title = title;
+        }
+        if let Some(ab) = changes.ab {
+            self.ab = ab;
+        }
+        if let Some(text) = changes.text {
+            self.content.text = text;
         }
-        self.status = PaperStatus::Published;
-        self.updated_at = now();
+        if let Some(pdf) = changes.pdf {
+            self.content.pdf = pdf;
+        }
+        if let Some(tags) = tags {
+            self.tags = tags;
+        }
+        if let Some(categories) = changes.categories {
+            self.categories = categories;
+        }
+        if let Some(references) = references {
+            self.references = references;
+        }
+        if let Some(cover_image) = changes.cover_image {
+            self.cover_image = cover_image;
+        }
+        self.touch();
         Ok(())
     }
 
-    pub fn unpublish(&mut self) -> Result<(), String> {
-        if self.status != PaperStatus::Published {
-            return Err("Only published papers can be unpublished".to_string());
+    /// Advances `updated_at`, which also serves as the paper's revision marker.
+    ///
+    /// The stamp strictly increases even when several updates share a block time,
+    /// so a client holding an older stamp always sees a conflict.
+    pub fn touch(&mut self) {
+        self.updated_at = now().max(self.updated_at + 1);
+    }
+
+    fn check_transition_guard(&self, transition: PaperStatusTransition) -> Result<(), PaperStatusTransitionError> {
+        match transition.target() {
+            PaperStatus::UnderReview | PaperStatus::Published => {
+                if self.ab.trim().is_empty() {
+                    return Err(PaperStatusTransitionError::MissingAbstract(transition));
+                }
+                if !self.content.has_content() {
+                    return Err(PaperStatusTransitionError::MissingContent(transition));
+                }
+                Ok(())
+            }
+            PaperStatus::Draft | PaperStatus::Archived => Ok(()),
         }
-        self.status = PaperStatus::Draft;
-        self.updated_at = now();
+    }
+
+    /// Validates the references of the paper and normalizes their context
+    pub fn normalize_references(&mut self) -> Result<(), ReferenceError> {
+        self.references = normalize_references(std::mem::take(&mut self.references))?;
         Ok(())
     }
 
+    /// Returns the other papers this paper references through `Citation::Paper`
+    pub fn referenced_papers(&self) -> BTreeSet<PaperId> {
+        self.references.iter()
+            .filter_map(|reference| match reference.citation {
+                Citation::Paper(id) if !id.is_same_paper(&self.id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the papers listed in the citations of this paper
+    pub fn citing_papers(&self) -> BTreeSet<PaperId> {
+        self.citations.iter()
+            .filter_map(|citation| match citation {
+                Citation::Paper(id) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn is_author(&self, user: &K) -> bool {
         self.lead_author == *user || self.co_authors.contains(user)
     }
+
+    pub fn is_invited(&self, user: &K) -> bool {
+        self.invited_co_authors.contains(user)
+    }
+
+    /// Returns the role the user holds as an author of this paper, if any
+    pub fn author_role(&self, user: &K) -> Option<PaperRole> {
+        if self.lead_author == *user {
+            return Some(PaperRole::LeadAuthor);
+        }
+        if !self.co_authors.contains(user) {
+            return None;
+        }
+
+        Some(self.co_author_roles.get(user).copied().unwrap_or_default().into())
+    }
+
+    /// Invites a user to become a co-author with the given role.
+    ///
+    /// The user only appears as an author once they accept the invitation.
+    pub fn invite_co_author(&mut self, user: K, role: CoAuthorRole) -> Result<(), CoAuthorError> {
+        if self.is_author(&user) {
+            return Err(CoAuthorError::AlreadyAuthor);
+        }
+        if self.is_invited(&user) {
+            return Err(CoAuthorError::AlreadyInvited);
+        }
+
+        self.invited_co_authors.push(user);
+        self.co_author_roles.insert(user, role);
+        self.touch();
+        Ok(())
+    }
+
+    /// Changes the role of a co-author or of a pending invitation
+    pub fn set_co_author_role(&mut self, user: &K, role: CoAuthorRole) -> Result<(), CoAuthorError> {
+        if !self.co_authors.contains(user) && !self.is_invited(user) {
+            return Err(CoAuthorError::NotCoAuthorOrInvited);
+        }
+
+        self.co_author_roles.insert(*user, role);
+        self.touch();
+        Ok(())
+    }
+
+    /// Turns a pending invitation into co-authorship with the role it was sent with
+    pub fn accept_invitation(&mut self, user: &K) -> Result<(), CoAuthorError> {
+        let role = self.co_author_roles.get(user).copied().unwrap_or_default();
+        self.withdraw_invitation(user)?;
+        self.co_author_roles.insert(*user, role);
+        self.co_authors.push(*user);
+        self.touch();
+        Ok(())
+    }
+
+    /// Drops a pending invitation, whether declined by the invitee or cancelled by the lead author
+    pub fn withdraw_invitation(&mut self, user: &K) -> Result<(), CoAuthorError> {
+        let position = self.invited_co_authors.iter()
+            .position(|invited| invited == user)
+            .ok_or(CoAuthorError::NotInvited)?;
+
+        self.invited_co_authors.remove(position);
+        self.co_author_roles.remove(user);
+        self.touch();
+        Ok(())
+    }
+
+    pub fn remove_co_author(&mut self, user: &K) -> Result<(), CoAuthorError> {
+        let position = self.co_authors.iter()
+            .position(|co_author| co_author == user)
+            .ok_or(CoAuthorError::NotCoAuthor)?;
+
+        self.co_authors.remove(position);
+        self.co_author_roles.remove(user);
+        self.touch();
+        Ok(())
+    }
+
+    /// Turns this paper into the revision following `previous`.
+    ///
+    /// `previous` must be the published head of the paper, and is left untouched. The revision keeps
+    /// its authorship, pending invitations and creation date, and starts without citations since
+    /// those were made against the earlier text. It is published through the transition table,
+    /// so it must have an abstract and some content.
+    pub fn into_revision_of(mut self, previous: &Self) -> Result<Self, PaperRevisionError> {
+        if previous.status != PaperStatus::Published {
+            return Err(PaperRevisionError::NotPublished);
+        }
+        self.normalize_references()?;
+
+        self.id = previous.id.next_version().map_err(|_| PaperRevisionError::VersionOverflow)?;
+        self.lead_author = previous.lead_author;
+        self.co_authors = previous.co_authors.clone();
+        self.invited_co_authors = previous.invited_co_authors.clone();
+        self.co_author_roles = previous.co_author_roles.clone();
+        self.status = PaperStatus::Draft;
+        self.created_at = previous.created_at;
+        self.updated_at = previous.updated_at;
+        self.citations = Vec::new();
+        self.transition(PaperStatusTransition::Publish)?;
+        Ok(self)
+    }
 }
 
 impl<K: UserPrimaryKey> Paper<K> {
-    pub fn from_dao(paper_dao: PaperDao<K>, id: PaperId) -> Self {
-        match paper_dao.version {
+    /// Joins the metadata and contents of a paper read apart
+    pub fn from_parts(metadata: PaperMetadata<K>, content: PaperContents) -> Self {
+        Paper {
+            id: metadata.id,
+            lead_author: metadata.lead_author,
+            co_authors: metadata.co_authors,
+            invited_co_authors: metadata.invited_co_authors,
+            co_author_roles: metadata.co_author_roles,
+            title: metadata.title,
+            ab: metadata.ab,
+            content,
+            categories: metadata.categories,
+            tags: metadata.tags,
+            status: metadata.status,
+            created_at: metadata.created_at,
+            updated_at: metadata.updated_at,
+            cover_image: metadata.cover_image,
+            references: metadata.references,
+            citations: metadata.citations,
+        }
+    }
+
+    /// Splits the paper into its metadata and its contents, which are stored apart
+    pub fn into_parts(self) -> (PaperMetadata<K>, PaperContents) {
+        let metadata = PaperMetadata {
+            id: self.id,
+            lead_author: self.lead_author,
+            co_authors: self.co_authors,
+            invited_co_authors: self.invited_co_authors,
+            co_author_roles: self.co_author_roles,
+            title: self.title,
+            ab: self.ab,
+            categories: self.categories,
+            tags: self.tags,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            cover_image: self.cover_image,
+            references: self.references,
+            citations: self.citations,
+        };
+        (metadata, self.content)
+    }
+}
+
+impl<K: UserPrimaryKey> PaperMetadata<K> {
+    /// Reads the metadata of a stored paper.
+    ///
+    /// Versions before V6 hold the contents of the paper inline, which are returned
+    /// alongside; later versions leave them to be read apart.
+    pub fn from_dao(paper_dao: PaperDao<K>, id: PaperId) -> (Self, Option<PaperContents>) {
+        let paper = match paper_dao.version {
             PaperDaoVersion::V1(v1) => Paper {
                 id,
                 lead_author: v1.lead_author,
                 co_authors: v1.co_authors,
+                invited_co_authors: Vec::new(),
+                co_author_roles: BTreeMap::new(),
                 title: v1.title,
                 ab: v1.ab,
                 content: v1.content,
@@ -101,30 +472,126 @@ impl<K: UserPrimaryKey> Paper<K> {
                 created_at: v1.created_at,
                 updated_at: v1.updated_at,
                 cover_image: v1.cover_image,
-                references: v1.references,
-                citations: v1.citations,
+                references: v1.references.into_iter().map(|citation| Reference::new(citation.into())).collect(),
+                citations: v1.citations.into_iter().map(Citation::from).collect(),
             },
-        }
+            PaperDaoVersion::V2(v2) => Paper {
+                id,
+                lead_author: v2.lead_author,
+                co_authors: v2.co_authors,
+                invited_co_authors: v2.invited_co_authors,
+                co_author_roles: BTreeMap::new(),
+                title: v2.title,
+                ab: v2.ab,
+                content: v2.content,
+                categories: v2.categories,
+                tags: v2.tags,
+                status: v2.status,
+                created_at: v2.created_at,
+                updated_at: v2.updated_at,
+                cover_image: v2.cover_image,
+                references: v2.references.into_iter().map(|citation| Reference::new(citation.into())).collect(),
+                citations: v2.citations.into_iter().map(Citation::from).collect(),
+            },
+            PaperDaoVersion::V3(v3) => Paper {
+                id,
+                lead_author: v3.lead_author,
+                co_authors: v3.co_authors,
+                invited_co_authors: v3.invited_co_authors,
+                co_author_roles: v3.co_author_roles.into_iter().collect(),
+                title: v3.title,
+                ab: v3.ab,
+                content: v3.content,
+                categories: v3.categories,
+                tags: v3.tags,
+                status: v3.status,
+                created_at: v3.created_at,
+                updated_at: v3.updated_at,
+                cover_image: v3.cover_image,
+                references: v3.references.into_iter().map(|citation| Reference::new(citation.into())).collect(),
+                citations: v3.citations.into_iter().map(Citation::from).collect(),
+            },
+            PaperDaoVersion::V4(v4) => Paper {
+                id,
+                lead_author: v4.lead_author,
+                co_authors: v4.co_authors,
+                invited_co_authors: v4.invited_co_authors,
+                co_author_roles: v4.co_author_roles.into_iter().collect(),
+                title: v4.title,
+                ab: v4.ab,
+                content: v4.content,
+                categories: v4.categories,
+                tags: v4.tags,
+                status: v4.status,
+                created_at: v4.created_at,
+                updated_at: v4.updated_at,
+                cover_image: v4.cover_image,
+                references: v4.references.into_iter().map(Reference::from).collect(),
+                citations: v4.citations.into_iter().map(|citation| Reference::from(citation).citation).collect(),
+            },
+            PaperDaoVersion::V5(v5) => Paper {
+                id,
+                lead_author: v5.lead_author,
+                co_authors: v5.co_authors,
+                invited_co_authors: v5.invited_co_authors,
+                co_author_roles: v5.co_author_roles.into_iter().collect(),
+                title: v5.title,
+                ab: v5.ab,
+                content: v5.content,
+                categories: v5.categories,
+                tags: v5.tags,
+                status: v5.status,
+                created_at: v5.created_at,
+                updated_at: v5.updated_at,
+                cover_image: v5.cover_image,
+                references: v5.references.into_iter().map(Reference::from).collect(),
+                citations: v5.citations.into_iter().map(Citation::from).collect(),
+            },
+            PaperDaoVersion::V6(v6) => {
+                let metadata = PaperMetadata {
+                    id,
+                    lead_author: v6.lead_author,
+                    co_authors: v6.co_authors,
+                    invited_co_authors: v6.invited_co_authors,
+                    co_author_roles: v6.co_author_roles.into_iter().collect(),
+                    title: v6.title,
+                    ab: v6.ab,
+                    categories: v6.categories,
+                    tags: v6.tags,
+                    status: v6.status,
+                    created_at: v6.created_at,
+                    updated_at: v6.updated_at,
+                    cover_image: v6.cover_image,
+                    references: v6.references.into_iter().map(Reference::from).collect(),
+                    citations: v6.citations.into_iter().map(Citation::from).collect(),
+                };
+                return (metadata, None);
+            }
+        };
+
+        let (metadata, content) = paper.into_parts();
+        (metadata, Some(content))
     }
 }
 
-impl<K: UserPrimaryKey> From<Paper<K>> for PaperDao<K> {
-    fn from(paper: Paper<K>) -> Self {
+impl<K: UserPrimaryKey> From<PaperMetadata<K>> for PaperDao<K> {
+    fn from(paper: PaperMetadata<K>) -> Self {
         PaperDao {
-            version: PaperDaoVersion::V1(V1 {
+            version: PaperDaoVersion::V6(V6 {
                 lead_author: paper.lead_author,
                 co_authors: paper.co_authors,
+                invited_co_authors: paper.invited_co_authors,
+                co_author_roles: paper.co_author_roles.into_iter().collect(),
                 title: paper.title,
                 ab: paper.ab,
-                content: paper.content,
                 categories: paper.categories,
                 tags: paper.tags,
                 status: paper.status,
                 created_at: paper.created_at,
                 updated_at: paper.updated_at,
                 cover_image: paper.cover_image,
-                references: paper.references,
-                citations: paper.citations,
+                references: paper.references.into_iter().map(Into::into).collect(),
+                citations: paper.citations.into_iter().map(Into::into).collect(),
             })
         }
     }
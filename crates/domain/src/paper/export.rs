@@ -0,0 +1,557 @@
+use crate::paper::{entity::dto::Paper, import::BIBTEX_MONTHS, Citation, CitationFormat, PaperId, Reference, PUBLISHER};
+use std::{collections::HashSet, fmt::Write};
+use util::time::date_from_nanos;
+
+/// Kind of work a bibliography record describes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) enum RecordKind {
+    Paper,
+    Article,
+    Book,
+    Report,
+    Software,
+    Webpage,
+    #[default]
+    Other,
+}
+
+/// Format-independent description of a cited work
+#[derive(Clone, Debug, Default)]
+pub(super) struct Record {
+    pub(super) key: String,
+    pub(super) kind: RecordKind,
+    pub(super) title: Option<String>,
+    pub(super) authors: Vec<String>,
+    /// Year, month and day of publication, where an unknown month and day are 0
+    pub(super) issued: Option<(u32, u32, u32)>,
+    pub(super) publisher: Option<String>,
+    /// Paper ID or report number
+    pub(super) number: Option<String>,
+    pub(super) doi: Option<String>,
+    /// arXiv identifier
+    pub(super) eprint: Option<String>,
+    pub(super) isbn: Option<String>,
+    pub(super) locator: Option<String>,
+    pub(super) url: Option<String>,
+    pub(super) note: Option<String>,
+    /// Why and how the citing paper cites the work
+    pub(super) annotation: Option<String>,
+    pub(super) keywords: Vec<String>,
+}
+
+impl Record {
+    pub(super) fn from_paper(paper: &Paper) -> Self {
+        let authors = std::iter::once(&paper.lead_author)
+            .chain(paper.co_authors.iter())
+            .map(|(_, name)| name.to_string())
+            .collect();
+
+        Record {
+            key: format!("{}:{}", PUBLISHER, paper.id),
+            kind: RecordKind::Paper,
+            title: Some(paper.title.to_string()),
+            authors,
+            issued: Some(date_from_nanos(paper.created_at)),
+            publisher: Some(PUBLISHER.to_string()),
+            number: Some(paper.id.clone()),
+            keywords: paper.tags.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Describes a paper that no longer exists or cannot be read by the caller
+    fn from_missing_paper(paper_id: &PaperId) -> Self {
+        Record {
+            key: format!("{}:{}", PUBLISHER, paper_id),
+            kind: RecordKind::Paper,
+            publisher: Some(PUBLISHER.to_string()),
+            number: Some(paper_id.to_string()),
+            note: Some(format!("Unavailable {} paper {}", PUBLISHER, paper_id)),
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn from_reference(index: usize, reference: &Reference, resolve: &impl Fn(&PaperId) -> Option<Paper>) -> Self {
+        let mut record = Record::from_citation(index, &reference.citation, resolve);
+        let context = &reference.context;
+        record.locator = context.locator.clone();
+        record.annotation = match (context.intent, &context.quote) {
+            (Some(intent), Some(quote)) => Some(format!("{}: \"{}\"", intent.as_str(), quote)),
+            (Some(intent), None) => Some(intent.as_str().to_string()),
+            (None, Some(quote)) => Some(format!("\"{}\"", quote)),
+            (None, None) => None,
+        };
+        record
+    }
+
+    fn from_citation(index: usize, citation: &Citation, resolve: &impl Fn(&PaperId) -> Option<Paper>) -> Self {
+        let mut record = Record {
+            key: format!("ref{}", index + 1),
+            url: citation.url(),
+            ..Default::default()
+        };
+        if let Some(work) = citation.work() {
+            record.title = work.title.clone();
+            record.authors = work.authors.clone();
+            record.issued = work.year.map(|year| (year as u32, 0, 0));
+        }
+
+        match citation {
+            Citation::Paper(id) => {
+                return resolve(id)
+                    .map(|paper| Record::from_paper(&paper))
+                    .unwrap_or_else(|| Record::from_missing_paper(id));
+            }
+            Citation::Url(_) => record.kind = RecordKind::Webpage,
+            Citation::Other(text) => record.note = Some(text.clone()),
+            Citation::Doi { doi, .. } => {
+                record.kind = RecordKind::Article;
+                record.doi = Some(doi.to_string());
+            }
+            Citation::Arxiv { id, .. } => {
+                record.kind = RecordKind::Article;
+                record.publisher = Some("arXiv".to_string());
+                record.eprint = Some(id.to_string());
+            }
+            Citation::Isbn { isbn, .. } => {
+                record.kind = RecordKind::Book;
+                record.isbn = Some(isbn.to_string());
+            }
+            Citation::Rfc { number, .. } => {
+                record.kind = RecordKind::Report;
+                record.publisher = Some("IETF".to_string());
+                record.number = Some(number.number().to_string());
+                record.title.get_or_insert_with(|| number.to_string());
+            }
+            Citation::GitHub { repository, .. } => {
+                record.kind = RecordKind::Software;
+                record.publisher = Some("GitHub".to_string());
+                record.title.get_or_insert_with(|| repository.to_string());
+            }
+        }
+        record
+    }
+}
+
+impl CitationFormat {
+    /// Exports a paper as a document citing it
+    pub fn export_paper(&self, paper: &Paper) -> String {
+        self.write(&[Record::from_paper(paper)])
+    }
+
+    /// Exports the references of a paper as a bibliography.
+    ///
+    /// `resolve` looks up the papers of this platform that are referenced. Papers it
+    /// cannot find are still listed by their ID, marked as unavailable. A work cited more
+    /// than once gets a numbered key for each further citation, so every key is unique.
+    pub fn export_references(&self, references: &[Reference], resolve: impl Fn(&PaperId) -> Option<Paper>) -> String {
+        let mut records: Vec<_> = references.iter()
+            .enumerate()
+            .map(|(index, reference)| Record::from_reference(index, reference, &resolve))
+            .collect();
+
+        let mut keys = HashSet::new();
+        for record in &mut records {
+            let key = record.key.clone();
+            let mut suffix = 1;
+            while !keys.insert(record.key.clone()) {
+                suffix += 1;
+                record.key = format!("{}-{}", key, suffix);
+            }
+        }
+        self.write(&records)
+    }
+
+    fn write(&self, records: &[Record]) -> String {
+        match self {
+            CitationFormat::BibTeX => write_bibtex(records),
+            CitationFormat::Ris => write_ris(records),
+            CitationFormat::CslJson => write_csl_json(records),
+        }
+    }
+}
+
+fn write_bibtex(records: &[Record]) -> String {
+    let mut out = String::new();
+    for record in records {
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        if let Some(title) = &record.title {
+            // Double braces keep the capitalization of the title
+            fields.push(("title", format!("{{{}}}", escape_bibtex(title))));
+        }
+        if !record.authors.is_empty() {
+            let authors: Vec<_> = record.authors.iter()
+                .map(|author| format!("{{{}}}", escape_bibtex(author)))
+                .collect();
+            fields.push(("author", authors.join(" and ")));
+        }
+        if let Some((year, month, _)) = record.issued {
+            fields.push(("year", year.to_string()));
+            if let Some(month) = (month as usize).checked_sub(1).and_then(|index| BIBTEX_MONTHS.get(index)) {
+                fields.push(("month", month.to_string()));
+            }
+        }
+        if let Some(publisher) = &record.publisher {
+            let field = match record.kind {
+                RecordKind::Book => "publisher",
+                RecordKind::Report => "institution",
+                _ => "howpublished",
+            };
+            fields.push((field, escape_bibtex(publisher)));
+        }
+        if let Some(number) = &record.number {
+            fields.push(("number", escape_bibtex(number)));
+        }
+        if let Some(doi) = &record.doi {
+            fields.push(("doi", escape_bibtex(doi)));
+        }
+        if let Some(eprint) = &record.eprint {
+            fields.push(("eprint", escape_bibtex(eprint)));
+            fields.push(("archiveprefix", "arXiv".to_string()));
+        }
+        if let Some(isbn) = &record.isbn {
+            fields.push(("isbn", isbn.clone()));
+        }
+        if let Some(locator) = &record.locator {
+            fields.push(("pages", escape_bibtex(locator)));
+        }
+        if let Some(url) = &record.url {
+            fields.push(("url", url.replace(['{', '}'], "")));
+        }
+        if let Some(note) = &record.note {
+            fields.push(("note", escape_bibtex(note)));
+        }
+        if let Some(annotation) = &record.annotation {
+            fields.push(("annote", escape_bibtex(annotation)));
+        }
+        if !record.keywords.is_empty() {
+            fields.push(("keywords", escape_bibtex(&record.keywords.join(", "))));
+        }
+
+        let entry_type = match record.kind {
+            RecordKind::Article => "article",
+            RecordKind::Book => "book",
+            RecordKind::Report => "techreport",
+            _ => "misc",
+        };
+        let _ = writeln!(out, "@{}{{{},", entry_type, record.key);
+        for (name, value) in fields {
+            if name == "month" {
+                // Month macros are expanded by BibTeX, so they are not braced
+                let _ = writeln!(out, "  {} = {},", name, value);
+            } else {
+                let _ = writeln!(out, "  {} = {{{}}},", name, value);
+            }
+        }
+        out.push_str("}\n\n");
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn write_ris(records: &[Record]) -> String {
+    let mut out = String::new();
+    for record in records {
+        let kind = match record.kind {
+            RecordKind::Paper => "GEN",
+            RecordKind::Article => "JOUR",
+            RecordKind::Book => "BOOK",
+            RecordKind::Report => "RPRT",
+            RecordKind::Software => "COMP",
+            RecordKind::Webpage => "ELEC",
+            RecordKind::Other => "GEN",
+        };
+        push_ris(&mut out, "TY", kind);
+        push_ris(&mut out, "ID", &record.key);
+        if let Some(title) = &record.title {
+            push_ris(&mut out, "TI", title);
+        }
+        for author in &record.authors {
+            push_ris(&mut out, "AU", author);
+        }
+        if let Some((year, month, day)) = record.issued {
+            push_ris(&mut out, "PY", &year.to_string());
+            if month > 0 {
+                push_ris(&mut out, "DA", &format!("{:04}/{:02}/{:02}/", year, month, day));
+            }
+        }
+        if let Some(publisher) = &record.publisher {
+            push_ris(&mut out, "PB", publisher);
+        }
+        if let Some(number) = &record.number {
+            push_ris(&mut out, "M1", number);
+        }
+        if let Some(doi) = &record.doi {
+            push_ris(&mut out, "DO", doi);
+        }
+        if let Some(isbn) = &record.isbn {
+            push_ris(&mut out, "SN", isbn);
+        }
+        if let Some(locator) = &record.locator {
+            push_ris(&mut out, "SP", locator);
+        }
+        if let Some(url) = &record.url {
+            push_ris(&mut out, "UR", url);
+        }
+        if let Some(note) = &record.note {
+            push_ris(&mut out, "N1", note);
+        }
+        if let Some(annotation) = &record.annotation {
+            push_ris(&mut out, "N1", annotation);
+        }
+        for keyword in &record.keywords {
+            push_ris(&mut out, "KW", keyword);
+        }
+        out.push_str("ER  - \n");
+    }
+    out
+}
+
+fn push_ris(out: &mut String, tag: &str, value: &str) {
+    // RIS fields end at the line break
+    let value: String = value.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    let _ = writeln!(out, "{}  - {}", tag, value.trim());
+}
+
+fn write_csl_json(records: &[Record]) -> String {
+    let items: Vec<_> = records.iter().map(|record| {
+        let kind = match record.kind {
+            RecordKind::Paper | RecordKind::Article => "article",
+            RecordKind::Book => "book",
+            RecordKind::Report => "report",
+            RecordKind::Software => "software",
+            RecordKind::Webpage => "webpage",
+            RecordKind::Other => "document",
+        };
+
+        let mut fields = vec![
+            format!("\"id\": {}", json_string(&record.key)),
+            format!("\"type\": {}", json_string(kind)),
+        ];
+        if let Some(title) = &record.title {
+            fields.push(format!("\"title\": {}", json_string(title)));
+        }
+        if !record.authors.is_empty() {
+            let authors: Vec<_> = record.authors.iter()
+                .map(|author| format!("{{\"literal\": {}}}", json_string(author)))
+                .collect();
+            fields.push(format!("\"author\": [{}]", authors.join(", ")));
+        }
+        match record.issued {
+            Some((year, 0, _)) => fields.push(format!("\"issued\": {{\"date-parts\": [[{}]]}}", year)),
+            Some((year, month, day)) => fields.push(format!("\"issued\": {{\"date-parts\": [[{}, {}, {}]]}}", year, month, day)),
+            None => {}
+        }
+        if let Some(publisher) = &record.publisher {
+            fields.push(format!("\"publisher\": {}", json_string(publisher)));
+        }
+        if let Some(number) = &record.number {
+            fields.push(format!("\"number\": {}", json_string(number)));
+        }
+        if let Some(doi) = &record.doi {
+            fields.push(format!("\"DOI\": {}", json_string(doi)));
+        }
+        if let Some(isbn) = &record.isbn {
+            fields.push(format!("\"ISBN\": {}", json_string(isbn)));
+        }
+        if let Some(locator) = &record.locator {
+            fields.push(format!("\"page\": {}", json_string(locator)));
+        }
+        if let Some(url) = &record.url {
+            fields.push(format!("\"URL\": {}", json_string(url)));
+        }
+        if let Some(note) = &record.note {
+            fields.push(format!("\"note\": {}", json_string(note)));
+        }
+        if let Some(annotation) = &record.annotation {
+            fields.push(format!("\"annote\": {}", json_string(annotation)));
+        }
+        if !record.keywords.is_empty() {
+            fields.push(format!("\"keyword\": {}", json_string(&record.keywords.join(", "))));
+        }
+
+        format!("  {{\n    {}\n  }}", fields.join(",\n    "))
+    }).collect();
+
+    if items.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", items.join(",\n"))
+    }
+}
+
+/// Escapes characters with a special meaning in BibTeX values
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{paper::{CitationContext, CitationIntent, Isbn, PaperContents, PaperStatus, PaperTitle, RfcNumber, WorkMetadata}, user::UserName};
+    use std::str::FromStr;
+
+    fn paper() -> Paper {
+        Paper {
+            id: "2025-01-0001".to_string(),
+            lead_author: ("alice".to_string(), UserName::new("Alice").unwrap()),
+            co_authors: vec![("bob".to_string(), UserName::new("Bob & Co").unwrap())],
+            title: PaperTitle::new("On \"Typst\" {Layouts}").unwrap(),
+            ab: String::new(),
+            content: PaperContents::default(),
+            categories: Vec::new(),
+            tags: vec!["typst".to_string(), "layout".to_string()],
+            status: PaperStatus::Published,
+            // 2025-01-15T00:00:00Z
+            created_at: 1_736_899_200_000_000_000,
+            updated_at: 1_736_899_200_000_000_000,
+            cover_image: None,
+            references: Vec::new(),
+            citations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_bibtex() {
+        let bibtex = CitationFormat::BibTeX.export_paper(&paper());
+        assert_eq!(bibtex, concat!(
+            "@misc{arche:2025-01-0001,\n",
+            "  title = {{On \"Typst\" \\{Layouts\\}}},\n",
+            "  author = {{Alice} and {Bob \\& Co}},\n",
+            "  year = {2025},\n",
+            "  month = jan,\n",
+            "  howpublished = {arche},\n",
+            "  number = {2025-01-0001},\n",
+            "  keywords = {typst, layout},\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_export_ris() {
+        let ris = CitationFormat::Ris.export_paper(&paper());
+        assert!(ris.starts_with("TY  - GEN\nID  - arche:2025-01-0001\n"));
+        assert!(ris.contains("AU  - Alice\nAU  - Bob & Co\n"));
+        assert!(ris.contains("DA  - 2025/01/15/\n"));
+        assert!(ris.ends_with("KW  - typst\nKW  - layout\nER  - \n"));
+    }
+
+    #[test]
+    fn test_export_csl_json() {
+        let json = CitationFormat::CslJson.export_paper(&paper());
+        assert!(json.starts_with("[\n  {\n    \"id\": \"arche:2025-01-0001\",\n    \"type\": \"article\",\n"));
+        assert!(json.contains("\"title\": \"On \\\"Typst\\\" {Layouts}\""));
+        assert!(json.contains("\"author\": [{\"literal\": \"Alice\"}, {\"literal\": \"Bob & Co\"}]"));
+        assert!(json.contains("\"issued\": {\"date-parts\": [[2025, 1, 15]]}"));
+    }
+
+    #[test]
+    fn test_export_references() {
+        let cited = PaperId::from_str("2025-01-0001").unwrap();
+        let missing = PaperId::from_str("2025-02-0002").unwrap();
+        let references = vec![
+            Reference {
+                citation: Citation::Paper(cited),
+                context: CitationContext {
+                    locator: None,
+                    quote: Some("Layouts are {hard}".to_string()),
+                    intent: Some(CitationIntent::Disputes),
+                },
+            },
+            Reference::new(Citation::Url("https://typst.app/docs".to_string())),
+            Reference::new(Citation::Paper(missing)),
+        ];
+
+        let bibtex = CitationFormat::BibTeX.export_references(&references, |id| (*id == cited).then(paper));
+        assert!(bibtex.contains("@misc{arche:2025-01-0001,\n  title = {{On"));
+        assert!(bibtex.contains("  annote = {disputes: \"Layouts are \\{hard\\}\"},\n"));
+        assert!(bibtex.contains("@misc{ref2,\n  url = {https://typst.app/docs},\n}"));
+        assert!(bibtex.contains("@misc{arche:2025-02-0002,\n  howpublished = {arche},\n  number = {2025-02-0002},\n  note = {Unavailable arche paper 2025-02-0002},\n}"));
+
+        assert_eq!(CitationFormat::CslJson.export_references(&[], |_| None), "[]\n");
+    }
+
+    #[test]
+    fn test_export_repeated_references() {
+        let cited = PaperId::from_str("2025-01-0001").unwrap();
+        let references = vec![
+            Reference::new(Citation::Paper(cited)),
+            Reference::new(Citation::Paper(cited)),
+            Reference::new(Citation::Paper(cited)),
+        ];
+
+        let bibtex = CitationFormat::BibTeX.export_references(&references, |id| (*id == cited).then(paper));
+        assert!(bibtex.contains("@misc{arche:2025-01-0001,\n"));
+        assert!(bibtex.contains("@misc{arche:2025-01-0001-2,\n"));
+        assert!(bibtex.contains("@misc{arche:2025-01-0001-3,\n"));
+
+        let ris = CitationFormat::Ris.export_references(&references, |_| None);
+        let ids: Vec<_> = ris.lines().filter(|line| line.starts_with("ID  - ")).collect();
+        assert_eq!(ids, ["ID  - arche:2025-01-0001", "ID  - arche:2025-01-0001-2", "ID  - arche:2025-01-0001-3"]);
+    }
+
+    #[test]
+    fn test_export_identified_works() {
+        let work = WorkMetadata {
+            title: Some("The Art of Computer Programming".to_string()),
+            authors: vec!["Donald E. Knuth".to_string()],
+            year: Some(1968),
+        };
+        let references = vec![
+            Reference {
+                citation: Citation::Isbn { isbn: Isbn::new("978-0-201-89683-1").unwrap(), work },
+                context: CitationContext { locator: Some("42-45".to_string()), ..Default::default() },
+            },
+            Reference::new(Citation::Rfc { number: RfcNumber::new("RFC 9110").unwrap(), work: WorkMetadata::default() }),
+        ];
+
+        let bibtex = CitationFormat::BibTeX.export_references(&references, |_| None);
+        assert!(bibtex.starts_with("@book{ref1,\n  title = {{The Art of Computer Programming}},\n"));
+        assert!(bibtex.contains("  year = {1968},\n  isbn = {9780201896831},\n  pages = {42-45},\n  url = {https://openlibrary.org/isbn/9780201896831},\n"));
+        assert!(bibtex.contains("@techreport{ref2,\n  title = {{RFC 9110}},\n  institution = {IETF},\n  number = {9110},\n"));
+
+        let ris = CitationFormat::Ris.export_references(&references, |_| None);
+        assert!(ris.starts_with("TY  - BOOK\n"));
+        assert!(ris.contains("PY  - 1968\nSN  - 9780201896831\nSP  - 42-45\n"));
+
+        let json = CitationFormat::CslJson.export_references(&references, |_| None);
+        assert!(json.contains("\"issued\": {\"date-parts\": [[1968]]}"));
+        assert!(json.contains("\"URL\": \"https://www.rfc-editor.org/rfc/rfc9110\""));
+    }
+}
@@ -0,0 +1,971 @@
+use crate::paper::{ArxivId, Citation, CitationContext, CitationFormat, Doi, GitHubRepository, Isbn, PaperId, Reference, RfcNumber, WorkMetadata, PUBLISHER};
+use std::{collections::HashMap, str::FromStr};
+use thiserror::Error;
+
+/// An entry of an imported document that could not be turned into a citation
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportError {
+    /// Position of the entry in the document, starting at 0
+    pub index: usize,
+    /// Key or ID of the entry, if it could be read
+    pub key: Option<String>,
+    pub message: String,
+}
+
+/// References read from a reference list, with the entries that were skipped
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportedReferences {
+    pub references: Vec<Reference>,
+    pub errors: Vec<ImportError>,
+}
+
+/// Format-independent fields of an imported entry
+#[derive(Clone, Debug, Default)]
+struct Entry {
+    key: Option<String>,
+    title: Option<String>,
+    authors: Vec<String>,
+    year: Option<String>,
+    container: Option<String>,
+    publisher: Option<String>,
+    number: Option<String>,
+    doi: Option<String>,
+    arxiv: Option<String>,
+    isbn: Option<String>,
+    /// Pages or section cited
+    locator: Option<String>,
+    url: Option<String>,
+    note: Option<String>,
+}
+
+impl Entry {
+    /// Turns the entry into a reference, keeping the cited pages as its locator
+    fn into_reference(self, resolve: &impl Fn(&PaperId) -> bool) -> Result<Reference, String> {
+        let context = CitationContext { locator: self.locator.clone(), ..Default::default() };
+        let citation = self.into_citation(resolve)?;
+        Ok(Reference { citation, context })
+    }
+
+    /// Turns the entry into a citation, preferring papers of this platform that `resolve` accepts
+    fn into_citation(self, resolve: &impl Fn(&PaperId) -> bool) -> Result<Citation, String> {
+        let published_here = self.publisher.as_deref().is_some_and(|publisher| publisher.eq_ignore_ascii_case(PUBLISHER));
+        let candidates = [
+            self.key.as_deref().and_then(|key| key.strip_prefix(PUBLISHER)?.strip_prefix(':')),
+            self.number.as_deref().filter(|_| published_here),
+        ];
+        for candidate in candidates.into_iter().flatten() {
+            if let Ok(id) = PaperId::from_str(candidate.trim()) {
+                if resolve(&id) {
+                    return Ok(Citation::Paper(id));
+                }
+            }
+        }
+
+        // Identifiers given explicitly must be valid, while links are only recognized if they are
+        if let Some(doi) = &self.doi {
+            let doi = Doi::new(doi).map_err(|e| e.to_string())?;
+            return Ok(Citation::Doi { doi, work: self.work() });
+        }
+        if let Some(arxiv) = &self.arxiv {
+            let id = ArxivId::new(arxiv).map_err(|e| e.to_string())?;
+            return Ok(Citation::Arxiv { id, work: self.work() });
+        }
+        if let Some(isbn) = &self.isbn {
+            let isbn = Isbn::new(isbn).map_err(|e| e.to_string())?;
+            return Ok(Citation::Isbn { isbn, work: self.work() });
+        }
+        if let Some(url) = &self.url {
+            return Ok(self.identify_link(url).unwrap_or_else(|| Citation::Url(url.clone())));
+        }
+        if let Some(title) = &self.title {
+            return Ok(Citation::Other(self.describe(title)));
+        }
+        if let Some(note) = self.note {
+            return Ok(Citation::Other(note));
+        }
+        Err("entry has neither a title nor an identifier".to_string())
+    }
+
+    /// Recognizes links to works that have a structured citation
+    fn identify_link(&self, url: &str) -> Option<Citation> {
+        if !url.get(..4).is_some_and(|scheme| scheme.eq_ignore_ascii_case("http")) {
+            return None;
+        }
+        if let Ok(doi) = Doi::new(url) {
+            return Some(Citation::Doi { doi, work: self.work() });
+        }
+        if let Ok(id) = ArxivId::new(url) {
+            return Some(Citation::Arxiv { id, work: self.work() });
+        }
+        if let Ok(number) = RfcNumber::new(url) {
+            return Some(Citation::Rfc { number, work: self.work() });
+        }
+        GitHubRepository::new(url).ok().map(|repository| Citation::GitHub { repository, work: self.work() })
+    }
+
+    fn work(&self) -> WorkMetadata {
+        WorkMetadata {
+            title: self.title.clone(),
+            authors: self.authors.clone(),
+            year: self.year.as_deref().and_then(|year| year.parse().ok()),
+        }
+    }
+
+    /// Renders the entry as `Authors (Year). Title. Container.`
+    fn describe(&self, title: &str) -> String {
+        let mut description = String::new();
+        if !self.authors.is_empty() {
+            description.push_str(&self.authors.join(", "));
+            description.push(' ');
+        }
+        if let Some(year) = &self.year {
+            description.push_str(&format!("({}). ", year));
+        }
+        description.push_str(title.trim_end_matches('.'));
+        description.push('.');
+        if let Some(container) = &self.container {
+            description.push_str(&format!(" {}.", container.trim_end_matches('.')));
+        }
+        description.trim().to_string()
+    }
+}
+
+impl CitationFormat {
+    /// Reads a reference list into references.
+    ///
+    /// Entries identifying a paper of this platform become `Citation::Paper` if `resolve`
+    /// accepts its ID. Malformed entries are reported in the result one by one and do not
+    /// stop the rest of the document from being imported.
+    pub fn import_references(&self, document: &str, resolve: impl Fn(&PaperId) -> bool) -> ImportedReferences {
+        let entries = match self {
+            CitationFormat::BibTeX => parse_bibtex(document),
+            CitationFormat::Ris => parse_ris(document),
+            CitationFormat::CslJson => parse_csl_json(document),
+        };
+
+        let mut imported = ImportedReferences::default();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let result = entry.and_then(|entry| {
+                let key = entry.key.clone();
+                entry.into_reference(&resolve).map_err(|message| (key, message))
+            });
+            match result {
+                Ok(reference) => imported.references.push(reference),
+                Err((key, message)) => imported.errors.push(ImportError { index, key, message }),
+            }
+        }
+        imported
+    }
+}
+
+type ParsedEntry = Result<Entry, (Option<String>, String)>;
+
+fn parse_bibtex(document: &str) -> Vec<ParsedEntry> {
+    let mut parser = BibTeXParser {
+        chars: document.chars().collect(),
+        position: 0,
+        macros: HashMap::new(),
+    };
+
+    let mut entries = Vec::new();
+    while parser.skip_to_entry() {
+        let start = parser.position;
+        match parser.entry() {
+            Ok(Some(entry)) => entries.push(Ok(entry)),
+            Ok(None) => {}
+            Err(error) => {
+                entries.push(Err(error));
+                // Resume at the next entry, which is expected to start a line
+                parser.position = start + 1;
+                parser.skip_line();
+            }
+        }
+    }
+    entries
+}
+
+struct BibTeXParser {
+    chars: Vec<char>,
+    position: usize,
+    macros: HashMap<String, String>,
+}
+
+impl BibTeXParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    /// Moves to the next `@`, returning false at the end of the document
+    fn skip_to_entry(&mut self) -> bool {
+        while let Some(c) = self.peek() {
+            if c == '@' {
+                return true;
+            }
+            self.position += 1;
+        }
+        false
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || "_-:.+/".contains(c)) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn expect(&mut self, expected: char, key: &Option<String>) -> Result<(), (Option<String>, String)> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => Err((key.clone(), format!("expected '{}' but found '{}'", expected, c))),
+            None => Err((key.clone(), format!("expected '{}' but the document ended", expected))),
+        }
+    }
+
+    /// Parses the entry at the current `@`, returning `None` for comments, preambles and macros
+    fn entry(&mut self) -> Result<Option<Entry>, (Option<String>, String)> {
+        self.position += 1;
+        let kind = self.identifier().to_lowercase();
+        self.skip_whitespace();
+
+        let close = match self.peek() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => return Err((None, format!("entry @{} has no opening brace", kind))),
+        };
+        self.position += 1;
+
+        match kind.as_str() {
+            "comment" | "preamble" => {
+                self.position -= 1;
+                self.braced(&None)?;
+                return Ok(None);
+            }
+            "string" => {
+                self.skip_whitespace();
+                let name = self.identifier().to_lowercase();
+                self.expect('=', &None)?;
+                let value = self.value(&None)?;
+                self.expect(close, &None)?;
+                self.macros.insert(name, value);
+                return Ok(None);
+            }
+            _ => {}
+        }
+
+        self.skip_whitespace();
+        let key = Some(self.identifier()).filter(|key| !key.is_empty());
+        if key.is_none() {
+            return Err((None, format!("entry @{} has no key", kind)));
+        }
+
+        let mut fields = HashMap::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(c) if c == close => {
+                    self.position += 1;
+                    break;
+                }
+                Some(_) => return Err((key, "expected ',' between fields".to_string())),
+                None => return Err((key, "entry is not closed".to_string())),
+            }
+
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                continue;
+            }
+            let name = self.identifier().to_lowercase();
+            if name.is_empty() {
+                return Err((key, "expected a field name".to_string()));
+            }
+            self.expect('=', &key)?;
+            let value = self.value(&key)?;
+            fields.insert(name, value);
+        }
+
+        Ok(Some(bibtex_entry(key, fields)))
+    }
+
+    /// Parses a field value, including `#` concatenations
+    fn value(&mut self, key: &Option<String>) -> Result<String, (Option<String>, String)> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('{') => value.push_str(&self.braced(key)?),
+                Some('"') => value.push_str(&self.quoted(key)?),
+                Some(c) if c.is_ascii_digit() => value.push_str(&self.identifier()),
+                Some(c) if c.is_alphabetic() => {
+                    let name = self.identifier().to_lowercase();
+                    let expansion = self.macros.get(&name).cloned()
+                        .or_else(|| month_number(&name).map(|month| month.to_string()))
+                        .ok_or_else(|| (key.clone(), format!("undefined macro: {}", name)))?;
+                    value.push_str(&expansion);
+                }
+                _ => return Err((key.clone(), "expected a field value".to_string())),
+            }
+
+            self.skip_whitespace();
+            if self.peek() == Some('#') {
+                self.position += 1;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Reads a `{...}` group and returns its content without the outer braces
+    fn braced(&mut self, key: &Option<String>) -> Result<String, (Option<String>, String)> {
+        let start = self.position + 1;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            match c {
+                '\\' => self.position += 1,
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(self.chars[start..self.position - 1].iter().collect());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err((key.clone(), "unbalanced braces".to_string()))
+    }
+
+    fn quoted(&mut self, key: &Option<String>) -> Result<String, (Option<String>, String)> {
+        self.position += 1;
+        let start = self.position;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            match c {
+                '\\' => self.position += 1,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' if depth == 0 => return Ok(self.chars[start..self.position - 1].iter().collect()),
+                _ => {}
+            }
+        }
+        Err((key.clone(), "unterminated quoted value".to_string()))
+    }
+}
+
+/// Names of the month macros predefined by BibTeX styles
+pub(super) const BIBTEX_MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+fn month_number(name: &str) -> Option<u32> {
+    BIBTEX_MONTHS.iter().position(|month| *month == name).map(|index| index as u32 + 1)
+}
+
+fn bibtex_entry(key: Option<String>, fields: HashMap<String, String>) -> Entry {
+    let field = |name: &str| fields.get(name).map(|value| clean_latex(value)).filter(|value| !value.is_empty());
+    let is_arxiv = field("archiveprefix")
+        .or_else(|| field("eprinttype"))
+        .is_some_and(|archive| archive.eq_ignore_ascii_case("arxiv"));
+
+    Entry {
+        key,
+        title: field("title"),
+        authors: fields.get("author").map(|authors| split_bibtex_authors(authors)).unwrap_or_default(),
+        year: field("year"),
+        container: field("journal").or_else(|| field("booktitle")),
+        publisher: field("publisher").or_else(|| field("howpublished")),
+        number: field("number"),
+        doi: field("doi"),
+        arxiv: field("eprint").filter(|_| is_arxiv),
+        isbn: field("isbn"),
+        locator: field("pages"),
+        url: field("url"),
+        note: field("note"),
+    }
+}
+
+/// Splits a BibTeX author list on `and` outside of braces, normalizing `Last, First` names
+fn split_bibtex_authors(authors: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for word in authors.split_whitespace() {
+        if depth == 0 && word.eq_ignore_ascii_case("and") {
+            names.push(std::mem::take(&mut current));
+            continue;
+        }
+        depth += word.matches('{').count() as i32 - word.matches('}').count() as i32;
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    names.push(current);
+
+    names.into_iter()
+        .map(|name| {
+            let name = clean_latex(&name);
+            match name.split_once(", ") {
+                Some((last, first)) if !name.starts_with('{') => format!("{} {}", first, last),
+                _ => name,
+            }
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Strips grouping braces and the escapes a BibTeX exporter adds
+fn clean_latex(value: &str) -> String {
+    let value = value
+        .replace("\\textbackslash{}", "\\")
+        .replace("\\textasciitilde{}", "~")
+        .replace("\\textasciicircum{}", "^");
+
+    let mut cleaned = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|next| "{}&%$#_\\".contains(*next)) => {
+                cleaned.extend(chars.next());
+            }
+            '{' | '}' => {}
+            c => cleaned.push(c),
+        }
+    }
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_ris(document: &str) -> Vec<ParsedEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<Entry> = None;
+    let mut last_tag = "";
+
+    for line in document.lines() {
+        let line = line.trim_end();
+        let Some((tag, value)) = ris_tag(line) else {
+            // Lines without a tag continue the previous field
+            if let (Some(entry), false) = (&mut current, line.trim().is_empty()) {
+                append_ris(entry, last_tag, line.trim());
+            }
+            continue;
+        };
+
+        match tag {
+            "TY" => {
+                if let Some(entry) = current.replace(Entry::default()) {
+                    entries.push(Err((entry.key, "entry is not closed by ER".to_string())));
+                }
+            }
+            "ER" => match current.take() {
+                Some(entry) => entries.push(Ok(entry)),
+                None => entries.push(Err((None, "ER without a matching TY".to_string()))),
+            },
+            _ => match &mut current {
+                Some(entry) => set_ris(entry, tag, value),
+                None => entries.push(Err((None, format!("{} outside of an entry", tag)))),
+            },
+        }
+        last_tag = tag;
+    }
+    if let Some(entry) = current {
+        entries.push(Err((entry.key, "entry is not closed by ER".to_string())));
+    }
+    entries
+}
+
+/// Splits a `XX  - value` line into its tag and value
+fn ris_tag(line: &str) -> Option<(&str, &str)> {
+    let bytes = line.as_bytes();
+    let is_tagged = bytes.len() >= 5
+        && bytes[..2].iter().all(u8::is_ascii_alphanumeric)
+        && &bytes[2..5] == b"  -"
+        && bytes.get(5).is_none_or(|c| *c == b' ');
+
+    is_tagged.then(|| (&line[..2], line.get(6..).unwrap_or_default().trim()))
+}
+
+fn set_ris(entry: &mut Entry, tag: &str, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let value = value.to_string();
+    match tag {
+        "ID" => entry.key = Some(value),
+        "TI" | "T1" => entry.title = Some(value),
+        "AU" | "A1" => entry.authors.push(value),
+        "PY" | "Y1" => entry.year = Some(value.chars().take_while(char::is_ascii_digit).collect()),
+        "T2" | "JO" | "JF" => entry.container = Some(value),
+        "PB" => entry.publisher = Some(value),
+        "M1" => entry.number = Some(value),
+        "DO" => entry.doi = Some(value),
+        // SN also holds ISSNs, so only valid ISBNs are taken from it
+        "SN" if Isbn::new(&value).is_ok() => entry.isbn = Some(value),
+        "SP" => entry.locator = Some(value),
+        "EP" => if let Some(locator) = &mut entry.locator {
+            locator.push('-');
+            locator.push_str(&value);
+        },
+        "UR" => entry.url = Some(value),
+        "N1" => entry.note = Some(value),
+        _ => {}
+    }
+}
+
+fn append_ris(entry: &mut Entry, tag: &str, value: &str) {
+    let field = match tag {
+        "TI" | "T1" => &mut entry.title,
+        "N1" => &mut entry.note,
+        _ => return,
+    };
+    if let Some(field) = field {
+        field.push(' ');
+        field.push_str(value);
+    }
+}
+
+fn parse_csl_json(document: &str) -> Vec<ParsedEntry> {
+    let items = match JsonParser::parse(document) {
+        Ok(Json::Array(items)) => items,
+        Ok(item @ Json::Object(_)) => vec![item],
+        Ok(_) => return vec![Err((None, "document is neither a CSL-JSON array nor an item".to_string()))],
+        Err(message) => return vec![Err((None, format!("invalid JSON: {}", message)))],
+    };
+
+    items.into_iter().map(|item| {
+        let Json::Object(fields) = item else {
+            return Err((None, "item is not an object".to_string()));
+        };
+        let fields: HashMap<_, _> = fields.into_iter().collect();
+        let text = |name: &str| match fields.get(name) {
+            Some(Json::String(value)) => Some(value.trim().to_string()).filter(|value| !value.is_empty()),
+            Some(Json::Number(value)) => Some(value.clone()),
+            _ => None,
+        };
+        let key = text("id");
+
+        let authors = match fields.get("author") {
+            None => Vec::new(),
+            Some(Json::Array(authors)) => authors.iter()
+                .map(csl_name)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| (key.clone(), "author is not a list of names".to_string()))?,
+            Some(_) => return Err((key, "author is not a list of names".to_string())),
+        };
+
+        Ok(Entry {
+            key: key.clone(),
+            title: text("title"),
+            authors,
+            year: fields.get("issued").and_then(csl_year),
+            container: text("container-title"),
+            publisher: text("publisher"),
+            number: text("number"),
+            doi: text("DOI"),
+            arxiv: None,
+            isbn: text("ISBN"),
+            locator: text("page"),
+            url: text("URL"),
+            note: text("note"),
+        })
+    }).collect()
+}
+
+fn csl_name(name: &Json) -> Option<String> {
+    let Json::Object(parts) = name else {
+        return None;
+    };
+    let part = |key: &str| parts.iter().find_map(|(name, value)| match value {
+        Json::String(value) if name == key => Some(value.as_str()),
+        _ => None,
+    });
+
+    match (part("literal"), part("given"), part("family")) {
+        (Some(literal), _, _) => Some(literal.to_string()),
+        (None, Some(given), Some(family)) => Some(format!("{} {}", given, family)),
+        (None, Some(name), None) | (None, None, Some(name)) => Some(name.to_string()),
+        (None, None, None) => None,
+    }
+}
+
+fn csl_year(issued: &Json) -> Option<String> {
+    let Json::Object(parts) = issued else {
+        return None;
+    };
+    parts.iter().find_map(|(name, value)| match (name.as_str(), value) {
+        ("date-parts", Json::Array(dates)) => match dates.first() {
+            Some(Json::Array(date)) => match date.first() {
+                Some(Json::Number(year)) => Some(year.clone()),
+                Some(Json::String(year)) => Some(year.clone()),
+                _ => None,
+            },
+            _ => None,
+        },
+        ("raw", Json::String(raw)) => Some(raw.chars().take_while(char::is_ascii_digit).collect()),
+        _ => None,
+    })
+}
+
+/// Maximum nesting of arrays and objects in an imported JSON document
+const MAX_JSON_DEPTH: usize = 32;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+enum JsonError {
+    #[error("{0}")]
+    Syntax(String),
+    #[error("arrays and objects are nested more than {} levels deep", MAX_JSON_DEPTH)]
+    TooDeep,
+}
+
+/// JSON value, keeping numbers as written
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    source: &'a str,
+    position: usize,
+    /// Number of arrays and objects the parser is inside of
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(source: &'a str) -> Result<Json, JsonError> {
+        let mut parser = JsonParser { bytes: source.as_bytes(), source, position: 0, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < parser.bytes.len() {
+            return Err(JsonError::Syntax(format!("unexpected data at byte {}", parser.position)));
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.position).is_some_and(u8::is_ascii_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, expected: u8) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(JsonError::Syntax(format!("expected '{}' at byte {}", expected as char, self.position)))
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> Result<Json, JsonError> {
+        if self.source[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(JsonError::Syntax(format!("unexpected token at byte {}", self.position)))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, JsonError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.position) {
+            Some(b'{' | b'[') => {
+                if self.depth == MAX_JSON_DEPTH {
+                    return Err(JsonError::TooDeep);
+                }
+                self.depth += 1;
+                let value = if self.bytes[self.position] == b'{' { self.object() } else { self.array() };
+                self.depth -= 1;
+                value
+            }
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(c) if *c == b'-' || c.is_ascii_digit() => {
+                let start = self.position;
+                while self.bytes.get(self.position).is_some_and(|c| c.is_ascii_digit() || b"+-.eE".contains(c)) {
+                    self.position += 1;
+                }
+                Ok(Json::Number(self.source[start..self.position].to_string()))
+            }
+            Some(_) => Err(JsonError::Syntax(format!("unexpected token at byte {}", self.position))),
+            None => Err(JsonError::Syntax("unexpected end of document".to_string())),
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, JsonError> {
+        self.position += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&b']') {
+            self.position += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bytes.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(JsonError::Syntax(format!("expected ',' or ']' at byte {}", self.position))),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, JsonError> {
+        self.position += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&b'}') {
+            self.position += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.bytes.get(self.position) != Some(&b'"') {
+                return Err(JsonError::Syntax(format!("expected a field name at byte {}", self.position)));
+            }
+            let name = self.string()?;
+            self.eat(b':')?;
+            fields.push((name, self.value()?));
+            self.skip_whitespace();
+            match self.bytes.get(self.position) {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(JsonError::Syntax(format!("expected ',' or '}}' at byte {}", self.position))),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            let start = self.position;
+            while self.bytes.get(self.position).is_some_and(|c| *c != b'"' && *c != b'\\') {
+                self.position += 1;
+            }
+            value.push_str(&self.source[start..self.position]);
+
+            match self.bytes.get(self.position) {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(value);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = match self.bytes.get(self.position) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let high = self.hex_escape()?;
+                            let code = if (0xD800..0xDC00).contains(&high) && self.source[self.position + 1..].starts_with("\\u") {
+                                self.position += 2;
+                                let low = self.hex_escape()?;
+                                0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => return Err(JsonError::Syntax(format!("invalid escape at byte {}", self.position))),
+                    };
+                    value.push(escaped);
+                    self.position += 1;
+                }
+                _ => return Err(JsonError::Syntax("unterminated string".to_string())),
+            }
+        }
+    }
+
+    /// Reads the four hex digits following `\u`, leaving the position on the last digit
+    fn hex_escape(&mut self) -> Result<u32, JsonError> {
+        let digits = self.source.get(self.position + 1..self.position + 5)
+            .ok_or_else(|| JsonError::Syntax("truncated unicode escape".to_string()))?;
+        let code = u32::from_str_radix(digits, 16)
+            .map_err(|_| JsonError::Syntax(format!("invalid unicode escape at byte {}", self.position)))?;
+        self.position += 4;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citations(imported: &ImportedReferences) -> Vec<Citation> {
+        imported.references.iter().map(|reference| reference.citation.clone()).collect()
+    }
+
+    fn arche_paper() -> PaperId {
+        PaperId::from_str("2025-01-0001").unwrap()
+    }
+
+    #[test]
+    fn test_import_bibtex() {
+        let document = r#"
+            @string{acm = "Communications of the ACM"}
+            @comment{exported from a reference manager}
+
+            @article{knuth84,
+              author = {Knuth, Donald E.},
+              title = {{Literate} Programming},
+              journal = acm,
+              year = 1984,
+            }
+
+            @misc{arche:2025-01-0001,
+              title = {{On Typst}},
+              howpublished = {arche},
+              number = {2025-01-0001},
+            }
+
+            @misc{broken,
+              title = {Unclosed
+
+            @online{rust, url = {https://www.rust-lang.org}, doi = {10.1000/xyz} # {123}}
+            @misc{empty, year = {2020}}
+        "#;
+
+        let imported = CitationFormat::BibTeX.import_references(document, |id| *id == arche_paper());
+        assert_eq!(citations(&imported), vec![
+            Citation::Other("Donald E. Knuth (1984). Literate Programming. Communications of the ACM.".to_string()),
+            Citation::Paper(arche_paper()),
+            Citation::Doi { doi: Doi::new("10.1000/xyz123").unwrap(), work: WorkMetadata::default() },
+        ]);
+        assert_eq!(imported.errors.len(), 2);
+        assert_eq!(imported.errors[0].index, 2);
+        assert_eq!(imported.errors[0].key.as_deref(), Some("broken"));
+        assert_eq!(imported.errors[1].key.as_deref(), Some("empty"));
+    }
+
+    #[test]
+    fn test_import_identified_works() {
+        let document = r#"
+            @misc{attention, title = {Attention Is All You Need}, year = {2017}, eprint = {1706.03762}, archiveprefix = {arXiv}}
+            @book{taocp, title = {Fundamental Algorithms}, isbn = {978-0-201-89683-1}, pages = {42--45}}
+            @book{typo, title = {Misprinted}, isbn = {978-0-201-89683-2}}
+            @misc{http, url = {https://www.rfc-editor.org/rfc/rfc9110.html}}
+            @software{typst, author = {Mädje, Laurenz}, url = {https://github.com/typst/typst}}
+        "#;
+
+        let imported = CitationFormat::BibTeX.import_references(document, |_| false);
+        assert_eq!(citations(&imported), vec![
+            Citation::Arxiv {
+                id: ArxivId::new("1706.03762").unwrap(),
+                work: WorkMetadata { title: Some("Attention Is All You Need".to_string()), year: Some(2017), ..Default::default() },
+            },
+            Citation::Isbn {
+                isbn: Isbn::new("9780201896831").unwrap(),
+                work: WorkMetadata { title: Some("Fundamental Algorithms".to_string()), ..Default::default() },
+            },
+            Citation::Rfc { number: RfcNumber::new("9110").unwrap(), work: WorkMetadata::default() },
+            Citation::GitHub {
+                repository: GitHubRepository::new("typst/typst").unwrap(),
+                work: WorkMetadata { authors: vec!["Laurenz Mädje".to_string()], ..Default::default() },
+            },
+        ]);
+        assert_eq!(imported.references[1].context.locator.as_deref(), Some("42--45"));
+        assert_eq!(imported.errors, vec![ImportError {
+            index: 2,
+            key: Some("typo".to_string()),
+            message: "ISBN checksum does not match: 978-0-201-89683-2".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_import_unresolved_paper() {
+        let document = "@misc{arche:2025-01-0001, title = {On Typst}, howpublished = {arche}}";
+        let imported = CitationFormat::BibTeX.import_references(document, |_| false);
+        assert_eq!(citations(&imported), vec![Citation::Other("On Typst.".to_string())]);
+    }
+
+    #[test]
+    fn test_import_csl_json() {
+        let document = r#"[
+            {"id": "arche:2025-01-0001", "type": "article", "title": "On Typst"},
+            {"id": "ref2", "type": "webpage", "URL": "https://typst.app/docs"},
+            {"id": "ref3", "title": "Café \"Tables\"", "author": [{"family": "Doe", "given": "Jane"}], "issued": {"date-parts": [[2021, 3]]}},
+            {"id": "ref4", "author": "Jane Doe", "title": "Bad authors"},
+            42
+        ]"#;
+
+        let imported = CitationFormat::CslJson.import_references(document, |id| *id == arche_paper());
+        assert_eq!(citations(&imported), vec![
+            Citation::Paper(arche_paper()),
+            Citation::Url("https://typst.app/docs".to_string()),
+            Citation::Other("Jane Doe (2021). Café \"Tables\".".to_string()),
+        ]);
+        assert_eq!(imported.errors, vec![
+            ImportError { index: 3, key: Some("ref4".to_string()), message: "author is not a list of names".to_string() },
+            ImportError { index: 4, key: None, message: "item is not an object".to_string() },
+        ]);
+
+        let imported = CitationFormat::CslJson.import_references("[{\"id\": ", |_| false);
+        assert_eq!(citations(&imported), vec![]);
+        assert_eq!(imported.errors.len(), 1);
+
+        let nested = format!("{}{}", "[".repeat(MAX_JSON_DEPTH + 1), "]".repeat(MAX_JSON_DEPTH + 1));
+        assert_eq!(JsonParser::parse(&nested), Err(JsonError::TooDeep));
+        let nested = format!("{}{}", "[".repeat(MAX_JSON_DEPTH), "]".repeat(MAX_JSON_DEPTH));
+        assert!(JsonParser::parse(&nested).is_ok());
+    }
+
+    #[test]
+    fn test_import_ris() {
+        let document = "TY  - GEN\nID  - ref1\nTI  - A long\n  title\nAU  - Doe, Jane\nPY  - 2020///\nER  - \nTY  - ELEC\nER  - \n";
+        let imported = CitationFormat::Ris.import_references(document, |_| false);
+        assert_eq!(citations(&imported), vec![Citation::Other("Doe, Jane (2020). A long title.".to_string())]);
+        assert_eq!(imported.errors.len(), 1);
+        assert_eq!(imported.errors[0].index, 1);
+
+        let document = "TY  - JOUR\nTI  - Periodical\nSN  - 0317-8471\nER  - \nTY  - BOOK\nSN  - 0-306-40615-2\nSP  - 7\nEP  - 9\nER  - \n";
+        let imported = CitationFormat::Ris.import_references(document, |_| false);
+        assert_eq!(citations(&imported), vec![
+            Citation::Other("Periodical.".to_string()),
+            Citation::Isbn {
+                isbn: Isbn::new("0306406152").unwrap(),
+                work: WorkMetadata::default(),
+            },
+        ]);
+        assert_eq!(imported.references[1].context.locator.as_deref(), Some("7-9"));
+    }
+}
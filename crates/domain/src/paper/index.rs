@@ -0,0 +1,51 @@
+use crate::paper::PaperId;
+use std::ops::RangeInclusive;
+
+/// Key of a secondary index of papers: a value papers are filed under, such as an
+/// author or a tag, and a paper revision filed under it.
+///
+/// Keys order by value, then by identifier, so the revisions filed under a value
+/// are contiguous and in order of creation month.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PaperIndexKey<T> {
+    pub value: T,
+    pub paper_id: PaperId,
+}
+
+impl<T: Clone> PaperIndexKey<T> {
+    /// Returns the range of the keys of the revisions filed under the value with identifiers in `ids`
+    pub fn range(value: T, ids: RangeInclusive<PaperId>) -> RangeInclusive<Self> {
+        let (first, last) = ids.into_inner();
+        PaperIndexKey { value: value.clone(), paper_id: first }..=PaperIndexKey { value, paper_id: last }
+    }
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl<T: Storable> Storable for PaperIndexKey<T> {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned([self.value.to_bytes().as_ref(), &self.paper_id.to_bytes()].concat())
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            // The identifier has a fixed size, so the value is everything before it
+            let split = bytes.len() - PaperId::BOUND.max_size() as usize;
+            PaperIndexKey {
+                value: T::from_bytes(Cow::Borrowed(&bytes[..split])),
+                paper_id: PaperId::from_bytes(Cow::Borrowed(&bytes[split..])),
+            }
+        }
+
+        const BOUND: Bound = match T::BOUND {
+            Bound::Bounded { max_size, .. } => Bound::Bounded {
+                max_size: max_size + PaperId::BOUND.max_size(),
+                is_fixed_size: false,
+            },
+            Bound::Unbounded => Bound::Unbounded,
+        };
+    }
+}
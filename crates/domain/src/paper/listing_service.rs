@@ -0,0 +1,242 @@
+use crate::paper::{repository::PaperRepository, ListingCursor, PaperCategory, PaperId, PaperSort, PaperStatus, SortKey};
+use std::{
+    collections::VecDeque,
+    ops::RangeInclusive,
+};
+use util::time::date_from_nanos;
+
+/// Criteria papers must all meet to be listed
+pub struct PaperFilter<K> {
+    pub status: PaperStatus,
+    pub category: Option<PaperCategory>,
+    pub tag: Option<String>,
+    /// Lead author or co-author
+    pub author: Option<K>,
+    /// Earliest creation date, in nanoseconds since epoch
+    pub created_from: Option<u64>,
+    /// Creation date papers were created before, in nanoseconds since epoch
+    pub created_until: Option<u64>,
+}
+
+/// A page of a paper listing
+pub struct ListingPage {
+    /// Latest revision of each paper on the page
+    pub papers: Vec<PaperId>,
+    /// Position the next page starts after, if any papers may remain
+    pub next: Option<ListingCursor>,
+}
+
+/// Lists the latest revision of papers, a page at a time.
+///
+/// Listings only walk the indexes kept by the repository, so no paper is decoded.
+/// A filter on authors, categories, tags or creation dates matching few enough
+/// revisions starts from the revisions indexed under its narrowest criterion; other
+/// listings walk the papers with the status of the filter in listing order until the
+/// page is full. A walk looks at no more than [`Self::MAX_SCANNED`] papers, so a page
+/// of a filter few papers meet may come out short, or even empty, with a cursor to
+/// carry on from.
+pub struct ListingService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> ListingService<R>
+where
+    R: PaperRepository,
+{
+    /// Maximum number of papers on a page
+    pub const MAX_PAGE_SIZE: usize = 100;
+    /// Number of revisions read from the repository at once when walking a listing
+    const BATCH_SIZE: usize = 100;
+    /// Maximum number of revisions under a criterion for a listing to start from them
+    const MAX_CANDIDATES: usize = 1000;
+    /// Maximum number of papers looked at for a page
+    pub const MAX_SCANNED: usize = 1000;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns the page of papers matching the filter after `after`.
+    ///
+    /// When filtering by author, papers failing `shows_authors` are left out.
+    pub fn page(
+        &self,
+        filter: &PaperFilter<R::UserPrimaryKey>,
+        sort: PaperSort,
+        after: Option<&ListingCursor>,
+        page_size: usize,
+        shows_authors: impl Fn(&PaperId) -> bool,
+    ) -> ListingPage {
+        let positions: Box<dyn Iterator<Item = ListingCursor> + '_> = match self.indexed_candidates(filter) {
+            Some(candidates) => Box::new(self.sorted(candidates, sort, after)),
+            None => Box::new(self.walk(sort, filter.status, after.cloned())),
+        };
+
+        let page_size = page_size.clamp(1, Self::MAX_PAGE_SIZE);
+        let mut page = Vec::new();
+        let mut last = None;
+        for (scanned, position) in positions.enumerate() {
+            let matches = scanned < Self::MAX_SCANNED
+                && self.matches(filter, &position.paper_id)
+                && (filter.author.is_none() || shows_authors(&position.paper_id));
+            // Papers remain after a full page or after as many as may be looked at
+            if scanned == Self::MAX_SCANNED || (matches && page.len() == page_size) {
+                return ListingPage { papers: page, next: last };
+            }
+            if matches {
+                page.push(position.paper_id);
+            }
+            last = Some(position);
+        }
+        ListingPage { papers: page, next: None }
+    }
+
+    /// Returns the revisions indexed under the author, category, tag or creation months
+    /// of the filter with the fewest of them, or `None` if the filter has no such criteria
+    /// or each has more than `MAX_CANDIDATES` revisions
+    fn indexed_candidates(&self, filter: &PaperFilter<R::UserPrimaryKey>) -> Option<Vec<PaperId>> {
+        let months = Self::creation_months(filter);
+        let dated = filter.created_from.is_some() || filter.created_until.is_some();
+        // One more revision than the maximum is read to tell whether there are too many
+        let limit = Self::MAX_CANDIDATES + 1;
+        let sets = [
+            filter.author.as_ref().map(|author| self.repository.iter_by_author(author, months.clone(), limit).collect::<Vec<_>>()),
+            filter.category.as_ref().map(|category| self.repository.iter_by_category(category, months.clone(), limit).collect()),
+            filter.tag.as_deref().map(|tag| self.repository.iter_by_tag(tag, months.clone(), limit).collect()),
+            dated.then(|| self.repository.iter_by_month(months.clone(), limit).collect()),
+        ];
+        sets.into_iter().flatten()
+            .filter(|candidates| candidates.len() <= Self::MAX_CANDIDATES)
+            .min_by_key(Vec::len)
+    }
+
+    /// Returns the months since epoch spanned by the creation dates of the filter.
+    ///
+    /// Identifiers carry the month their paper was first created in, as creation dates
+    /// of all revisions do, so they narrow down papers before their dates are compared.
+    fn creation_months(filter: &PaperFilter<R::UserPrimaryKey>) -> RangeInclusive<u16> {
+        let months = |nanos: u64| {
+            let (year, month, _) = date_from_nanos(nanos);
+            (year.saturating_sub(1970) * 12 + month - 1).min(u16::MAX as u32) as u16
+        };
+        filter.created_from.map_or(0, months)..=filter.created_until.map_or(u16::MAX, months)
+    }
+
+    /// Orders revisions by their position in the listing, keeping those after `after`
+    fn sorted(&self, candidates: Vec<PaperId>, sort: PaperSort, after: Option<&ListingCursor>) -> impl Iterator<Item = ListingCursor> {
+        let mut positions: Vec<_> = candidates.iter()
+            .filter_map(|paper_id| self.repository.get_listing_cursor(sort, paper_id))
+            .filter(|position| after.is_none_or(|after| position.cmp_listed(after).is_gt()))
+            .collect();
+        positions.sort_by(ListingCursor::cmp_listed);
+        positions.into_iter()
+    }
+
+    /// Walks the latest revisions with the status in listing order after `after`, a batch at a time
+    fn walk(&self, sort: PaperSort, status: PaperStatus, mut after: Option<ListingCursor>) -> impl Iterator<Item = ListingCursor> + '_ {
+        let mut batch = VecDeque::new();
+        std::iter::from_fn(move || {
+            if batch.is_empty() {
+                batch.extend(self.repository.iter_sorted(sort, status, after.as_ref(), Self::BATCH_SIZE));
+            }
+            let position = batch.pop_front()?;
+            after = Some(position.clone());
+            Some(position)
+        })
+    }
+
+    /// Whether a revision is the latest of its paper and meets every criterion of the filter
+    fn matches(&self, filter: &PaperFilter<R::UserPrimaryKey>, paper_id: &PaperId) -> bool {
+        if self.repository.latest_version(paper_id) != Some(*paper_id) {
+            return false;
+        }
+        if self.repository.get_summary(paper_id).is_none_or(|summary| summary.status != filter.status) {
+            return false;
+        }
+        if !(filter.author.as_ref().is_none_or(|author| self.repository.is_by_author(author, paper_id))
+            && filter.category.as_ref().is_none_or(|category| self.repository.has_category(category, paper_id))
+            && filter.tag.as_deref().is_none_or(|tag| self.repository.has_tag(tag, paper_id)))
+        {
+            return false;
+        }
+        if filter.created_from.is_none() && filter.created_until.is_none() {
+            return true;
+        }
+
+        // Newest-first listings are sorted by creation date
+        let Some(SortKey::Number(created_at)) = self.repository.get_listing_cursor(PaperSort::Newest, paper_id).map(|position| position.key) else {
+            return false;
+        };
+        filter.created_from.is_none_or(|from| created_at >= from)
+            && filter.created_until.is_none_or(|until| created_at < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{id, paper, MemoryPaperRepository};
+
+    type Service = ListingService<MemoryPaperRepository>;
+
+    fn filter(author: Option<u8>) -> PaperFilter<u8> {
+        PaperFilter { status: PaperStatus::Published, category: None, tag: None, author, created_from: None, created_until: None }
+    }
+
+    /// Returns the identifiers of the papers on each page of the listing, newest first
+    fn pages(service: &Service, filter: &PaperFilter<u8>, page_size: usize) -> Vec<Vec<PaperId>> {
+        let mut pages = Vec::new();
+        let mut after = None;
+        loop {
+            let page = service.page(filter, PaperSort::Newest, after.as_ref(), page_size, |_| true);
+            pages.push(page.papers);
+            match page.next {
+                Some(next) => after = Some(next),
+                None => return pages,
+            }
+        }
+    }
+
+    #[test]
+    fn test_page() {
+        // Papers by odd authors are written by 1, and paper 3 is a draft
+        let papers = (1..=5).map(|number| {
+            let status = if number == 3 { PaperStatus::Draft } else { PaperStatus::Published };
+            paper(id(number), (number % 2) as u8, status)
+        });
+        let service = Service::new(MemoryPaperRepository::with(papers));
+
+        assert_eq!(pages(&service, &filter(None), 2), vec![vec![id(5), id(4)], vec![id(2), id(1)]]);
+        assert_eq!(pages(&service, &filter(Some(1)), 1), vec![vec![id(5)], vec![id(1)]]);
+    }
+
+    #[test]
+    fn test_page_with_many_candidates() {
+        // Every fourth paper is by author 2 and the rest by author 1, too many to start from
+        let papers = (1..=1500).map(|number| paper(id(number), if number % 4 == 0 { 2 } else { 1 }, PaperStatus::Published));
+        let service = Service::new(MemoryPaperRepository::with(papers));
+
+        let page = service.page(&filter(Some(1)), PaperSort::Newest, None, 2, |_| true);
+        assert_eq!(page.papers, vec![id(1499), id(1498)]);
+        let page = service.page(&filter(Some(1)), PaperSort::Newest, page.next.as_ref(), 2, |_| true);
+        assert_eq!(page.papers, vec![id(1497), id(1495)]);
+    }
+
+    #[test]
+    fn test_page_scan_budget() {
+        // Author 1 writes every paper but is only shown on the oldest one
+        let papers = (1..=1500).map(|number| paper(id(number), 1, PaperStatus::Published));
+        let service = Service::new(MemoryPaperRepository::with(papers));
+        let shows_authors = |paper_id: &PaperId| *paper_id == id(1);
+
+        let page = service.page(&filter(Some(1)), PaperSort::Newest, None, 10, shows_authors);
+        assert!(page.papers.is_empty());
+        assert_eq!(page.next.as_ref().map(|next| next.paper_id), Some(id(1500 - Service::MAX_SCANNED as u32 + 1)));
+        let page = service.page(&filter(Some(1)), PaperSort::Newest, page.next.as_ref(), 10, shows_authors);
+        assert_eq!(page.papers, vec![id(1)]);
+        assert_eq!(page.next, None);
+    }
+}
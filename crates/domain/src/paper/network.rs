@@ -0,0 +1,474 @@
+use crate::{
+    paper::{entity::model::PaperMetadata, repository::PaperRepository, PaperId},
+    user::UserPrimaryKey,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    ops::Bound,
+};
+
+/// A page of the citation network
+pub struct CitationNetworkPage<K: UserPrimaryKey> {
+    /// Latest public revision of each paper on the page
+    pub papers: Vec<PaperMetadata<K>>,
+    /// Citing and cited paper of each citation made by a paper on the page
+    pub citations: Vec<(PaperId, PaperId)>,
+    /// Paper the next page starts after, if any papers remain
+    pub next: Option<PaperId>,
+}
+
+/// A page of the co-authorship network
+pub struct CoAuthorshipNetworkPage<K> {
+    /// Authors of the papers on the page who wrote none of the papers on earlier pages
+    pub authors: Vec<K>,
+    /// Each pair of authors of a paper on the page, with that paper
+    pub collaborations: Vec<(K, K, PaperId)>,
+    /// Paper the next page starts after, if any papers remain
+    pub next: Option<PaperId>,
+}
+
+/// Builds the citation and co-authorship networks of the papers visible to the public.
+///
+/// Papers are identified by their first revision and described by their latest public
+/// revision, so a paper is a single node however many revisions it has. A paper cites
+/// another if any of its public revisions does, as for citation metrics.
+///
+/// Networks cover either every public paper or the neighborhood of a paper, and are
+/// built a page of papers at a time, in identifier order, so that a single call stays
+/// within the instruction limit. Pages of a whole network seek from the cursor through
+/// the revisions and stop once full; pages of a neighborhood walk the indexes of the
+/// papers and authors it reaches. A neighborhood stops growing at
+/// [`Self::MAX_NEIGHBORHOOD`] papers, nearest first, and only the first
+/// [`Self::MAX_AUTHOR_REVISIONS`] revisions of each author are followed.
+pub struct NetworkService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> NetworkService<R>
+where
+    R: PaperRepository,
+{
+    /// Maximum number of hops around a paper a neighborhood may span
+    pub const MAX_HOPS: u8 = 5;
+    /// Number of papers covered by a page
+    pub const PAGE_SIZE: usize = 100;
+    /// Maximum number of papers a neighborhood may cover
+    pub const MAX_NEIGHBORHOOD: usize = 500;
+    /// Maximum number of revisions read from the index of an author at a time
+    pub const MAX_AUTHOR_REVISIONS: usize = 100;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns a page of the network of papers and their citations.
+    ///
+    /// With a `center`, the network only covers the papers linked to it by a chain of
+    /// at most the given number of citations, whichever way they point.
+    pub fn citation_page(&self, center: Option<(&PaperId, u8)>, after: Option<&PaperId>) -> CitationNetworkPage<R::UserPrimaryKey> {
+        let scope = center.map(|(paper_id, hops)| self.citation_neighborhood(paper_id, hops));
+        let (page, next) = match &scope {
+            Some(scope) => Self::page(scope.range(Self::after(after)).map(|paper| (*paper, ()))),
+            None => Self::page(self.public_papers(after).map(|paper| (paper, ()))),
+        };
+
+        let papers = page.iter()
+            .filter_map(|(paper, _)| self.latest_public_revision(paper))
+            .filter_map(|revision| self.repository.get_metadata(&revision))
+            .collect();
+        // Cited papers are all public, so only a neighborhood needs checking
+        let citations = page.iter()
+            .flat_map(|(citing, _)| self.cited_papers(citing).into_iter()
+                .filter(|cited| scope.as_ref().is_none_or(|scope| scope.contains(cited)))
+                .map(|cited| (*citing, cited))
+                .collect::<Vec<_>>())
+            .collect();
+
+        CitationNetworkPage { papers, citations, next }
+    }
+
+    /// Returns a page of the network of authors and the papers they wrote together.
+    ///
+    /// Papers whose latest public revision fails `shows_authors` are left out. With a `center`, the
+    /// network only covers the authors linked to the authors of the paper by a chain of
+    /// at most the given number of co-authored papers.
+    pub fn co_authorship_page(
+        &self,
+        center: Option<(&PaperId, u8)>,
+        after: Option<&PaperId>,
+        shows_authors: impl Fn(&PaperId) -> bool,
+    ) -> CoAuthorshipNetworkPage<R::UserPrimaryKey> {
+        let scope = center.map(|(paper_id, hops)| self.co_authorship_neighborhood(paper_id, hops, &shows_authors));
+        let (page, next) = match &scope {
+            Some(scope) => Self::page(scope.range(Self::after(after)).map(|(paper, authors)| (*paper, authors.clone()))),
+            None => Self::page(self.public_papers(after)
+                .filter_map(|paper| Some((paper, self.shown_authors(&paper, &shows_authors)?)))),
+        };
+
+        // Authors listed on an earlier page wrote one of the papers before this page
+        let wrote_earlier = |author: &R::UserPrimaryKey, first: &PaperId| match &scope {
+            Some(scope) => scope.range(..first).any(|(_, authors)| authors.contains(author)),
+            None => self.wrote_before(author, first, &shows_authors),
+        };
+
+        let mut seen = BTreeSet::new();
+        let mut new_authors = Vec::new();
+        let mut collaborations = Vec::new();
+        for (paper, paper_authors) in &page {
+            for (index, author) in paper_authors.iter().enumerate() {
+                if seen.insert(*author) && !wrote_earlier(author, &page[0].0) {
+                    new_authors.push(*author);
+                }
+                for other in &paper_authors[index + 1..] {
+                    collaborations.push((*author, *other, *paper));
+                }
+            }
+        }
+
+        CoAuthorshipNetworkPage { authors: new_authors, collaborations, next }
+    }
+
+    /// Returns the latest revision of a paper visible to the public
+    pub fn latest_public_revision(&self, paper_id: &PaperId) -> Option<PaperId> {
+        self.repository.iter_versions(paper_id)
+            .filter(|version| self.repository.get_summary(version).is_some_and(|summary| summary.status.is_public()))
+            .last()
+    }
+
+    /// Returns the papers with a revision visible to the public, after `after` in identifier order.
+    ///
+    /// Revisions are read a page at a time from the cursor, so only as many are read as
+    /// the papers taken need.
+    fn public_papers(&self, after: Option<&PaperId>) -> impl Iterator<Item = PaperId> + '_ {
+        let mut cursor = after.copied();
+        let mut last = after.map(PaperId::first_version);
+        let mut revisions = VecDeque::new();
+        std::iter::from_fn(move || loop {
+            if revisions.is_empty() {
+                revisions.extend(self.repository.iter_ids(cursor.as_ref(), Self::PAGE_SIZE));
+                cursor = Some(*revisions.back()?);
+            }
+            let revision = revisions.pop_front()?;
+            let paper = revision.first_version();
+            if last != Some(paper) && self.repository.get_summary(&revision).is_some_and(|summary| summary.status.is_public()) {
+                last = Some(paper);
+                return Some(paper);
+            }
+        })
+    }
+
+    /// Returns the authors of the latest public revision of the paper, unless it fails `shows_authors`
+    fn shown_authors(&self, paper_id: &PaperId, shows_authors: &impl Fn(&PaperId) -> bool) -> Option<Vec<R::UserPrimaryKey>> {
+        let revision = self.latest_public_revision(paper_id).filter(|revision| shows_authors(revision))?;
+        Some(self.repository.iter_authors(&revision).collect())
+    }
+
+    /// Returns the public papers showing the user among their authors, with those authors,
+    /// out of the first [`Self::MAX_AUTHOR_REVISIONS`] revisions of the user
+    fn papers_of(
+        &self,
+        author: &R::UserPrimaryKey,
+        shows_authors: &impl Fn(&PaperId) -> bool,
+    ) -> BTreeMap<PaperId, Vec<R::UserPrimaryKey>> {
+        let papers: BTreeSet<_> = self.repository.iter_by_author(author, 0..=u16::MAX, Self::MAX_AUTHOR_REVISIONS)
+            .map(|revision| revision.first_version())
+            .collect();
+        papers.into_iter()
+            .filter_map(|paper| Some((paper, self.shown_authors(&paper, shows_authors)?)))
+            .filter(|(_, authors)| authors.contains(author))
+            .collect()
+    }
+
+    /// Checks whether the user is shown among the authors of a public paper before `first`.
+    ///
+    /// Only the first [`Self::MAX_AUTHOR_REVISIONS`] revisions of the user are read, so an
+    /// author with more may be listed again on a later page, but never left out.
+    fn wrote_before(&self, author: &R::UserPrimaryKey, first: &PaperId, shows_authors: &impl Fn(&PaperId) -> bool) -> bool {
+        let papers: BTreeSet<_> = self.repository.iter_by_author(author, 0..=first.months(), Self::MAX_AUTHOR_REVISIONS)
+            .map(|revision| revision.first_version())
+            .filter(|paper| paper < first)
+            .collect();
+        papers.iter()
+            .any(|paper| self.shown_authors(paper, shows_authors).is_some_and(|authors| authors.contains(author)))
+    }
+
+    /// Returns the public papers cited by any public revision of the paper
+    fn cited_papers(&self, paper_id: &PaperId) -> BTreeSet<PaperId> {
+        self.repository.iter_versions(paper_id)
+            .filter(|version| self.repository.get_summary(version).is_some_and(|summary| summary.status.is_public()))
+            .flat_map(|version| self.repository.iter_referenced(&version).collect::<Vec<_>>())
+            .filter(|cited| !cited.is_same_paper(paper_id))
+            .map(|cited| cited.first_version())
+            .filter(|cited| self.latest_public_revision(cited).is_some())
+            .collect()
+    }
+
+    /// Iterates over the public papers citing any revision of the paper, once per citing revision.
+    ///
+    /// Citations only list public revisions, so no status needs checking.
+    fn citing_papers<'a>(&'a self, paper_id: &'a PaperId) -> impl Iterator<Item = PaperId> + 'a {
+        self.repository.iter_versions(paper_id)
+            .flat_map(|version| self.repository.iter_citing(&version).collect::<Vec<_>>())
+            .filter(|citing| !citing.is_same_paper(paper_id))
+            .map(|citing| citing.first_version())
+    }
+
+    /// Returns the public papers within `hops` citations of the paper, including itself,
+    /// up to [`Self::MAX_NEIGHBORHOOD`] of them
+    fn citation_neighborhood(&self, paper_id: &PaperId, hops: u8) -> BTreeSet<PaperId> {
+        let origin = paper_id.first_version();
+        if self.latest_public_revision(&origin).is_none() {
+            return BTreeSet::new();
+        }
+
+        let hops = hops.min(Self::MAX_HOPS);
+        let mut reached = BTreeSet::from([origin]);
+        let mut queue = VecDeque::from([(origin, 0)]);
+        while let Some((paper, distance)) = queue.pop_front() {
+            if distance == hops {
+                continue;
+            }
+            for neighbor in self.cited_papers(&paper).into_iter().chain(self.citing_papers(&paper)) {
+                if reached.len() == Self::MAX_NEIGHBORHOOD {
+                    return reached;
+                }
+                if reached.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+        reached
+    }
+
+    /// Returns the papers of the authors within `hops` co-authored papers of the authors
+    /// of the paper, each with those of its authors in the neighborhood, up to
+    /// [`Self::MAX_NEIGHBORHOOD`] papers
+    fn co_authorship_neighborhood(
+        &self,
+        paper_id: &PaperId,
+        hops: u8,
+        shows_authors: &impl Fn(&PaperId) -> bool,
+    ) -> BTreeMap<PaperId, Vec<R::UserPrimaryKey>> {
+        let Some(origin) = self.shown_authors(&paper_id.first_version(), shows_authors) else {
+            return BTreeMap::new();
+        };
+
+        let hops = hops.min(Self::MAX_HOPS);
+        let mut papers = BTreeMap::new();
+        let mut reached: BTreeSet<_> = origin.iter().copied().collect();
+        let mut queue: VecDeque<_> = reached.iter().map(|author| (*author, 0)).collect();
+        'walk: while let Some((author, distance)) = queue.pop_front() {
+            for (paper, authors) in self.papers_of(&author, shows_authors) {
+                if papers.len() == Self::MAX_NEIGHBORHOOD && !papers.contains_key(&paper) {
+                    break 'walk;
+                }
+                if distance < hops {
+                    for co_author in &authors {
+                        if reached.insert(*co_author) {
+                            queue.push_back((*co_author, distance + 1));
+                        }
+                    }
+                }
+                papers.insert(paper, authors);
+            }
+        }
+
+        // Co-authors beyond the last hop are left out
+        for authors in papers.values_mut() {
+            authors.retain(|author| reached.contains(author));
+        }
+        papers
+    }
+
+    /// Returns the bounds of the papers after `after`
+    fn after(after: Option<&PaperId>) -> (Bound<PaperId>, Bound<PaperId>) {
+        (after.map_or(Bound::Unbounded, |after| Bound::Excluded(*after)), Bound::Unbounded)
+    }
+
+    /// Takes the papers making up a page, along with the paper the next page starts after
+    fn page<T>(mut papers: impl Iterator<Item = (PaperId, T)>) -> (Vec<(PaperId, T)>, Option<PaperId>) {
+        let page: Vec<_> = papers.by_ref().take(Self::PAGE_SIZE).collect();
+        let next = papers.next().and(page.last().map(|(paper, _)| *paper));
+        (page, next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        paper::{entity::model::Paper, Citation, PaperStatus, Reference},
+        testing::{id, paper, MemoryPaperRepository},
+    };
+
+    type Service = NetworkService<MemoryPaperRepository>;
+
+    /// Returns paper `number`, published by the given authors and citing `cited`
+    fn published(number: u32, authors: &[u8], cited: &[u32]) -> Paper<u8> {
+        let mut paper = paper(id(number), authors[0], PaperStatus::Published);
+        paper.co_authors = authors[1..].to_vec();
+        paper.references = cited.iter().map(|cited| Reference::new(Citation::Paper(id(*cited)))).collect();
+        paper
+    }
+
+    /// Returns a repository holding the papers, each listing the papers citing it
+    fn with_citations(papers: Vec<Paper<u8>>) -> MemoryPaperRepository {
+        let references: Vec<_> = papers.iter()
+            .flat_map(|citing| citing.referenced_papers().into_iter().map(|cited| (cited, citing.id)))
+            .collect();
+        MemoryPaperRepository::with(papers.into_iter().map(|mut paper| {
+            paper.citations = references.iter()
+                .filter(|(cited, _)| *cited == paper.id)
+                .map(|(_, citing)| Citation::Paper(*citing))
+                .collect();
+            paper
+        }))
+    }
+
+    #[test]
+    fn test_citation_page() {
+        // Every third paper is a draft, and each paper cites the one before it
+        let papers = (1..=200).map(|number| match number {
+            1 => published(1, &[1], &[]),
+            _ if number % 3 == 0 => paper(id(number), 1, PaperStatus::Draft),
+            _ => published(number, &[1], &[number - 1]),
+        });
+        let service = Service::new(with_citations(papers.collect()));
+
+        let first = service.citation_page(None, None);
+        assert_eq!(first.papers.len(), Service::PAGE_SIZE);
+        assert_eq!(first.papers[0].id, id(1));
+        assert_eq!(first.next, Some(first.papers[Service::PAGE_SIZE - 1].id));
+        assert!(first.papers.iter().all(|paper| paper.status.is_public()));
+        // Citations of drafts and of papers that don't exist are left out
+        assert!(first.citations.contains(&(id(2), id(1))));
+        assert!(!first.citations.iter().any(|(_, cited)| *cited == id(3)));
+
+        let second = service.citation_page(None, first.next.as_ref());
+        assert_eq!(second.papers.len(), 134 - Service::PAGE_SIZE);
+        assert_eq!(second.next, None);
+        assert_eq!(second.papers.last().map(|paper| paper.id), Some(id(200)));
+    }
+
+    #[test]
+    fn test_citation_page_across_revisions() {
+        // The second revision of 2 is public while the first is a draft
+        let revision = PaperId::new(0, 2, 2).unwrap();
+        let mut second = published(2, &[1], &[1]);
+        second.id = revision;
+        let service = Service::new(with_citations(vec![
+            published(1, &[1], &[]),
+            paper(id(2), 1, PaperStatus::Draft),
+            second,
+            published(3, &[1], &[2]),
+        ]));
+
+        let page = service.citation_page(None, None);
+        assert_eq!(page.papers.iter().map(|paper| paper.id).collect::<Vec<_>>(), vec![id(1), revision, id(3)]);
+        assert_eq!(page.citations, vec![(id(2), id(1)), (id(3), id(2))]);
+
+        // A page after a paper skips its other revisions
+        let page = service.citation_page(None, Some(&id(2)));
+        assert_eq!(page.papers.iter().map(|paper| paper.id).collect::<Vec<_>>(), vec![id(3)]);
+    }
+
+    #[test]
+    fn test_citation_neighborhood_page() {
+        // 2 cites 1, 3 cites 2 and 4 cites 3, while 5 cites nothing
+        let service = Service::new(with_citations(vec![
+            published(1, &[1], &[]),
+            published(2, &[1], &[1]),
+            published(3, &[1], &[2]),
+            published(4, &[1], &[3]),
+            published(5, &[1], &[]),
+        ]));
+
+        let page = service.citation_page(Some((&id(2), 1)), None);
+        assert_eq!(page.papers.iter().map(|paper| paper.id).collect::<Vec<_>>(), vec![id(1), id(2), id(3)]);
+        assert_eq!(page.citations, vec![(id(2), id(1)), (id(3), id(2))]);
+        assert_eq!(page.next, None);
+
+        let page = service.citation_page(Some((&id(2), 1)), Some(&id(1)));
+        assert_eq!(page.papers.iter().map(|paper| paper.id).collect::<Vec<_>>(), vec![id(2), id(3)]);
+    }
+
+    #[test]
+    fn test_co_authorship_page() {
+        // Authors 1 and 2 write every paper together, each with a third author of their
+        // own, apart from paper 150 whose authors are hidden
+        let papers = (1..=200).map(|number| published(number, &[1, 2, (number % 50) as u8 + 10], &[]));
+        let service = Service::new(MemoryPaperRepository::with(papers));
+        let shows_authors = |paper: &PaperId| *paper != id(150);
+
+        let first = service.co_authorship_page(None, None, shows_authors);
+        assert_eq!(first.next, Some(id(100)));
+        assert_eq!(first.authors.len(), 52);
+        assert_eq!(first.collaborations.len(), 3 * Service::PAGE_SIZE);
+        assert_eq!(first.collaborations[0], (1, 2, id(1)));
+
+        // Authors listed on the first page are not listed again
+        let second = service.co_authorship_page(None, first.next.as_ref(), shows_authors);
+        assert_eq!(second.next, None);
+        assert!(second.authors.is_empty());
+        assert_eq!(second.collaborations.len(), 3 * 99);
+        assert!(!second.collaborations.iter().any(|(_, _, paper)| *paper == id(150)));
+    }
+
+    #[test]
+    fn test_co_authorship_neighborhood_page() {
+        // 1 writes with 2, 2 with 3, and 3 with 4, while 5 writes alone
+        let service = Service::new(MemoryPaperRepository::with([
+            published(1, &[1, 2], &[]),
+            published(2, &[2, 3], &[]),
+            published(3, &[3, 4], &[]),
+            published(4, &[5], &[]),
+        ]));
+
+        let page = service.co_authorship_page(Some((&id(1), 1)), None, |_| true);
+        assert_eq!(page.authors, vec![1, 2, 3]);
+        // Co-authors beyond the last hop are left out of the papers of the neighborhood
+        assert_eq!(page.collaborations, vec![(1, 2, id(1)), (2, 3, id(2))]);
+
+        let page = service.co_authorship_page(Some((&id(1), 1)), Some(&id(1)), |_| true);
+        assert_eq!(page.authors, vec![3]);
+
+        // Papers whose authors are hidden don't link their authors
+        let page = service.co_authorship_page(Some((&id(1), 5)), None, |paper| *paper != id(2));
+        assert_eq!(page.authors, vec![1, 2]);
+        assert!(service.co_authorship_page(Some((&id(1), 1)), None, |paper| *paper != id(1)).authors.is_empty());
+    }
+
+    #[test]
+    fn test_neighborhood_bounds() {
+        // Every paper cites paper 1, and author 1 writes all of them with author 2
+        let count = Service::MAX_NEIGHBORHOOD as u32 + 50;
+        let papers = (1..=count).map(|number| match number {
+            1 => published(1, &[1, 2], &[]),
+            _ => published(number, &[1, 2], &[1]),
+        });
+        let service = Service::new(with_citations(papers.collect()));
+
+        let mut papers = Vec::new();
+        let mut after = None;
+        loop {
+            let page = service.citation_page(Some((&id(1), 1)), after.as_ref());
+            papers.extend(page.papers.into_iter().map(|paper| paper.id));
+            match page.next {
+                Some(next) => after = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(papers.len(), Service::MAX_NEIGHBORHOOD);
+        assert_eq!(papers[0], id(1));
+
+        // Only the first revisions of each author are followed
+        let page = service.co_authorship_page(Some((&id(1), 1)), None, |_| true);
+        assert_eq!(page.authors, vec![1, 2]);
+        let page = service.co_authorship_page(Some((&id(1), 1)), Some(&id(Service::MAX_AUTHOR_REVISIONS as u32 - 1)), |_| true);
+        assert_eq!(page.collaborations, vec![(1, 2, id(Service::MAX_AUTHOR_REVISIONS as u32))]);
+        assert_eq!(page.next, None);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::paper::GraphFormat;
+use std::fmt::Write;
+
+/// Shape of an exported network: its name, whether edges have a direction and the
+/// data that may be attached to nodes and edges
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Network {
+    pub name: &'static str,
+    pub directed: bool,
+    pub node_keys: &'static [&'static str],
+    pub edge_keys: &'static [&'static str],
+}
+
+impl Network {
+    /// Papers, each pointing at the papers it references
+    pub const CITATIONS: Network = Network {
+        name: "citations",
+        directed: true,
+        node_keys: &["label", "category", "status"],
+        edge_keys: &[],
+    };
+
+    /// Users, linked once for every paper they wrote together
+    pub const CO_AUTHORSHIP: Network = Network {
+        name: "co_authorship",
+        directed: false,
+        node_keys: &["label"],
+        edge_keys: &["paper"],
+    };
+}
+
+/// A node of an exported network.
+///
+/// Data is given as pairs of a node key of the network and its value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetworkNode {
+    pub id: String,
+    pub data: Vec<(&'static str, String)>,
+}
+
+/// An edge of an exported network.
+///
+/// Data is given as pairs of an edge key of the network and its value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetworkEdge {
+    pub source: String,
+    pub target: String,
+    pub data: Vec<(&'static str, String)>,
+}
+
+impl GraphFormat {
+    /// Exports a page of a network.
+    ///
+    /// Networks too large to export in one call are exported a page at a time. Only the
+    /// `first` page opens the document and only the `last` one closes it, so the pages
+    /// joined in order make up a single document.
+    pub fn export_network(&self, network: &Network, nodes: &[NetworkNode], edges: &[NetworkEdge], first: bool, last: bool) -> String {
+        match self {
+            GraphFormat::GraphML => write_graphml(network, nodes, edges, first, last),
+            GraphFormat::Dot => write_dot(network, nodes, edges, first, last),
+        }
+    }
+}
+
+fn write_graphml(network: &Network, nodes: &[NetworkNode], edges: &[NetworkEdge], first: bool, last: bool) -> String {
+    let mut out = String::new();
+    if first {
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        let keys = network.node_keys.iter().map(|key| ("node", key))
+            .chain(network.edge_keys.iter().map(|key| ("edge", key)));
+        for (domain, key) in keys {
+            let _ = writeln!(out, "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"string\"/>", key, domain, key);
+        }
+        let direction = if network.directed { "directed" } else { "undirected" };
+        let _ = writeln!(out, "  <graph id=\"{}\" edgedefault=\"{}\">", network.name, direction);
+    }
+
+    for node in nodes {
+        let _ = write!(out, "    <node id=\"{}\"", escape_xml(&node.id));
+        write_graphml_data(&mut out, &node.data, "node");
+    }
+    for edge in edges {
+        let _ = write!(out, "    <edge source=\"{}\" target=\"{}\"", escape_xml(&edge.source), escape_xml(&edge.target));
+        write_graphml_data(&mut out, &edge.data, "edge");
+    }
+
+    if last {
+        out.push_str("  </graph>\n");
+        out.push_str("</graphml>\n");
+    }
+    out
+}
+
+/// Closes the element opened for a node or edge, with its data as children
+fn write_graphml_data(out: &mut String, data: &[(&'static str, String)], element: &str) {
+    if data.is_empty() {
+        out.push_str("/>\n");
+        return;
+    }
+
+    out.push_str(">\n");
+    for (key, value) in data {
+        let _ = writeln!(out, "      <data key=\"{}\">{}</data>", key, escape_xml(value));
+    }
+    let _ = writeln!(out, "    </{}>", element);
+}
+
+fn write_dot(network: &Network, nodes: &[NetworkNode], edges: &[NetworkEdge], first: bool, last: bool) -> String {
+    let mut out = String::new();
+    if first {
+        let kind = if network.directed { "digraph" } else { "graph" };
+        let _ = writeln!(out, "{} \"{}\" {{", kind, escape_dot(network.name));
+    }
+
+    for node in nodes {
+        let _ = write!(out, "  \"{}\"", escape_dot(&node.id));
+        write_dot_attributes(&mut out, &node.data);
+    }
+    let operator = if network.directed { "->" } else { "--" };
+    for edge in edges {
+        let _ = write!(out, "  \"{}\" {} \"{}\"", escape_dot(&edge.source), operator, escape_dot(&edge.target));
+        write_dot_attributes(&mut out, &edge.data);
+    }
+
+    if last {
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// Ends the statement of a node or edge, with its data as attributes
+fn write_dot_attributes(out: &mut String, data: &[(&'static str, String)]) {
+    if !data.is_empty() {
+        let attributes: Vec<_> = data.iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_dot(value)))
+            .collect();
+        let _ = write!(out, " [{}]", attributes.join(", "));
+    }
+    out.push_str(";\n");
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_dot(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes() -> Vec<NetworkNode> {
+        vec![
+            NetworkNode {
+                id: "2025-01-0001".to_string(),
+                data: vec![("label", "On \"Typst\" & <Layouts>".to_string()), ("status", "published".to_string())],
+            },
+            NetworkNode { id: "2025-01-0002".to_string(), data: Vec::new() },
+        ]
+    }
+
+    fn edges() -> Vec<NetworkEdge> {
+        vec![NetworkEdge { source: "2025-01-0002".to_string(), target: "2025-01-0001".to_string(), data: Vec::new() }]
+    }
+
+    #[test]
+    fn test_export_graphml() {
+        let graphml = GraphFormat::GraphML.export_network(&Network::CITATIONS, &nodes(), &edges(), true, true);
+
+        assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n"));
+        assert!(graphml.contains("  <key id=\"category\" for=\"node\" attr.name=\"category\" attr.type=\"string\"/>\n"));
+        assert!(graphml.contains("  <graph id=\"citations\" edgedefault=\"directed\">\n"));
+        assert!(graphml.contains("    <node id=\"2025-01-0001\">\n      <data key=\"label\">On &quot;Typst&quot; &amp; &lt;Layouts&gt;</data>\n"));
+        assert!(graphml.contains("    <node id=\"2025-01-0002\"/>\n"));
+        assert!(graphml.contains("    <edge source=\"2025-01-0002\" target=\"2025-01-0001\"/>\n"));
+        assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+    }
+
+    #[test]
+    fn test_export_dot() {
+        let edges = vec![NetworkEdge {
+            source: "alice".to_string(),
+            target: "bob".to_string(),
+            data: vec![("paper", "2025-01-0001".to_string())],
+        }];
+        let dot = GraphFormat::Dot.export_network(&Network::CO_AUTHORSHIP, &[], &edges, true, true);
+        assert_eq!(dot, "graph \"co_authorship\" {\n  \"alice\" -- \"bob\" [paper=\"2025-01-0001\"];\n}\n");
+
+        let dot = GraphFormat::Dot.export_network(&Network::CITATIONS, &nodes(), &[], true, true);
+        assert!(dot.contains("  \"2025-01-0001\" [label=\"On \\\"Typst\\\" & <Layouts>\", status=\"published\"];\n"));
+    }
+
+    #[test]
+    fn test_export_pages() {
+        let (nodes, edges) = (nodes(), edges());
+        for format in [GraphFormat::GraphML, GraphFormat::Dot] {
+            let whole = format.export_network(&Network::CITATIONS, &nodes, &edges, true, true);
+            let pages = format.export_network(&Network::CITATIONS, &nodes[..1], &[], true, false)
+                + &format.export_network(&Network::CITATIONS, &nodes[1..], &edges, false, true);
+            assert_eq!(whole, pages);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::paper::{entity::model::Paper, repository::PaperRepository, Citation, PaperId, Reference};
+
+/// Finds and repairs references to papers of this platform that no longer exist.
+///
+/// Deleting a paper does not touch the papers referencing it, since those belong to
+/// other authors. Their references are left dangling until an author repairs them.
+pub struct ReferenceIntegrityService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> ReferenceIntegrityService<R>
+where
+    R: PaperRepository,
+{
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns the position and target of every reference of the paper to a paper that does not exist
+    pub fn dangling_references(&self, paper: &Paper<R::UserPrimaryKey>) -> Vec<(usize, PaperId)> {
+        paper.references.iter()
+            .enumerate()
+            .filter_map(|(index, reference)| match reference.citation {
+                Citation::Paper(id) if !self.repository.contains(&id) => Some((index, id)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the references of the paper with the dangling ones repaired, or `None` if none dangle.
+    ///
+    /// A reference to a deleted revision moves to the latest remaining revision of the
+    /// same paper; references to papers with no revision left are dropped. Clients learn
+    /// which ones beforehand from the reason of each broken reference.
+    pub fn repaired_references(&self, paper: &Paper<R::UserPrimaryKey>) -> Option<Vec<Reference>> {
+        if self.dangling_references(paper).is_empty() {
+            return None;
+        }
+
+        let mut repaired: Vec<Reference> = Vec::with_capacity(paper.references.len());
+        for reference in &paper.references {
+            match reference.citation {
+                Citation::Paper(id) if !self.repository.contains(&id) => {
+                    let Some(latest) = self.repository.latest_version(&id) else {
+                        continue;
+                    };
+                    // The paper may already cite the latest revision the same way
+                    let moved = Reference { citation: Citation::Paper(latest), ..reference.clone() };
+                    if !paper.references.contains(&moved) && !repaired.contains(&moved) {
+                        repaired.push(moved);
+                    }
+                }
+                _ => repaired.push(reference.clone()),
+            }
+        }
+        Some(repaired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        paper::{CitationContext, CitationIntent, PaperStatus},
+        testing::{id, paper, MemoryPaperRepository},
+    };
+
+    fn revision(number: u32, version: u16) -> PaperId {
+        PaperId::new(0, number, version).unwrap()
+    }
+
+    fn citing(references: Vec<Reference>) -> Paper<u8> {
+        let mut citing = paper(id(1), 1, PaperStatus::Published);
+        citing.references = references;
+        citing
+    }
+
+    #[test]
+    fn test_repaired_references() {
+        // Paper 2 exists, paper 3 lost its first revision but kept its second, and paper 4 is gone
+        let repository = MemoryPaperRepository::with([
+            paper(id(2), 2, PaperStatus::Published),
+            paper(revision(3, 2), 3, PaperStatus::Published),
+        ]);
+        let service = ReferenceIntegrityService::new(repository);
+        let disputes = CitationContext { intent: Some(CitationIntent::Disputes), ..Default::default() };
+        let paper = citing(vec![
+            Reference::new(Citation::Paper(id(2))),
+            Reference { citation: Citation::Paper(id(3)), context: disputes.clone() },
+            Reference::new(Citation::Paper(id(4))),
+            Reference::new(Citation::Url("https://typst.app".to_string())),
+        ]);
+
+        assert_eq!(service.dangling_references(&paper), vec![(1, id(3)), (2, id(4))]);
+        assert_eq!(service.repaired_references(&paper), Some(vec![
+            Reference::new(Citation::Paper(id(2))),
+            Reference { citation: Citation::Paper(revision(3, 2)), context: disputes },
+            Reference::new(Citation::Url("https://typst.app".to_string())),
+        ]));
+    }
+
+    #[test]
+    fn test_repaired_references_unchanged() {
+        let repository = MemoryPaperRepository::with([paper(id(2), 2, PaperStatus::Published)]);
+        let service = ReferenceIntegrityService::new(repository);
+        let paper = citing(vec![
+            Reference::new(Citation::Paper(id(2))),
+            Reference::new(Citation::Other("Knuth, 1968".to_string())),
+        ]);
+
+        assert!(service.dangling_references(&paper).is_empty());
+        assert_eq!(service.repaired_references(&paper), None);
+    }
+
+    #[test]
+    fn test_repaired_references_already_retargeted() {
+        // The paper cites both the deleted revision and its successor the same way
+        let repository = MemoryPaperRepository::with([paper(revision(3, 2), 3, PaperStatus::Published)]);
+        let service = ReferenceIntegrityService::new(repository);
+        let paper = citing(vec![
+            Reference::new(Citation::Paper(id(3))),
+            Reference::new(Citation::Paper(revision(3, 2))),
+        ]);
+
+        assert_eq!(service.repaired_references(&paper), Some(vec![Reference::new(Citation::Paper(revision(3, 2)))]));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    paper::{PaperId, PaperSummary, PaperTitle, entity::model::Paper},
+    paper::{AuthorCitationMetrics, BlobHash, ListingCursor, PaperCategory, PaperId, PaperSort, PaperStatus, PaperSummary, PaperTitle, entity::model::{Paper, PaperMetadata}, upload::UploadSession},
     user,
 };
+use std::ops::RangeInclusive;
 
 /// Repository trait for paper operations.
 /// Provides methods to retrieve papers and check for existence.
@@ -20,6 +21,44 @@ pub trait PaperRepository {
     /// * `None` - If the paper doesn't exist
     fn get(&self, paper_id: &PaperId) -> Option<Paper<Self::UserPrimaryKey>>;
 
+    /// Retrieves a paper without its contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The unique identifier of the paper to retrieve
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PaperMetadata)` - The metadata of the requested paper if found
+    /// * `None` - If the paper doesn't exist
+    fn get_metadata(&self, paper_id: &PaperId) -> Option<PaperMetadata<Self::UserPrimaryKey>>;
+
+    /// Retrieves the text content of a paper, without its PDF.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The unique identifier of the paper
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` - The text of the paper
+    /// * `None` - If the paper doesn't exist or has no text
+    fn get_text(&self, paper_id: &PaperId) -> Option<String>;
+
+    /// Reads a range of the content of a file in the blob store.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The digest of the stored file
+    /// * `offset` - The position of the first byte to read
+    /// * `len` - The number of bytes to read
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<u8>)` - The bytes read, fewer than `len` past the end of the file
+    /// * `None` - If the file is not stored
+    fn read_blob(&self, hash: &BlobHash, offset: u64, len: u64) -> Option<Vec<u8>>;
+
     /// Retrieves a summary of an paper by its ID.
     ///
     /// # Arguments
@@ -56,6 +95,245 @@ pub trait PaperRepository {
     /// * `false` - If the paper doesn't exist
     fn contains(&self, paper_id: &PaperId) -> bool;
 
+    /// Retrieves the identifier of the most recent revision of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of any revision of the paper
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PaperId)` - The identifier carrying the highest stored version
+    /// * `None` - If no revision of the paper exists
+    fn latest_version(&self, paper_id: &PaperId) -> Option<PaperId>;
+
+    /// Iterates over the identifiers of every stored revision of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of any revision of the paper
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the revision identifiers, oldest first.
+    fn iter_versions(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over paper revisions in identifier order, a batch at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The revision to start after, or `None` to start from the first revision
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the next revisions, of every status.
+    fn iter_ids(&self, after: Option<&PaperId>, limit: usize) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the papers a user has been invited to co-author.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The primary key of the invited user
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of papers with a pending invitation for the user.
+    fn iter_invitations(&self, user: &Self::UserPrimaryKey) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the authors of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the lead author followed by the co-authors, empty if the
+    /// paper doesn't exist.
+    fn iter_authors(&self, paper_id: &PaperId) -> impl Iterator<Item = Self::UserPrimaryKey>;
+
+    /// Iterates over the papers a paper references through `Citation::Paper`.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of the citing paper revision
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the referenced papers, whatever the
+    /// status of the citing paper.
+    fn iter_referenced(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the papers listed in the citations of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of the cited paper revision
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the citing paper revisions.
+    fn iter_citing(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId>;
+
+    /// Retrieves the citation metrics of a user, kept up to date as papers change.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The primary key of the user
+    ///
+    /// # Returns
+    ///
+    /// The metrics over every paper the user is the lead author or a co-author of a
+    /// revision of, counted as `CitationMetricsService::citation_count` does.
+    fn get_author_metrics(&self, user: &Self::UserPrimaryKey) -> AuthorCitationMetrics;
+
+    /// Iterates over the papers a word of the full-text index occurs in.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The word, as produced by `search::tokenize`
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the paper revisions containing the word,
+    /// with its weighted number of occurrences in each.
+    fn iter_postings(&self, term: &str, limit: usize) -> impl Iterator<Item = (PaperId, u32)>;
+
+    /// Retrieves the weighted number of words indexed for a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u32)` - The length of the paper in the full-text index
+    /// * `None` - If the paper doesn't exist
+    fn get_indexed_length(&self, paper_id: &PaperId) -> Option<u32>;
+
+    /// Retrieves statistics over the whole full-text index.
+    ///
+    /// # Returns
+    ///
+    /// The number of indexed paper revisions and the sum of their lengths.
+    fn index_statistics(&self) -> (u64, u64);
+
+    /// Iterates over the latest revision of each paper with a status, in listing order.
+    ///
+    /// # Arguments
+    ///
+    /// * `sort` - The order to list revisions in
+    /// * `status` - The status of the revisions
+    /// * `after` - The position to start after, or `None` to start from the first revision
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the positions of the next revisions.
+    fn iter_sorted(&self, sort: PaperSort, status: PaperStatus, after: Option<&ListingCursor>, limit: usize) -> impl Iterator<Item = ListingCursor>;
+
+    /// Retrieves the position of a paper revision in listings.
+    ///
+    /// # Arguments
+    ///
+    /// * `sort` - The order of the listing
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ListingCursor)` - The position of the revision
+    /// * `None` - If the paper doesn't exist
+    fn get_listing_cursor(&self, sort: PaperSort, paper_id: &PaperId) -> Option<ListingCursor>;
+
+    /// Iterates over the paper revisions a user is the lead author or a co-author of.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The primary key of the author
+    /// * `months` - The months since epoch the papers were created in
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the revisions in order, of every status.
+    fn iter_by_author(&self, user: &Self::UserPrimaryKey, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the paper revisions in a category.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category of the revisions
+    /// * `months` - The months since epoch the papers were created in
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the revisions in order, of every status.
+    fn iter_by_category(&self, category: &PaperCategory, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the paper revisions with a tag, ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of the revisions
+    /// * `months` - The months since epoch the papers were created in
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the revisions in order, of every status.
+    fn iter_by_tag(&self, tag: &str, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId>;
+
+    /// Iterates over the paper revisions created in a range of months.
+    ///
+    /// # Arguments
+    ///
+    /// * `months` - The months since epoch the papers were created in
+    /// * `limit` - The maximum number of revisions to return
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the revisions in order, of every status.
+    fn iter_by_month(&self, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId>;
+
+    /// Checks whether a user is the lead author or a co-author of a paper revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The primary key of the author
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// `true` if the revision is listed under the author, `false` otherwise.
+    fn is_by_author(&self, user: &Self::UserPrimaryKey, paper_id: &PaperId) -> bool;
+
+    /// Checks whether a paper revision is in a category.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// `true` if the revision is listed under the category, `false` otherwise.
+    fn has_category(&self, category: &PaperCategory, paper_id: &PaperId) -> bool;
+
+    /// Checks whether a paper revision has a tag, ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag
+    /// * `paper_id` - The identifier of the paper revision
+    ///
+    /// # Returns
+    ///
+    /// `true` if the revision is listed under the tag, `false` otherwise.
+    fn has_tag(&self, tag: &str, paper_id: &PaperId) -> bool;
+
     /// Iterates over all paper summaries in the repository.
     ///
     /// # Returns
@@ -72,9 +350,9 @@ pub trait PaperRepository {
     ///
     /// # Returns
     ///
-    /// * `Some(Paper)` - The previous paper with this ID, if it existed
+    /// * `Some(PaperMetadata)` - The previous paper with this ID without its contents, if it existed
     /// * `None` - If there was no previous paper with this ID
-    fn insert(&mut self, paper_id: PaperId, paper: Paper<Self::UserPrimaryKey>) -> Option<Paper<Self::UserPrimaryKey>>;
+    fn insert(&mut self, paper_id: PaperId, paper: Paper<Self::UserPrimaryKey>) -> Option<PaperMetadata<Self::UserPrimaryKey>>;
 
     /// Removes an paper from the repository.
     ///
@@ -84,9 +362,9 @@ pub trait PaperRepository {
     ///
     /// # Returns
     ///
-    /// * `Some(Paper)` - The removed paper if found
+    /// * `Some(PaperMetadata)` - The removed paper without its contents if found
     /// * `None` - If the paper doesn't exist
-    fn remove(&mut self, paper_id: &PaperId) -> Option<Paper<Self::UserPrimaryKey>>;
+    fn remove(&mut self, paper_id: &PaperId) -> Option<PaperMetadata<Self::UserPrimaryKey>>;
 
     /// Generates a new paper ID following the format `YYYY-MM-number`.
     ///
@@ -100,3 +378,77 @@ pub trait PaperRepository {
     ///   where nnnn is the next sequential number for the current month
     fn generate_id(&mut self) -> PaperId;
 }
+
+/// Repository trait for files being uploaded in chunks.
+pub trait UploadRepository {
+    type UserPrimaryKey: user::UserPrimaryKey;
+
+    /// Retrieves an upload by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_id` - The unique identifier of the upload
+    ///
+    /// # Returns
+    ///
+    /// * `Some(UploadSession)` - The requested upload if found, even if it has expired
+    /// * `None` - If the upload doesn't exist
+    fn get(&self, upload_id: u64) -> Option<UploadSession<Self::UserPrimaryKey>>;
+
+    /// Iterates over all uploads in the repository.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the uploads with the uploads themselves.
+    fn iter(&self) -> impl Iterator<Item = (u64, UploadSession<Self::UserPrimaryKey>)>;
+
+    /// Retrieves the total size of all uploads, as declared when they began.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the sizes of the uploads in the repository, expired or not.
+    fn open_bytes(&self) -> u64;
+
+    /// Inserts or updates an upload in the repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_id` - The unique identifier of the upload
+    /// * `session` - The upload to store
+    fn insert(&mut self, upload_id: u64, session: UploadSession<Self::UserPrimaryKey>);
+
+    /// Stores a chunk of an upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_id` - The unique identifier of the upload
+    /// * `offset` - The offset of the first byte of the chunk in the file
+    /// * `bytes` - The content of the chunk
+    fn insert_chunk(&mut self, upload_id: u64, offset: u64, bytes: Vec<u8>);
+
+    /// Iterates over the chunks of an upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_id` - The unique identifier of the upload
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the content of the chunks, by increasing offset.
+    fn iter_chunks(&self, upload_id: u64) -> impl Iterator<Item = Vec<u8>>;
+
+    /// Removes an upload and its chunks from the repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_id` - The unique identifier of the upload to remove
+    ///
+    /// # Returns
+    ///
+    /// * `Some(UploadSession)` - The removed upload if found
+    /// * `None` - If the upload doesn't exist
+    fn remove(&mut self, upload_id: u64) -> Option<UploadSession<Self::UserPrimaryKey>>;
+
+    /// Generates a new upload ID, never handed out before.
+    fn generate_id(&mut self) -> u64;
+}
@@ -0,0 +1,466 @@
+use crate::paper::PaperId;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+use unicode_normalization::{
+    char::{canonical_combining_class, is_combining_mark},
+    UnicodeNormalization,
+};
+
+/// Maximum length in characters of an indexed word; longer words are not indexed
+pub const MAX_TERM_LENGTH: usize = 32;
+
+/// Number of words a snippet spans, counting each Chinese or Japanese character as a word
+pub const SNIPPET_WORDS: usize = 24;
+
+/// Version of [`tokenize`], bumped whenever the terms it produces change so that stored
+/// indexes get rebuilt
+pub const TOKENIZER_VERSION: u32 = 1;
+
+/// A term of a text, as indexed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    /// The term, normalized
+    pub term: String,
+    /// Byte offset of the term in the text
+    pub start: usize,
+    /// Byte offset just past the term in the text
+    pub end: usize,
+}
+
+/// A word of a text, or a single character of a Chinese or Japanese run
+#[derive(Clone, Debug, PartialEq)]
+struct Word {
+    text: String,
+    start: usize,
+    end: usize,
+    /// Whether the word is a Chinese or Japanese character following another one
+    joined: bool,
+}
+
+/// Splits a text into the terms the full-text index is made of.
+///
+/// Text is normalized to NFKC, which folds full-width and half-width forms, and
+/// compared in lowercase. Words are runs of letters and digits; words longer than
+/// [`MAX_TERM_LENGTH`] characters are skipped.
+///
+/// Chinese and Japanese are not written with spaces, so their runs are indexed as every
+/// character and every pair of adjacent characters. Any part of such a run is then
+/// found by the pairs it is made of, or by its character if it has only one.
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let words = words(text);
+    let mut tokens = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        tokens.push(Token { term: word.text.clone(), start: word.start, end: word.end });
+        if let Some(next) = words.get(index + 1).filter(|next| next.joined) {
+            tokens.push(Token { term: format!("{}{}", word.text, next.text), start: word.start, end: next.end });
+        }
+    }
+    tokens
+}
+
+/// Returns the distinct terms of a query, in the order they first appear.
+///
+/// Chinese and Japanese runs are looked up by their pairs of adjacent characters, so
+/// text containing only part of a run still matches, ranked below text containing more
+/// of its pairs.
+pub fn query_terms(query: &str) -> Vec<String> {
+    let words = words(query);
+    let mut terms: Vec<String> = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        let next = words.get(index + 1).filter(|next| next.joined);
+        let term = match next {
+            Some(next) => format!("{}{}", word.text, next.text),
+            // A character ending a run is already part of the pair before it
+            None if word.joined => continue,
+            None => word.text.clone(),
+        };
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Splits a text into normalized words, keeping the offsets of the original text
+fn words(text: &str) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut word: Option<Word> = None;
+    let mut previous_cjk = false;
+    // A trailing separator closes the last word
+    for (c, start, end) in normalize(text).into_iter().chain([(' ', text.len(), text.len())]) {
+        let is_cjk = is_cjk(c);
+        let is_word = !is_cjk && (c.is_alphanumeric() || is_combining_mark(c));
+        if !is_word {
+            if let Some(word) = word.take().filter(|word| word.text.chars().count() <= MAX_TERM_LENGTH) {
+                words.push(word);
+            }
+        }
+
+        if is_cjk {
+            words.push(Word { text: c.to_string(), start, end, joined: previous_cjk });
+        } else if is_word {
+            let word = word.get_or_insert_with(|| Word { text: String::new(), start, end, joined: false });
+            word.text.push(c);
+            word.end = end;
+        }
+        previous_cjk = is_cjk;
+    }
+    words
+}
+
+/// Normalizes a text to lowercase NFKC, returning each character with the byte range
+/// of the original text it comes from.
+///
+/// Characters are normalized together with the combining marks following them, such
+/// as the half-width voiced sound mark, so that they compose.
+fn normalize(text: &str) -> Vec<(char, usize, usize)> {
+    let mut out = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        while let Some((index, mark)) = chars.next_if(|(_, next)| !is_starter(*next)) {
+            end = index + mark.len_utf8();
+        }
+        for normalized in text[start..end].nfkc().flat_map(char::to_lowercase) {
+            out.push((normalized, start, end));
+        }
+    }
+    out
+}
+
+/// Whether a character decomposes to a character that does not combine with the one before it
+fn is_starter(c: char) -> bool {
+    std::iter::once(c).nfkd().next().is_none_or(|first| canonical_combining_class(first) == 0)
+}
+
+/// Whether a character belongs to a script written without spaces between words:
+/// Chinese characters, hiragana and katakana
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3005}'..='\u{3007}' // Iteration mark, closing mark and ideographic zero
+        | '\u{3041}'..='\u{309F}' // Hiragana
+        | '\u{30A1}'..='\u{30FA}' | '\u{30FC}'..='\u{30FF}' // Katakana, without the middle dot
+        | '\u{31F0}'..='\u{31FF}' // Katakana phonetic extensions
+        | '\u{3400}'..='\u{4DBF}' // CJK unified ideographs extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+        | '\u{20000}'..='\u{3134F}' // CJK unified ideographs extensions B and later
+    )
+}
+
+/// How a paper is recorded in the full-text index.
+///
+/// Words are counted once per occurrence, weighted by the field they occur in, so
+/// that a word of the title counts as much as several words of the content.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SearchDocument {
+    /// Weighted number of words in the paper
+    pub length: u32,
+    /// Weighted number of occurrences of each distinct word
+    pub terms: BTreeMap<String, u32>,
+}
+
+impl SearchDocument {
+    pub const TITLE_WEIGHT: u32 = 3;
+    pub const TAG_WEIGHT: u32 = 2;
+    pub const ABSTRACT_WEIGHT: u32 = 2;
+    pub const CONTENT_WEIGHT: u32 = 1;
+
+    pub fn new(title: &str, ab: &str, text: &str, tags: &[String]) -> Self {
+        let mut document = Self::default();
+        document.add(title, Self::TITLE_WEIGHT);
+        document.add(ab, Self::ABSTRACT_WEIGHT);
+        document.add(text, Self::CONTENT_WEIGHT);
+        for tag in tags {
+            document.add(tag, Self::TAG_WEIGHT);
+        }
+        document
+    }
+
+    fn add(&mut self, text: &str, weight: u32) {
+        for token in tokenize(text) {
+            *self.terms.entry(token.term).or_default() += weight;
+            self.length += weight;
+        }
+    }
+}
+
+/// Key of a posting of the full-text index: a word and a paper it occurs in
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PostingKey {
+    pub term: String,
+    pub paper_id: PaperId,
+}
+
+impl PostingKey {
+    /// Returns the range of the postings of every paper the word occurs in
+    pub fn range(term: &str) -> RangeInclusive<PostingKey> {
+        PostingKey { term: term.to_string(), paper_id: PaperId::MIN }..=PostingKey { term: term.to_string(), paper_id: PaperId::MAX }
+    }
+}
+
+/// Okapi BM25 ranking over the papers of the full-text index
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bm25 {
+    documents: u64,
+    average_length: f64,
+}
+
+impl Bm25 {
+    /// Saturation of repeated occurrences of a word
+    const K1: f64 = 1.2;
+    /// Normalization by the length of a paper
+    const B: f64 = 0.75;
+
+    /// Creates the ranking for an index of `documents` papers with `total_length` weighted words
+    pub fn new(documents: u64, total_length: u64) -> Self {
+        let average_length = if documents == 0 { 0.0 } else { total_length as f64 / documents as f64 };
+        Self { documents, average_length }
+    }
+
+    /// Returns the weight of a word occurring in `document_frequency` papers; rarer words weigh more
+    pub fn idf(&self, document_frequency: u64) -> f64 {
+        let documents = self.documents.max(document_frequency) as f64;
+        let frequency = document_frequency as f64;
+        ((documents - frequency + 0.5) / (frequency + 0.5) + 1.0).ln()
+    }
+
+    /// Returns what a word weighing `idf` adds to the score of a paper of `length` weighted
+    /// words, in which it occurs `term_frequency` times
+    pub fn score(&self, idf: f64, term_frequency: u32, length: u32) -> f64 {
+        let frequency = term_frequency as f64;
+        let relative_length = if self.average_length > 0.0 { length as f64 / self.average_length } else { 1.0 };
+        idf * frequency * (Self::K1 + 1.0) / (frequency + Self::K1 * (1.0 - Self::B + Self::B * relative_length))
+    }
+}
+
+/// A piece of a snippet, highlighted if it is a word of the query
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// Returns the number of occurrences of the words in the text
+pub fn count_matches(text: &str, terms: &[String]) -> usize {
+    tokenize(text).iter().filter(|token| terms.contains(&token.term)).count()
+}
+
+/// Extracts the passage of a text with the most occurrences of the words, split into
+/// segments so that the occurrences can be highlighted.
+///
+/// The passage spans [`SNIPPET_WORDS`] words, starting at the beginning of the text if
+/// no word occurs. Whitespace is collapsed, and an ellipsis marks cut-off text.
+pub fn snippet(text: &str, terms: &[String]) -> Vec<SnippetSegment> {
+    let words = words(text);
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    // A Chinese or Japanese character matches if the query has it, or a pair it is part of
+    let pair = |first: &Word, second: &Word| second.joined && terms.contains(&format!("{}{}", first.text, second.text));
+    let matched: Vec<bool> = words.iter().enumerate().map(|(index, word)| {
+        terms.contains(&word.text)
+            || index > 0 && pair(&words[index - 1], word)
+            || words.get(index + 1).is_some_and(|next| pair(word, next))
+    }).collect();
+
+    // Slide a window over the words, keeping the first one with the most matches
+    let width = SNIPPET_WORDS.min(words.len());
+    let mut count = matched[..width].iter().filter(|m| **m).count();
+    let (mut best, mut best_count) = (0, count);
+    for first in 1..=words.len() - width {
+        count = count + matched[first + width - 1] as usize - matched[first - 1] as usize;
+        if count > best_count {
+            (best, best_count) = (first, count);
+        }
+    }
+
+    // Leave some words before the first occurrence, so it reads in context
+    if let Some(first_match) = (best..best + width).find(|index| matched[*index]) {
+        best = best.max(first_match.saturating_sub(width / 3)).min(words.len() - width);
+    }
+
+    let last = best + width - 1;
+    let start = if best == 0 { 0 } else { words[best].start };
+    let end = if last == words.len() - 1 { text.len() } else { words[last].end };
+
+    let mut segments = Vec::new();
+    let mut push = |text: &str, highlighted: bool| {
+        let text = collapse_whitespace(text);
+        if !text.is_empty() {
+            segments.push(SnippetSegment { text, highlighted });
+        }
+    };
+    if start > 0 {
+        push("…", false);
+    }
+    let mut offset = start;
+    for (word, matched) in words[best..=last].iter().zip(&matched[best..=last]) {
+        // A character normalized to several, such as a ligature, is highlighted once
+        if *matched && word.start >= offset {
+            push(&text[offset..word.start], false);
+            push(&text[word.start..word.end], true);
+            offset = word.end;
+        }
+    }
+    push(&text[offset..end], false);
+    if end < text.len() {
+        push("…", false);
+    }
+
+    // Joining the ellipses to their neighbours keeps plain segments together, as joining
+    // adjacent characters does for highlighted ones
+    segments.dedup_by(|next, previous| {
+        if next.highlighted != previous.highlighted {
+            return false;
+        }
+        previous.text.push_str(&next.text);
+        true
+    });
+    segments
+}
+
+/// Replaces every run of whitespace with a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                out.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            out.push(c);
+            in_whitespace = false;
+        }
+    }
+    out
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for PostingKey {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned([self.term.as_bytes(), &self.paper_id.to_bytes()].concat())
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            // The identifier has a fixed size, so the word is everything before it
+            let split = bytes.len() - PaperId::BOUND.max_size() as usize;
+            PostingKey {
+                term: String::from_utf8_lossy(&bytes[..split]).into_owned(),
+                paper_id: PaperId::from_bytes(Cow::Borrowed(&bytes[split..])),
+            }
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            // A character takes at most 4 bytes in UTF-8
+            max_size: MAX_TERM_LENGTH as u32 * 4 + PaperId::BOUND.max_size(),
+            is_fixed_size: false,
+        };
+    }
+
+    impl Storable for SearchDocument {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned(candid::encode_one(self).expect("Failed to encode SearchDocument"))
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            candid::decode_one(&bytes).expect("Failed to decode SearchDocument")
+        }
+
+        const BOUND: Bound = Bound::Unbounded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("Typst's layout-engine, v2!");
+        let terms: Vec<_> = tokens.iter().map(|token| token.term.as_str()).collect();
+        assert_eq!(terms, ["typst", "s", "layout", "engine", "v2"]);
+        assert_eq!((tokens[2].start, tokens[2].end), (8, 14));
+
+        assert!(tokenize(&"a".repeat(MAX_TERM_LENGTH + 1)).is_empty());
+        assert_eq!(query_terms("Rust rust RUST borrow"), ["rust", "borrow"]);
+    }
+
+    #[test]
+    fn test_tokenize_cjk() {
+        // Full-width letters and half-width katakana fold to their usual forms
+        let tokens = tokenize("Ｒｕｓｔ入門ｶﾞｲﾄﾞ");
+        let terms: Vec<_> = tokens.iter().map(|token| token.term.as_str()).collect();
+        assert_eq!(terms, ["rust", "入", "入門", "門", "門ガ", "ガ", "ガイ", "イ", "イド", "ド"]);
+        assert_eq!((tokens[5].start, tokens[5].end), (18, 24));
+
+        assert_eq!(query_terms("入門 ガイド"), ["入門", "ガイ", "イド"]);
+        assert_eq!(query_terms("Rust入門"), ["rust", "入門"]);
+        assert_eq!(query_terms("門"), ["門"]);
+
+        // Any part of a mixed title is found
+        let document = SearchDocument::new("Rust入門ガイド", "", "", &[]);
+        for query in ["入門", "門", "ガイド", "門ガ", "ｒｕｓｔ", "ｲﾄﾞ"] {
+            assert!(query_terms(query).iter().all(|term| document.terms.contains_key(term)), "{}", query);
+        }
+    }
+
+    #[test]
+    fn test_search_document() {
+        let document = SearchDocument::new("Rust Borrowing", "About rust", "rust", &["rust".to_string()]);
+
+        assert_eq!(document.terms["rust"], 3 + 2 + 1 + 2);
+        assert_eq!(document.terms["borrowing"], 3);
+        assert_eq!(document.length, 3 * 2 + 2 * 2 + 1 + 2);
+    }
+
+    #[test]
+    fn test_bm25() {
+        let bm25 = Bm25::new(10, 1000);
+
+        // Rare words weigh more than common ones
+        assert!(bm25.idf(1) > bm25.idf(5));
+        assert!(bm25.idf(10) > 0.0);
+        // More occurrences score higher, with diminishing returns
+        let (one, two, three) = (bm25.score(1.0, 1, 100), bm25.score(1.0, 2, 100), bm25.score(1.0, 3, 100));
+        assert!(one < two && two < three && three - two < two - one);
+        // Shorter papers score higher for the same occurrences
+        assert!(bm25.score(1.0, 1, 50) > bm25.score(1.0, 1, 200));
+    }
+
+    #[test]
+    fn test_snippet() {
+        let terms = query_terms("borrow");
+        let segments = snippet("The  borrow checker\nchecks borrows.", &terms);
+        assert_eq!(segments, vec![
+            SnippetSegment { text: "The ".to_string(), highlighted: false },
+            SnippetSegment { text: "borrow".to_string(), highlighted: true },
+            SnippetSegment { text: " checker checks borrows.".to_string(), highlighted: false },
+        ]);
+
+        let text = format!("{} borrow end", "word ".repeat(SNIPPET_WORDS * 2));
+        let segments = snippet(&text, &terms);
+        assert!(segments[0].text.starts_with("…word word"));
+        assert_eq!(segments[1], SnippetSegment { text: "borrow".to_string(), highlighted: true });
+        assert_eq!(segments[2].text, " end");
+        assert_eq!(count_matches(&text, &terms), 1);
+
+        assert!(snippet("", &terms).is_empty());
+
+        let segments = snippet("これはＲｕｓｔ入門です。", &query_terms("入門"));
+        assert_eq!(segments, vec![
+            SnippetSegment { text: "これはＲｕｓｔ".to_string(), highlighted: false },
+            SnippetSegment { text: "入門".to_string(), highlighted: true },
+            SnippetSegment { text: "です。".to_string(), highlighted: false },
+        ]);
+    }
+}
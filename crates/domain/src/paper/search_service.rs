@@ -0,0 +1,179 @@
+use crate::{
+    paper::{
+        entity::model::PaperMetadata,
+        repository::PaperRepository,
+        search::{query_terms, Bm25},
+        PaperId,
+    },
+    user::UserPrimaryKey,
+};
+use std::collections::BTreeMap;
+
+/// A page of full-text search results
+pub struct SearchPage<K: UserPrimaryKey> {
+    /// Latest public revision of each matching paper with its score, best first
+    pub papers: Vec<(PaperMetadata<K>, f64)>,
+    /// Whether more results follow
+    pub has_more: bool,
+}
+
+/// Ranks papers against full-text queries.
+///
+/// The repository indexes every revision it stores, whatever its status, so results
+/// are narrowed down to the latest public revision of each paper.
+///
+/// Queries are bounded so that a single call stays within the instruction limit: only
+/// their first [`Self::MAX_QUERY_TERMS`] terms are looked up, and terms found in more
+/// than [`Self::MAX_POSTINGS`] revisions are skipped as too common to rank by.
+pub struct SearchService<R>
+where
+    R: PaperRepository,
+{
+    repository: R,
+}
+
+impl<R> SearchService<R>
+where
+    R: PaperRepository,
+{
+    /// Number of results on a page
+    pub const PAGE_SIZE: usize = 20;
+    /// Maximum length of a query in characters
+    pub const MAX_QUERY_LENGTH: usize = 200;
+    /// Maximum number of terms of a query looked up
+    pub const MAX_QUERY_TERMS: usize = 8;
+    /// Maximum number of revisions a term is looked up in
+    pub const MAX_POSTINGS: usize = 1000;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Returns a page of the public papers matching the query and accepted by `filter`
+    pub fn search(&self, query: &str, filter: impl Fn(&PaperMetadata<R::UserPrimaryKey>) -> bool, page: u32) -> SearchPage<R::UserPrimaryKey> {
+        let mut papers: Vec<_> = self.rank(query).into_iter()
+            .filter_map(|(paper_id, score)| Some((self.repository.get_metadata(&paper_id)?, score)))
+            .filter(|(paper, _)| filter(paper))
+            .skip(page as usize * Self::PAGE_SIZE)
+            .take(Self::PAGE_SIZE + 1)
+            .collect();
+        let has_more = papers.len() > Self::PAGE_SIZE;
+        papers.truncate(Self::PAGE_SIZE);
+        SearchPage { papers, has_more }
+    }
+
+    /// Returns the terms of the query that are looked up
+    pub fn query_terms(query: &str) -> Vec<String> {
+        let mut terms = query_terms(query);
+        terms.truncate(Self::MAX_QUERY_TERMS);
+        terms
+    }
+
+    /// Returns the public papers containing any word of the query with their BM25 score,
+    /// best first.
+    pub fn rank(&self, query: &str) -> Vec<(PaperId, f64)> {
+        let (documents, total_length) = self.repository.index_statistics();
+        let bm25 = Bm25::new(documents, total_length);
+
+        let mut scores: BTreeMap<PaperId, f64> = BTreeMap::new();
+        for term in Self::query_terms(query) {
+            let postings: Vec<_> = self.repository.iter_postings(&term, Self::MAX_POSTINGS + 1).collect();
+            if postings.len() > Self::MAX_POSTINGS {
+                continue;
+            }
+            let idf = bm25.idf(postings.len() as u64);
+            for (paper_id, frequency) in postings {
+                let length = self.repository.get_indexed_length(&paper_id).unwrap_or_default();
+                *scores.entry(paper_id).or_default() += bm25.score(idf, frequency, length);
+            }
+        }
+
+        let mut latest_public: BTreeMap<PaperId, Option<PaperId>> = BTreeMap::new();
+        let mut ranked: Vec<_> = scores.into_iter()
+            .filter(|(paper_id, _)| {
+                let latest = latest_public.entry(paper_id.first_version()).or_insert_with(|| {
+                    self.repository.iter_versions(paper_id)
+                        .filter(|version| self.repository.get_summary(version).is_some_and(|summary| summary.status.is_public()))
+                        .last()
+                });
+                *latest == Some(*paper_id)
+            })
+            .collect();
+        ranked.sort_by(|(a_id, a_score), (b_id, b_score)| b_score.total_cmp(a_score).then(a_id.cmp(b_id)));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        paper::{entity::model::Paper, PaperStatus, PaperTitle},
+        testing::{id, paper, MemoryPaperRepository},
+    };
+
+    type Service = SearchService<MemoryPaperRepository>;
+
+    fn titled(number: u32, status: PaperStatus, title: &str) -> Paper<u8> {
+        let mut paper = paper(id(number), 1, status);
+        paper.title = PaperTitle::new(title).unwrap();
+        paper
+    }
+
+    fn ranked(service: &Service, query: &str) -> Vec<PaperId> {
+        service.rank(query).into_iter().map(|(paper_id, _)| paper_id).collect()
+    }
+
+    #[test]
+    fn test_rank() {
+        let service = Service::new(MemoryPaperRepository::with([
+            titled(1, PaperStatus::Published, "Ownership in Rust"),
+            titled(2, PaperStatus::Published, "Borrow checking in Rust"),
+            titled(3, PaperStatus::Published, "Garbage collection in Java"),
+        ]));
+
+        // Papers matching more of the query rank first
+        assert_eq!(ranked(&service, "rust borrow"), vec![id(2), id(1)]);
+        assert_eq!(ranked(&service, "Java"), vec![id(3)]);
+        assert!(ranked(&service, "haskell").is_empty());
+    }
+
+    #[test]
+    fn test_rank_visibility() {
+        // The second revision of 1 is a draft, so the first one is found
+        let mut draft = titled(1, PaperStatus::Draft, "Ownership in Rust");
+        draft.id = PaperId::new(0, 1, 2).unwrap();
+        let service = Service::new(MemoryPaperRepository::with([
+            titled(1, PaperStatus::Published, "Ownership in Rust"),
+            draft,
+            titled(2, PaperStatus::Draft, "Borrow checking in Rust"),
+            titled(3, PaperStatus::UnderReview, "Lifetimes in Rust"),
+        ]));
+
+        assert_eq!(ranked(&service, "rust"), vec![id(1)]);
+    }
+
+    #[test]
+    fn test_rank_empty_query() {
+        let service = Service::new(MemoryPaperRepository::with([titled(1, PaperStatus::Published, "Ownership in Rust")]));
+
+        assert!(ranked(&service, "").is_empty());
+        assert!(ranked(&service, " ,.! ").is_empty());
+        assert!(service.search("", |_| true, 0).papers.is_empty());
+    }
+
+    #[test]
+    fn test_rank_bounds() {
+        // Every paper mentions Rust, which is then too common to rank by
+        let papers = (1..=Service::MAX_POSTINGS as u32 + 1).map(|number| match number {
+            1 => titled(1, PaperStatus::Published, "Ownership in Rust"),
+            _ => titled(number, PaperStatus::Published, "Rust"),
+        });
+        let service = Service::new(MemoryPaperRepository::with(papers));
+
+        assert!(ranked(&service, "rust").is_empty());
+        assert_eq!(ranked(&service, "rust ownership"), vec![id(1)]);
+        let terms: String = (0..Service::MAX_QUERY_TERMS).map(|index| format!("word{} ", index)).collect();
+        assert_eq!(Service::query_terms(&(terms + "ownership")).len(), Service::MAX_QUERY_TERMS);
+    }
+}
@@ -1,11 +1,38 @@
 use crate::paper::{
-    entity::model::Paper,
+    citation_graph::CitationGraphService,
+    entity::model::{Paper, PaperChanges, PaperRevisionError},
     repository::PaperRepository,
-    PaperId,
+    upload::UploadError,
+    CoAuthorRole, PaperId, PaperPermissionError, PaperRole, PaperStatus, PaperStatusTransition,
 };
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
 
 use super::PaperSummary;
 
+/// Errors returned by paper operations that check the caller's permissions
+#[derive(Error, CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PaperServiceError {
+    #[error("Invalid paper ID: {0}")]
+    InvalidId(String),
+    #[error("Paper not found: {0}")]
+    NotFound(String),
+    #[error("User not found: {0}")]
+    UserNotFound(String),
+    #[error(transparent)]
+    Permission(#[from] PaperPermissionError),
+    #[error("Paper {paper_id} was modified at {updated_at} since it was last read")]
+    Conflict { paper_id: String, updated_at: u64 },
+    #[error(transparent)]
+    Upload(#[from] UploadError),
+    #[error("Cannot publish a revision: {0}")]
+    Revision(String),
+    #[error("{0}")]
+    Rejected(String),
+}
+
 pub struct PaperService<R>
 where
     R: PaperRepository,
@@ -15,7 +42,7 @@ where
 
 impl<R> PaperService<R>
 where
-    R: PaperRepository,
+    R: PaperRepository + Clone,
 {
     pub fn new(repository: R) -> Self {
         Self { repository }
@@ -34,21 +61,198 @@ where
     }
 
     pub fn check_is_author(&self, user: &R::UserPrimaryKey, paper_id: &PaperId) -> bool {
-        if let Some(summary) = self.repository.get_summary(paper_id) {
-            summary.lead_author == *user
-        } else {
-            false
-        }
+        self.repository.get(paper_id).is_some_and(|paper| paper.is_author(user))
+    }
+
+    /// Returns the role the user holds as an author of the paper, if any
+    pub fn author_role(&self, user: &R::UserPrimaryKey, paper_id: &PaperId) -> Option<PaperRole> {
+        self.repository.get(paper_id)?.author_role(user)
     }
 
-    pub fn update(&mut self, paper: Paper<R::UserPrimaryKey>) -> Result<(), String> {
+    /// Replaces the latest revision of a paper and returns its new `updated_at`.
+    ///
+    /// `paper.updated_at` must be the stamp the client last read. If the paper has
+    /// changed since, or a newer revision was published, the update is rejected
+    /// with a conflict carrying the current revision.
+    pub fn update(&mut self, mut paper: Paper<R::UserPrimaryKey>) -> Result<u64, PaperServiceError> {
         let id = paper.id;
-        if self.repository.contains(&id) {
-            self.repository.insert(id, paper);
-            Ok(())
+        let current = self.get_unchanged_since(&id, paper.updated_at)?;
+        paper.normalize_references().map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
+
+        // Authorship only changes through co-author invitations
+        paper.lead_author = current.lead_author;
+        paper.co_authors = current.co_authors;
+        paper.invited_co_authors = current.invited_co_authors;
+        paper.co_author_roles = current.co_author_roles;
+
+        // Creation date and received citations are owned by the server
+        paper.created_at = current.created_at;
+        paper.citations = current.citations;
+
+        // Status changes must follow the transition table
+        let target = paper.status;
+        paper.status = current.status;
+        let transition = current.status.transition_to(target)
+            .map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
+        if let Some(transition) = transition {
+            paper.transition(transition).map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
+        }
+
+        paper.touch();
+        let updated_at = paper.updated_at;
+        self.save(paper);
+        Ok(updated_at)
+    }
+
+    /// Applies field-level changes to the latest revision of a paper and returns its new `updated_at`.
+    ///
+    /// Like [`Self::update`], the paper must not have changed since `seen_updated_at`.
+    pub fn patch(&mut self, paper_id: &PaperId, changes: PaperChanges, seen_updated_at: u64) -> Result<u64, PaperServiceError> {
+        let mut paper = self.get_unchanged_since(paper_id, seen_updated_at)?;
+
+        paper.apply_changes(changes).map_err(|e| PaperServiceError::Rejected(e.to_string()))?;
+        let updated_at = paper.updated_at;
+        self.save(paper);
+        Ok(updated_at)
+    }
+
+    /// Returns the paper if it is the latest revision and still carries the `updated_at` the client read
+    fn get_unchanged_since(&self, paper_id: &PaperId, seen_updated_at: u64) -> Result<Paper<R::UserPrimaryKey>, PaperServiceError> {
+        let current = self.repository.get(paper_id)
+            .ok_or_else(|| PaperServiceError::NotFound(paper_id.to_string()))?;
+        if !self.is_latest_version(paper_id) {
+            let latest = self.repository.latest_version(paper_id)
+                .and_then(|latest_id| self.repository.get(&latest_id))
+                .unwrap_or(current);
+            return Err(PaperServiceError::Conflict { paper_id: latest.id.to_string(), updated_at: latest.updated_at });
+        }
+        if seen_updated_at != current.updated_at {
+            return Err(PaperServiceError::Conflict { paper_id: paper_id.to_string(), updated_at: current.updated_at });
+        }
+
+        Ok(current)
+    }
+
+    /// Applies a single status transition to the latest revision of a paper.
+    pub fn transition_status(&mut self, paper_id: &PaperId, transition: PaperStatusTransition) -> Result<PaperStatus, String> {
+        let mut paper = self.repository.get(paper_id)
+            .ok_or_else(|| "Paper not found".to_string())?;
+        if !self.is_latest_version(paper_id) {
+            return Err("Superseded revisions of a paper cannot be modified".to_string());
+        }
+
+        paper.transition(transition).map_err(|e| e.to_string())?;
+        let status = paper.status;
+        self.save(paper);
+        Ok(status)
+    }
+
+    pub fn is_latest_version(&self, paper_id: &PaperId) -> bool {
+        self.repository.latest_version(paper_id) == Some(*paper_id)
+    }
+
+    /// Publishes `paper` as the next revision of the paper it was edited from.
+    ///
+    /// The stored head is left untouched, so readers citing it keep getting the
+    /// exact text they cited. Returns the identifier of the new revision.
+    pub fn publish_revision(&mut self, paper: Paper<R::UserPrimaryKey>) -> Result<PaperId, PaperServiceError> {
+        let previous = self.repository.get(&paper.id)
+            .ok_or_else(|| PaperServiceError::NotFound(paper.id.to_string()))?;
+        let revision = if self.is_latest_version(&previous.id) {
+            paper.into_revision_of(&previous)
         } else {
-            Err("Paper not found".to_string())
+            Err(PaperRevisionError::NotLatest)
+        }.map_err(|e| PaperServiceError::Revision(e.to_string()))?;
+
+        let id = revision.id;
+        self.save(revision);
+        Ok(id)
+    }
+
+    /// Invites a user to co-author the latest revision of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The paper to invite the user to
+    /// * `invitee` - The user being invited
+    /// * `role` - The role the invitee will hold once they accept
+    pub fn invite_co_author(
+        &mut self,
+        paper_id: &PaperId,
+        invitee: R::UserPrimaryKey,
+        role: CoAuthorRole,
+    ) -> Result<(), String> {
+        let mut paper = self.get_latest_for_authorship(paper_id)?;
+
+        paper.invite_co_author(invitee, role).map_err(|e| e.to_string())?;
+        self.repository.insert(*paper_id, paper);
+        Ok(())
+    }
+
+    /// Accepts an invitation, making the invitee a co-author
+    pub fn accept_invitation(&mut self, paper_id: &PaperId, invitee: &R::UserPrimaryKey) -> Result<(), String> {
+        let mut paper = self.get_latest_for_authorship(paper_id)?;
+
+        paper.accept_invitation(invitee).map_err(|e| e.to_string())?;
+        self.repository.insert(*paper_id, paper);
+        Ok(())
+    }
+
+    /// Drops a pending invitation, whether declined or cancelled
+    pub fn withdraw_invitation(&mut self, paper_id: &PaperId, invitee: &R::UserPrimaryKey) -> Result<(), String> {
+        let mut paper = self.get_latest_for_authorship(paper_id)?;
+
+        paper.withdraw_invitation(invitee).map_err(|e| e.to_string())?;
+        self.repository.insert(*paper_id, paper);
+        Ok(())
+    }
+
+    /// Removes a co-author from a paper
+    pub fn remove_co_author(&mut self, paper_id: &PaperId, co_author: &R::UserPrimaryKey) -> Result<(), String> {
+        let mut paper = self.get_latest_for_authorship(paper_id)?;
+
+        paper.remove_co_author(co_author).map_err(|e| e.to_string())?;
+        self.repository.insert(*paper_id, paper);
+        Ok(())
+    }
+
+    /// Changes the role of a co-author or of a pending invitation
+    pub fn set_co_author_role(
+        &mut self,
+        paper_id: &PaperId,
+        co_author: &R::UserPrimaryKey,
+        role: CoAuthorRole,
+    ) -> Result<(), String> {
+        let mut paper = self.get_latest_for_authorship(paper_id)?;
+
+        paper.set_co_author_role(co_author, role).map_err(|e| e.to_string())?;
+        self.repository.insert(*paper_id, paper);
+        Ok(())
+    }
+
+    /// Returns the papers a user is invited to, by their latest revision; earlier revisions
+    /// keep the invitations they were published with
+    pub fn get_invitations(&self, user: &R::UserPrimaryKey) -> Vec<PaperId> {
+        self.repository.iter_invitations(user)
+            .filter(|paper_id| self.is_latest_version(paper_id))
+            .collect()
+    }
+
+    fn get_latest_for_authorship(&self, paper_id: &PaperId) -> Result<Paper<R::UserPrimaryKey>, String> {
+        let paper = self.repository.get(paper_id)
+            .ok_or_else(|| "Paper not found".to_string())?;
+        if !self.is_latest_version(paper_id) {
+            return Err("Authorship can only change on the latest revision of a paper".to_string());
         }
+
+        Ok(paper)
+    }
+
+    pub fn get_revisions(&self, paper_id: &PaperId) -> Vec<Paper<R::UserPrimaryKey>> {
+        self.repository
+            .iter_versions(paper_id)
+            .filter_map(|id| self.repository.get(&id))
+            .collect()
     }
 
     pub fn create_draft(
@@ -62,11 +266,23 @@ where
     }
 
     pub fn remove(&mut self, id: &PaperId) -> Result<(), String> {
-        if self.repository.contains(id) {
-            self.repository.remove(id);
-            Ok(())
-        } else {
-            Err("Paper not found".to_string())
-        }
+        let mut graph = CitationGraphService::new(self.repository.clone());
+        let before = graph.stored_cited_papers(id);
+        self.repository.remove(id)
+            .ok_or_else(|| "Paper not found".to_string())?;
+
+        graph.sync(id, &before, &BTreeSet::new());
+        Ok(())
+    }
+
+    /// Stores a paper and keeps the citations of the papers it references in step
+    fn save(&mut self, paper: Paper<R::UserPrimaryKey>) {
+        let id = paper.id;
+        let mut graph = CitationGraphService::new(self.repository.clone());
+        let after = CitationGraphService::<R>::cited_papers(&paper);
+        let before = graph.stored_cited_papers(&id);
+        self.repository.insert(id, paper);
+
+        graph.sync(&id, &before, &after);
     }
 }
@@ -0,0 +1,215 @@
+use crate::paper::BlobHash;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use thiserror::Error;
+
+/// Errors raised while uploading a file in chunks
+#[derive(Error, CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum UploadError {
+    #[error("Upload not found: {0}")]
+    NotFound(u64),
+    #[error("Upload {0} has expired")]
+    Expired(u64),
+    #[error("At most {max} uploads can be open at once")]
+    TooManyUploads { max: u32 },
+    #[error("Uploads are full, as at most {max} bytes can be uploading at once")]
+    UploadsFull { max: u64 },
+    #[error("File name cannot be blank")]
+    BlankName,
+    #[error("File size must be between 1 and {max} bytes")]
+    InvalidSize { max: u64 },
+    #[error("Invalid SHA-256 digest: '{0}'")]
+    InvalidDigest(String),
+    #[error("Chunks must hold between 1 and {max} bytes")]
+    InvalidChunkSize { max: u64 },
+    #[error("Next chunk must start at offset {expected}")]
+    UnexpectedOffset { expected: u64 },
+    #[error("Chunk runs past the declared size of {size} bytes")]
+    ChunkPastEnd { size: u64 },
+    #[error("Only {received} of {size} bytes have been uploaded")]
+    Incomplete { received: u64, size: u64 },
+    #[error("Uploaded file does not match its SHA-256 digest")]
+    DigestMismatch,
+}
+
+/// A file being uploaded in chunks, before it is attached to a paper.
+///
+/// Chunks are appended in order, each starting where the previous one ended, so a
+/// client retrying a chunk after a failed call is told where to resume from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UploadSession<K> {
+    /// The user who began the upload, the only one who may continue it
+    pub owner: K,
+    pub name: String,
+    /// Size of the whole file in bytes, as declared when the upload began
+    pub size: u64,
+    /// SHA-256 digest of the whole file, as declared when the upload began
+    pub sha256: BlobHash,
+    /// Number of bytes received so far
+    pub received: u64,
+    /// When the upload is abandoned, in nanoseconds since epoch
+    pub expires_at: u64,
+}
+
+impl<K: PartialEq> UploadSession<K> {
+    /// Largest file that can be uploaded
+    pub const MAX_SIZE: u64 = 32 * 1024 * 1024;
+    /// Largest chunk, well within the ingress message limit
+    pub const MAX_CHUNK_SIZE: u64 = 1024 * 1024;
+    /// Time an upload stays open, in nanoseconds
+    pub const LIFETIME: u64 = 60 * 60 * 1_000_000_000;
+    /// Number of uploads a user may have open at once
+    pub const MAX_OPEN: u32 = 4;
+    /// Total size of the uploads open at once, across every user
+    pub const MAX_OPEN_BYTES: u64 = 512 * 1024 * 1024;
+
+    /// Begins an upload of `size` bytes whose SHA-256 digest is `sha256` in hexadecimal
+    pub fn new(owner: K, name: &str, size: u64, sha256: &str, now: u64) -> Result<Self, UploadError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(UploadError::BlankName);
+        }
+        if size == 0 || size > Self::MAX_SIZE {
+            return Err(UploadError::InvalidSize { max: Self::MAX_SIZE });
+        }
+
+        Ok(Self {
+            owner,
+            name: name.to_string(),
+            size,
+            sha256: sha256.parse().map_err(|_| UploadError::InvalidDigest(sha256.to_string()))?,
+            received: 0,
+            expires_at: now.saturating_add(Self::LIFETIME),
+        })
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Checks that `caller` may continue the upload identified by `upload_id`.
+    ///
+    /// Uploads of other users are reported as missing, so their identifiers reveal nothing.
+    pub fn check_access(&self, upload_id: u64, caller: &K, now: u64) -> Result<(), UploadError> {
+        if self.owner != *caller {
+            return Err(UploadError::NotFound(upload_id));
+        }
+        if self.is_expired(now) {
+            return Err(UploadError::Expired(upload_id));
+        }
+        Ok(())
+    }
+
+    /// Records a chunk of `length` bytes starting at `offset`
+    pub fn accept_chunk(&mut self, offset: u64, length: u64) -> Result<(), UploadError> {
+        if length == 0 || length > Self::MAX_CHUNK_SIZE {
+            return Err(UploadError::InvalidChunkSize { max: Self::MAX_CHUNK_SIZE });
+        }
+        if offset != self.received {
+            return Err(UploadError::UnexpectedOffset { expected: self.received });
+        }
+        if self.received + length > self.size {
+            return Err(UploadError::ChunkPastEnd { size: self.size });
+        }
+
+        self.received += length;
+        Ok(())
+    }
+
+    /// Joins the chunks of a complete upload, in order, and checks them against the declared digest
+    pub fn assemble(&self, chunks: impl IntoIterator<Item = Vec<u8>>) -> Result<Vec<u8>, UploadError> {
+        if self.received != self.size {
+            return Err(UploadError::Incomplete { received: self.received, size: self.size });
+        }
+
+        let mut content = Vec::with_capacity(self.size as usize);
+        for chunk in chunks {
+            content.extend(chunk);
+        }
+        if content.len() as u64 != self.size || BlobHash::of(&content) != self.sha256 {
+            return Err(UploadError::DigestMismatch);
+        }
+
+        Ok(content)
+    }
+}
+
+/// Key of a chunk of an upload: the upload and the offset the chunk starts at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UploadChunkKey {
+    pub upload_id: u64,
+    pub offset: u64,
+}
+
+impl UploadChunkKey {
+    /// Returns the range of the keys of every chunk of an upload
+    pub fn range(upload_id: u64) -> RangeInclusive<UploadChunkKey> {
+        UploadChunkKey { upload_id, offset: 0 }..=UploadChunkKey { upload_id, offset: u64::MAX }
+    }
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for UploadChunkKey {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            // Big-endian, so that keys order by upload, then by offset
+            Cow::Owned([self.upload_id.to_be_bytes(), self.offset.to_be_bytes()].concat())
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            let (upload_id, offset) = bytes.split_at(8);
+            UploadChunkKey {
+                upload_id: u64::from_be_bytes(upload_id.try_into().unwrap()),
+                offset: u64::from_be_bytes(offset.try_into().unwrap()),
+            }
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            max_size: 16,
+            is_fixed_size: true,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SHA-256 of "hello world"
+    const HELLO_WORLD: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[test]
+    fn test_new_session() {
+        let session = UploadSession::new(1, " paper.pdf ", 11, HELLO_WORLD, 100).unwrap();
+        assert_eq!(session.name, "paper.pdf");
+        assert_eq!(session.expires_at, 100 + UploadSession::<u8>::LIFETIME);
+
+        assert_eq!(UploadSession::new(1, " ", 11, HELLO_WORLD, 0), Err(UploadError::BlankName));
+        assert!(matches!(UploadSession::new(1, "a.pdf", 0, HELLO_WORLD, 0), Err(UploadError::InvalidSize { .. })));
+        assert!(matches!(UploadSession::new(1, "a.pdf", UploadSession::<u8>::MAX_SIZE + 1, HELLO_WORLD, 0), Err(UploadError::InvalidSize { .. })));
+        for digest in ["", &HELLO_WORLD[1..], HELLO_WORLD.replace('b', "g").as_str()] {
+            assert!(matches!(UploadSession::new(1, "a.pdf", 11, digest, 0), Err(UploadError::InvalidDigest(_))), "{}", digest);
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut session = UploadSession::new(1, "a.pdf", 11, HELLO_WORLD, 0).unwrap();
+        assert_eq!(session.check_access(7, &2, 0), Err(UploadError::NotFound(7)));
+        assert_eq!(session.check_access(7, &1, session.expires_at), Err(UploadError::Expired(7)));
+
+        session.accept_chunk(0, 6).unwrap();
+        assert_eq!(session.accept_chunk(0, 5), Err(UploadError::UnexpectedOffset { expected: 6 }));
+        assert_eq!(session.accept_chunk(6, 6), Err(UploadError::ChunkPastEnd { size: 11 }));
+        assert_eq!(session.assemble([b"hello ".to_vec()]), Err(UploadError::Incomplete { received: 6, size: 11 }));
+
+        session.accept_chunk(6, 5).unwrap();
+        assert_eq!(session.assemble([b"hello ".to_vec(), b"world".to_vec()]), Ok(b"hello world".to_vec()));
+        assert_eq!(session.assemble([b"hello ".to_vec(), b"World".to_vec()]), Err(UploadError::DigestMismatch));
+    }
+}
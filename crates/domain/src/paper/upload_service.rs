@@ -0,0 +1,169 @@
+use crate::paper::{
+    repository::UploadRepository,
+    upload::{UploadError, UploadSession},
+    value_object::content::RawFile,
+};
+
+/// Receives files in chunks until they are complete and match their digest.
+///
+/// Uploads are abandoned once expired. Expired uploads are dropped along with their
+/// chunks, a bounded batch at a time, whenever a new upload begins and by periodic
+/// sweeps in between.
+pub struct UploadService<R>
+where
+    R: UploadRepository,
+{
+    repository: R,
+}
+
+impl<R> UploadService<R>
+where
+    R: UploadRepository,
+{
+    /// Number of expired uploads dropped by each sweep, to bound its cost
+    pub const SWEEP_LIMIT: usize = 16;
+
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// Begins an upload for `owner` and returns its identifier
+    pub fn begin(&mut self, owner: R::UserPrimaryKey, name: &str, size: u64, sha256: &str, now: u64) -> Result<u64, UploadError> {
+        self.remove_expired(now, Self::SWEEP_LIMIT);
+
+        // Expired uploads the sweep has not reached yet do not count against the owner
+        let open = self.repository.iter()
+            .filter(|(_, session)| session.owner == owner && !session.is_expired(now))
+            .count();
+        if open >= UploadSession::<R::UserPrimaryKey>::MAX_OPEN as usize {
+            return Err(UploadError::TooManyUploads { max: UploadSession::<R::UserPrimaryKey>::MAX_OPEN });
+        }
+
+        let session = UploadSession::new(owner, name, size, sha256, now)?;
+        if self.repository.open_bytes() + session.size > UploadSession::<R::UserPrimaryKey>::MAX_OPEN_BYTES {
+            return Err(UploadError::UploadsFull { max: UploadSession::<R::UserPrimaryKey>::MAX_OPEN_BYTES });
+        }
+        let upload_id = self.repository.generate_id();
+        self.repository.insert(upload_id, session);
+        Ok(upload_id)
+    }
+
+    /// Appends a chunk to an upload and returns the number of bytes received so far
+    pub fn append(&mut self, caller: &R::UserPrimaryKey, upload_id: u64, offset: u64, bytes: Vec<u8>, now: u64) -> Result<u64, UploadError> {
+        let mut session = self.get(caller, upload_id, now)?;
+        session.accept_chunk(offset, bytes.len() as u64)?;
+
+        let received = session.received;
+        self.repository.insert_chunk(upload_id, offset, bytes);
+        self.repository.insert(upload_id, session);
+        Ok(received)
+    }
+
+    /// Returns the file of a complete upload once it matches its digest.
+    ///
+    /// The upload stays open until `finish` is called.
+    pub fn assemble(&self, caller: &R::UserPrimaryKey, upload_id: u64, now: u64) -> Result<RawFile, UploadError> {
+        let session = self.get(caller, upload_id, now)?;
+        let content = session.assemble(self.repository.iter_chunks(upload_id))?;
+        Ok(RawFile { name: session.name, content })
+    }
+
+    /// Drops an upload and its chunks
+    pub fn finish(&mut self, upload_id: u64) {
+        self.repository.remove(upload_id);
+    }
+
+    /// Drops up to `limit` expired uploads along with their chunks, and returns how many were dropped
+    pub fn remove_expired(&mut self, now: u64, limit: usize) -> usize {
+        let expired: Vec<_> = self.repository.iter()
+            .filter(|(_, session)| session.is_expired(now))
+            .map(|(upload_id, _)| upload_id)
+            .take(limit)
+            .collect();
+        for upload_id in &expired {
+            self.repository.remove(*upload_id);
+        }
+        expired.len()
+    }
+
+    fn get(&self, caller: &R::UserPrimaryKey, upload_id: u64, now: u64) -> Result<UploadSession<R::UserPrimaryKey>, UploadError> {
+        let session = self.repository.get(upload_id).ok_or(UploadError::NotFound(upload_id))?;
+        session.check_access(upload_id, caller, now)?;
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MemoryUploadRepository;
+
+    type Session = UploadSession<u8>;
+
+    // SHA-256 of "hello world"
+    const HELLO_WORLD: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[test]
+    fn test_remove_expired() {
+        let repository = MemoryUploadRepository::default();
+        let mut service = UploadService::new(repository.clone());
+        let first = service.begin(1, "a.pdf", 11, HELLO_WORLD, 0).unwrap();
+        let second = service.begin(2, "b.pdf", 11, HELLO_WORLD, 10).unwrap();
+        let third = service.begin(3, "c.pdf", 11, HELLO_WORLD, 20).unwrap();
+        service.append(&1, first, 0, b"hello ".to_vec(), 0).unwrap();
+        service.append(&2, second, 0, b"hello ".to_vec(), 10).unwrap();
+
+        // Nothing has expired yet
+        assert_eq!(service.remove_expired(Session::LIFETIME - 1, usize::MAX), 0);
+
+        // Sweeps drop at most `limit` expired uploads along with their chunks
+        assert_eq!(service.remove_expired(Session::LIFETIME + 10, 1), 1);
+        assert_eq!(repository.get(first), None);
+        assert_eq!(repository.chunk_count(), 1);
+        assert_eq!(service.remove_expired(Session::LIFETIME + 10, usize::MAX), 1);
+        assert_eq!(repository.get(second), None);
+        assert_eq!(repository.chunk_count(), 0);
+        assert!(repository.get(third).is_some());
+        assert_eq!(repository.open_bytes(), 11);
+    }
+
+    #[test]
+    fn test_max_open() {
+        let mut service = UploadService::new(MemoryUploadRepository::default());
+        for _ in 0..Session::MAX_OPEN {
+            service.begin(1, "a.pdf", 11, HELLO_WORLD, 0).unwrap();
+        }
+        assert_eq!(service.begin(1, "a.pdf", 11, HELLO_WORLD, 0), Err(UploadError::TooManyUploads { max: Session::MAX_OPEN }));
+
+        // Other users may still begin uploads, and the user once theirs have expired
+        assert!(service.begin(2, "a.pdf", 11, HELLO_WORLD, 0).is_ok());
+        assert!(service.begin(1, "a.pdf", 11, HELLO_WORLD, Session::LIFETIME).is_ok());
+    }
+
+    #[test]
+    fn test_begin_sweep_limit() {
+        let mut service = UploadService::new(MemoryUploadRepository::default());
+        let uploads = UploadService::<MemoryUploadRepository>::SWEEP_LIMIT as u8 + 2;
+        for owner in 0..uploads {
+            service.begin(owner, "a.pdf", 11, HELLO_WORLD, 0).unwrap();
+        }
+
+        // Beginning an upload drops a bounded batch of expired uploads, leaving the rest to sweeps
+        service.begin(uploads, "a.pdf", 11, HELLO_WORLD, Session::LIFETIME).unwrap();
+        assert_eq!(service.remove_expired(Session::LIFETIME, usize::MAX), 2);
+    }
+
+    #[test]
+    fn test_max_open_bytes() {
+        let mut service = UploadService::new(MemoryUploadRepository::default());
+        let uploads = (Session::MAX_OPEN_BYTES / Session::MAX_SIZE) as u8;
+        for owner in 0..uploads {
+            service.begin(owner, "a.pdf", Session::MAX_SIZE, HELLO_WORLD, 0).unwrap();
+        }
+        assert_eq!(service.begin(uploads, "a.pdf", 1, HELLO_WORLD, 0), Err(UploadError::UploadsFull { max: Session::MAX_OPEN_BYTES }));
+
+        // Finishing an upload frees its bytes
+        service.finish(1);
+        assert!(service.begin(uploads, "a.pdf", Session::MAX_SIZE, HELLO_WORLD, 0).is_ok());
+    }
+}
@@ -1,17 +1,36 @@
+pub mod blob;
 pub mod category;
 pub mod citation;
+pub mod citation_format;
+pub mod citation_style;
 pub mod content;
+pub mod graph_format;
 pub mod id;
+pub mod identifier;
+pub mod listing;
+pub mod metrics;
 pub mod number;
+pub mod reference;
+pub mod role;
 pub mod status;
 pub mod summary;
 pub mod title;
 pub mod version;
 
+pub use blob::{BlobChunkKey, BlobEntry, BlobHash, BlobHashError};
 pub use category::PaperCategory;
-pub use citation::Citation;
+pub use citation::{Citation, WorkMetadata};
+pub use citation_format::CitationFormat;
+pub use citation_style::CitationStyle;
 pub use content::{PaperContents, ContentFileSource};
+pub use graph_format::GraphFormat;
 pub use id::{PaperId, PaperIdError};
-pub use status::PaperStatus;
+pub use identifier::{ArxivId, Doi, GitHubRepository, IdentifierError, Isbn, RfcNumber};
+pub use listing::{ListingCursor, ListingCursorError, PaperSort, SortKey};
+pub use metrics::AuthorCitationMetrics;
+pub use reference::{BrokenReference, BrokenReferenceReason, CitationContext, CitationIntent, Reference, ReferenceError};
+pub use role::{CoAuthorRole, PaperAction, PaperPermissionError, PaperRole};
+pub use status::{PaperStatus, PaperStatusTransition, PaperStatusTransitionError};
 pub use summary::PaperSummary;
 pub use title::{PaperTitle, PaperTitleError};
+pub use version::{PaperVersion, PaperVersionError};
@@ -0,0 +1,161 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid SHA-256 digest: '{0}'")]
+pub struct BlobHashError(String);
+
+/// SHA-256 digest of the content of a file, which identifies it in the blob store.
+///
+/// # Format
+/// 64 hexadecimal digits, written in lowercase and read in either case.
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlobHash([u8; 32]);
+
+impl BlobHash {
+    /// Returns the digest of `content`
+    pub fn of(content: &[u8]) -> Self {
+        Self(Sha256::digest(content).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for BlobHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BlobHash {
+    type Err = BlobHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || BlobHashError(s.to_string());
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(error());
+        }
+
+        let mut digest = [0; 32];
+        for (index, byte) in digest.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[index * 2..index * 2 + 2], 16).map_err(|_| error())?;
+        }
+        Ok(Self(digest))
+    }
+}
+
+/// Size and number of references of a blob.
+///
+/// Each revision of a paper holding the file counts as a reference. Blobs no longer
+/// referenced are kept until they are collected, so they can be referenced again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobEntry {
+    pub size: u64,
+    pub references: u32,
+}
+
+impl BlobEntry {
+    /// Size of the chunks the content of blobs is stored in
+    pub const CHUNK_SIZE: usize = 1024 * 1024;
+    /// Most bytes of a blob read at once, so that a read fits in a reply
+    pub const MAX_READ: u64 = 2 * 1024 * 1024;
+}
+
+/// Key of a chunk of a blob: the blob and the position of the chunk in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlobChunkKey {
+    pub hash: BlobHash,
+    pub index: u32,
+}
+
+impl BlobChunkKey {
+    /// Returns the range of the keys of every chunk of a blob
+    pub fn range(hash: BlobHash) -> RangeInclusive<BlobChunkKey> {
+        BlobChunkKey { hash, index: 0 }..=BlobChunkKey { hash, index: u32::MAX }
+    }
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for BlobHash {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Borrowed(&self.0)
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            Self(bytes.as_ref().try_into().expect("Failed to decode BlobHash"))
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            max_size: 32,
+            is_fixed_size: true,
+        };
+    }
+
+    impl Storable for BlobEntry {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned([self.size.to_be_bytes().as_slice(), &self.references.to_be_bytes()].concat())
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            let (size, references) = bytes.split_at(8);
+            BlobEntry {
+                size: u64::from_be_bytes(size.try_into().unwrap()),
+                references: u32::from_be_bytes(references.try_into().unwrap()),
+            }
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            max_size: 12,
+            is_fixed_size: true,
+        };
+    }
+
+    impl Storable for BlobChunkKey {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            // Big-endian, so that keys order by blob, then by position
+            Cow::Owned([self.hash.as_bytes().as_slice(), &self.index.to_be_bytes()].concat())
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            let (hash, index) = bytes.split_at(32);
+            BlobChunkKey {
+                hash: BlobHash(hash.try_into().unwrap()),
+                index: u32::from_be_bytes(index.try_into().unwrap()),
+            }
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            max_size: 36,
+            is_fixed_size: true,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_hash() {
+        let hash = BlobHash::of(b"hello world");
+        assert_eq!(hash.to_string(), "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9");
+        assert_eq!(hash.to_string().to_uppercase().parse(), Ok(hash));
+
+        for invalid in ["", "b94d", "g".repeat(64).as_str(), "é".repeat(32).as_str()] {
+            assert!(invalid.parse::<BlobHash>().is_err(), "{}", invalid);
+        }
+    }
+}
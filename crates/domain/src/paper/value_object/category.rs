@@ -1,8 +1,9 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Category for technical papers
-#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PaperCategory {
     Programming,
     SystemDesign,
@@ -12,3 +13,36 @@ pub enum PaperCategory {
     Blockchain,
     Other(String),
 }
+
+impl fmt::Display for PaperCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaperCategory::Programming => write!(f, "programming"),
+            PaperCategory::SystemDesign => write!(f, "system design"),
+            PaperCategory::DevOps => write!(f, "devops"),
+            PaperCategory::Security => write!(f, "security"),
+            PaperCategory::MachineLearning => write!(f, "machine learning"),
+            PaperCategory::Blockchain => write!(f, "blockchain"),
+            PaperCategory::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for PaperCategory {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned(candid::encode_one(self).expect("Failed to encode PaperCategory"))
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            candid::decode_one(&bytes).expect("Failed to decode PaperCategory")
+        }
+
+        const BOUND: Bound = Bound::Unbounded;
+    }
+}
@@ -1,10 +1,111 @@
-use super::PaperId;
+use super::{ArxivId, Doi, GitHubRepository, IdentifierError, Isbn, PaperId, RfcNumber};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
+/// Optional bibliographic details of a cited external work
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorkMetadata {
+    pub title: Option<String>,
+    /// Author names in citation order
+    pub authors: Vec<String>,
+    pub year: Option<u16>,
+}
+
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub enum Citation {
     Paper(PaperId),
     Url(String),
     Other(String),
+    Doi { doi: Doi, work: WorkMetadata },
+    Arxiv { id: ArxivId, work: WorkMetadata },
+    Isbn { isbn: Isbn, work: WorkMetadata },
+    Rfc { number: RfcNumber, work: WorkMetadata },
+    GitHub { repository: GitHubRepository, work: WorkMetadata },
+}
+
+impl Citation {
+    /// Returns the canonical link of a cited external work.
+    ///
+    /// Papers of this platform and free-form citations have none.
+    pub fn url(&self) -> Option<String> {
+        match self {
+            Citation::Paper(_) | Citation::Other(_) => None,
+            Citation::Url(url) => Some(url.clone()),
+            Citation::Doi { doi, .. } => Some(doi.url()),
+            Citation::Arxiv { id, .. } => Some(id.url()),
+            Citation::Isbn { isbn, .. } => Some(isbn.url()),
+            Citation::Rfc { number, .. } => Some(number.url()),
+            Citation::GitHub { repository, .. } => Some(repository.url()),
+        }
+    }
+
+    /// Returns the bibliographic details given for an identified external work
+    pub fn work(&self) -> Option<&WorkMetadata> {
+        match self {
+            Citation::Paper(_) | Citation::Url(_) | Citation::Other(_) => None,
+            Citation::Doi { work, .. }
+            | Citation::Arxiv { work, .. }
+            | Citation::Isbn { work, .. }
+            | Citation::Rfc { work, .. }
+            | Citation::GitHub { work, .. } => Some(work),
+        }
+    }
+
+    /// Checks the identifier of an external work and returns the citation with the
+    /// identifier in its canonical form.
+    ///
+    /// Identifiers received from clients are decoded without going through their
+    /// constructors, so they must be parsed again before being stored.
+    pub fn normalized(self) -> Result<Self, IdentifierError> {
+        Ok(match self {
+            Citation::Paper(_) | Citation::Url(_) | Citation::Other(_) => self,
+            Citation::Doi { doi, work } => Citation::Doi { doi: Doi::new(doi.as_str())?, work },
+            Citation::Arxiv { id, work } => Citation::Arxiv { id: ArxivId::new(id.as_str())?, work },
+            Citation::Isbn { isbn, work } => Citation::Isbn { isbn: Isbn::new(isbn.as_str())?, work },
+            Citation::Rfc { number, work } => Citation::Rfc { number: RfcNumber::new(number.number().to_string())?, work },
+            Citation::GitHub { repository, work } => Citation::GitHub { repository: GitHubRepository::new(repository.to_string())?, work },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let work = WorkMetadata::default();
+        let citation = Citation::Doi { doi: Doi::new("10.1145/3290380").unwrap(), work: work.clone() };
+        assert_eq!(citation.url().as_deref(), Some("https://doi.org/10.1145/3290380"));
+        assert_eq!(citation.work(), Some(&work));
+
+        let citation = Citation::Rfc { number: RfcNumber::new("9110").unwrap(), work };
+        assert_eq!(citation.url().as_deref(), Some("https://www.rfc-editor.org/rfc/rfc9110"));
+
+        assert_eq!(Citation::Other("Personal communication".to_string()).url(), None);
+    }
+
+    /// Decodes an identifier the way a request does, without going through its constructor
+    fn decoded<T: for<'de> Deserialize<'de> + CandidType>(raw: &str) -> T {
+        candid::decode_one(&candid::encode_one(raw).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_normalized() {
+        let work = WorkMetadata::default();
+
+        let citation = Citation::Doi { doi: decoded("doi:10.1145/3290380"), work: work.clone() };
+        assert_eq!(citation.normalized(), Ok(Citation::Doi { doi: Doi::new("10.1145/3290380").unwrap(), work: work.clone() }));
+
+        let citation = Citation::Arxiv { id: decoded("arXiv:2101.00001v2"), work: work.clone() };
+        let Ok(Citation::Arxiv { id, .. }) = citation.normalized() else { panic!("arXiv ID rejected") };
+        assert_eq!(id.as_str(), "2101.00001v2");
+
+        let citation = Citation::Isbn { isbn: decoded("ISBN 0-306-40615-2"), work: work.clone() };
+        let Ok(Citation::Isbn { isbn, .. }) = citation.normalized() else { panic!("ISBN rejected") };
+        assert_eq!(isbn.as_str(), "0306406152");
+
+        let citation = Citation::Isbn { isbn: decoded("0-306-40615-3"), work };
+        assert_eq!(citation.normalized(), Err(IdentifierError::IsbnChecksum("0-306-40615-3".to_string())));
+    }
 }
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable formats papers and bibliographies are exported to
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum CitationFormat {
+    /// BibTeX, as used by LaTeX
+    BibTeX,
+    /// RIS, as imported by most reference managers
+    Ris,
+    /// CSL-JSON, as used by Zotero and citeproc
+    CslJson,
+}
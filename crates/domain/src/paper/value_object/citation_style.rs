@@ -0,0 +1,20 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Styles bibliographies are rendered in as plain text
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum CitationStyle {
+    /// APA, 7th edition
+    Apa,
+    /// IEEE, numbered in citation order
+    Ieee,
+    /// Chicago author-date, 17th edition
+    Chicago,
+}
+
+impl CitationStyle {
+    /// Returns true if bibliographies in this style are sorted by author rather than numbered
+    pub fn is_alphabetical(&self) -> bool {
+        !matches!(self, CitationStyle::Ieee)
+    }
+}
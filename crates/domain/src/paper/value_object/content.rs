@@ -1,3 +1,4 @@
+use crate::paper::BlobHash;
 use candid::CandidType;
 use serde::{Serialize, Deserialize};
 
@@ -13,12 +14,24 @@ impl PaperContents {
     pub fn new(text: Option<String>, pdf: Option<ContentFileSource>) -> Self {
         Self { text, pdf }
     }
+
+    /// Returns true if the paper has either non-blank text or a PDF
+    pub fn has_content(&self) -> bool {
+        self.text.as_deref().is_some_and(|text| !text.trim().is_empty()) || self.pdf.is_some()
+    }
 }
 
+/// Where the PDF of a paper comes from.
+///
+/// Papers read from the repository reference stored files as `Blob`, so that the file is
+/// not stored again when the paper is saved. Clients are sent the reference alone, as a
+/// file may be larger than a reply, and read its content in ranges from the blob store.
+/// Clients send new files as `Raw`, and keep a stored file by sending its reference back.
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum ContentFileSource {
     Raw(RawFile),
     Http(String),
+    Blob(BlobFile),
 }
 
 impl Default for ContentFileSource {
@@ -33,6 +46,32 @@ pub struct RawFile {
     pub content: Vec<u8>,
 }
 
+/// A file in the blob store, referenced by the digest of its content
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlobFile {
+    pub name: String,
+    pub hash: BlobHash,
+    pub size: u64,
+}
+
+impl BlobFile {
+    /// Returns the reference a file has in the blob store
+    pub fn of(file: &RawFile) -> Self {
+        Self { name: file.name.clone(), hash: BlobHash::of(&file.content), size: file.content.len() as u64 }
+    }
+}
+
+/// How the PDF of a paper is stored.
+///
+/// Raw files are moved to the blob store, so that identical files are stored once.
+/// `Raw` remains for files stored before the blob store existed.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum StoredFile {
+    Raw(RawFile),
+    Http(String),
+    Blob(BlobFile),
+}
+
 #[cfg(feature = "ic-stable")]
 mod ic_stable {
     use super::*;
@@ -50,4 +89,16 @@ mod ic_stable {
 
         const BOUND: Bound = Bound::Unbounded;
     }
+
+    impl Storable for StoredFile {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned(candid::encode_one(self).expect("Failed to encode StoredFile"))
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            candid::decode_one(&bytes).expect("Failed to decode StoredFile")
+        }
+
+        const BOUND: Bound = Bound::Unbounded;
+    }
 }
@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Formats networks of papers and authors are exported to
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum GraphFormat {
+    /// GraphML, as read by Gephi, yEd and NetworkX
+    GraphML,
+    /// Graphviz DOT
+    Dot,
+}
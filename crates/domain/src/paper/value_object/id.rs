@@ -1,6 +1,7 @@
+use super::PaperVersion;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use std::{fmt, str::FromStr};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
 use thiserror::Error;
 
 const UNIX_EPOCH_YEAR: u16 = 1970;
@@ -40,6 +41,9 @@ pub enum PaperIdError {
 
     #[error("Invalid version in PaperId: version must be greater than zero, got {0}")]
     ZeroVersion(u16),
+
+    #[error("Invalid version in PaperId: no version follows {0}")]
+    VersionOverflow(u16),
 }
 
 /// Represents a unique identifier for papers in the system.
@@ -176,6 +180,11 @@ impl FromStr for PaperId {
 }
 
 impl PaperId {
+    /// The identifier ordering before every other one
+    pub const MIN: PaperId = PaperId { months: 0, number: 0, version: 1 };
+    /// The identifier ordering after every other one
+    pub const MAX: PaperId = PaperId { months: u16::MAX, number: u32::MAX, version: u16::MAX };
+
     /// Creates a new PaperId
     pub fn new(months: u16, number: u32, version: u16) -> Result<Self, PaperIdError> {
         // Validate version is not zero
@@ -190,6 +199,11 @@ impl PaperId {
         })
     }
 
+    /// Returns the range of identifiers of the papers created in the given months since epoch
+    pub fn created_in(months: RangeInclusive<u16>) -> RangeInclusive<PaperId> {
+        PaperId { months: *months.start(), ..Self::MIN }..=PaperId { months: *months.end(), ..Self::MAX }
+    }
+
     pub fn months(&self) -> u16 {
         self.months
     }
@@ -201,6 +215,40 @@ impl PaperId {
     pub fn version(&self) -> u16 {
         self.version
     }
+
+    /// Returns the version of this identifier as a `PaperVersion`
+    pub fn paper_version(&self) -> PaperVersion {
+        // Versions are validated on construction, so this never sees zero
+        PaperVersion::new(self.version).unwrap_or_default()
+    }
+
+    /// Returns the identifier of the same paper at the given version
+    pub fn with_version(&self, version: PaperVersion) -> Self {
+        Self {
+            version: version.get(),
+            ..*self
+        }
+    }
+
+    /// Returns the identifier of the first version of this paper
+    pub fn first_version(&self) -> Self {
+        self.with_version(PaperVersion::INITIAL)
+    }
+
+    /// Returns the identifier of the next version of this paper
+    pub fn next_version(&self) -> Result<Self, PaperIdError> {
+        let next = self
+            .paper_version()
+            .next()
+            .map_err(|_| PaperIdError::VersionOverflow(self.version))?;
+
+        Ok(self.with_version(next))
+    }
+
+    /// Returns true if both identifiers refer to the same paper, regardless of version
+    pub fn is_same_paper(&self, other: &Self) -> bool {
+        self.months == other.months && self.number == other.number
+    }
 }
 
 #[cfg(feature = "ic-stable")]
@@ -323,4 +371,32 @@ mod tests {
         ids.sort();
         assert_eq!(ids, vec![&id1, &id2, &id3, &id4]);
     }
+
+    #[test]
+    fn test_paper_id_versions() {
+        let id = PaperId { months: 660, number: 7, version: 1 };
+
+        let v2 = id.next_version().unwrap();
+        assert_eq!(v2.to_string(), "2025-01-0007-v2");
+        assert!(v2.is_same_paper(&id));
+        assert_eq!(v2.first_version(), id);
+
+        let other = PaperId { months: 660, number: 8, version: 2 };
+        assert!(!other.is_same_paper(&v2));
+
+        let last = PaperId { months: 660, number: 7, version: u16::MAX };
+        match last.next_version().unwrap_err() {
+            PaperIdError::VersionOverflow(v) => assert_eq!(v, u16::MAX),
+            e => panic!("Expected VersionOverflow error, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_paper_id_created_in() {
+        let range = PaperId::created_in(660..=661);
+        assert!(range.contains(&PaperId { months: 660, number: 1, version: 1 }));
+        assert!(range.contains(&PaperId { months: 661, number: u32::MAX, version: 3 }));
+        assert!(!range.contains(&PaperId { months: 659, number: 9, version: 1 }));
+        assert!(!range.contains(&PaperId { months: 662, number: 1, version: 1 }));
+    }
 }
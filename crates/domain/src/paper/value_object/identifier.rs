@@ -0,0 +1,349 @@
+use candid::CandidType;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Error returned when an identifier of an external work is malformed
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum IdentifierError {
+    #[error("invalid DOI: {0}")]
+    Doi(String),
+
+    #[error("invalid arXiv identifier: {0}")]
+    Arxiv(String),
+
+    #[error("invalid ISBN: {0}")]
+    Isbn(String),
+
+    #[error("ISBN checksum does not match: {0}")]
+    IsbnChecksum(String),
+
+    #[error("invalid RFC number: {0}")]
+    Rfc(String),
+
+    #[error("invalid GitHub repository: {0}")]
+    GitHub(String),
+}
+
+/// Strips the first matching prefix, ignoring ASCII case
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefixes: &[&str]) -> &'a str {
+    for prefix in prefixes {
+        if value.len() >= prefix.len()
+            && value.is_char_boundary(prefix.len())
+            && value[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            return &value[prefix.len()..];
+        }
+    }
+    value
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// A Digital Object Identifier such as `10.1145/3290380`
+///
+/// `doi:` prefixes and `doi.org` links are accepted and stripped.
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Display)]
+pub struct Doi(String);
+
+impl Doi {
+    pub fn new(doi: impl AsRef<str>) -> Result<Self, IdentifierError> {
+        let doi = doi.as_ref().trim();
+        let doi = strip_prefix_ignore_case(doi, &[
+            "https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi.org/", "doi:",
+        ]).trim();
+
+        let valid = doi.strip_prefix("10.")
+            .and_then(|rest| rest.split_once('/'))
+            .is_some_and(|(registrant, suffix)| {
+                registrant.split('.').all(is_digits)
+                    && !suffix.is_empty()
+                    && !suffix.chars().any(char::is_whitespace)
+            });
+        if !valid {
+            return Err(IdentifierError::Doi(doi.to_string()));
+        }
+
+        Ok(Self(doi.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the `doi.org` link resolving this DOI
+    pub fn url(&self) -> String {
+        format!("https://doi.org/{}", self.0)
+    }
+}
+
+/// An arXiv identifier in either the current `2101.00001v2` or the legacy `hep-th/9901001` scheme
+///
+/// `arXiv:` prefixes and `arxiv.org` links are accepted and stripped.
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Display)]
+pub struct ArxivId(String);
+
+impl ArxivId {
+    pub fn new(id: impl AsRef<str>) -> Result<Self, IdentifierError> {
+        let id = id.as_ref().trim();
+        let id = strip_prefix_ignore_case(id, &[
+            "https://arxiv.org/abs/", "http://arxiv.org/abs/", "https://arxiv.org/pdf/", "http://arxiv.org/pdf/", "arxiv:",
+        ]).trim();
+        let id = id.strip_suffix(".pdf").unwrap_or(id);
+
+        // An optional version suffix such as `v2` may follow either scheme
+        let unversioned = match id.rfind('v') {
+            Some(index) if is_digits(&id[index + 1..]) => &id[..index],
+            _ => id,
+        };
+        let has_month = |digits: &str| digits.get(2..4)
+            .and_then(|month| month.parse::<u32>().ok())
+            .is_some_and(|month| (1..=12).contains(&month));
+
+        let valid = match unversioned.split_once('/') {
+            Some((archive, number)) => {
+                let (archive, class) = archive.split_once('.').unwrap_or((archive, "AA"));
+                !archive.is_empty()
+                    && archive.bytes().all(|byte| byte.is_ascii_lowercase() || byte == b'-')
+                    && class.len() == 2 && class.bytes().all(|byte| byte.is_ascii_uppercase())
+                    && number.len() == 7 && is_digits(number) && has_month(number)
+            }
+            None => unversioned.split_once('.').is_some_and(|(month, number)| {
+                month.len() == 4 && is_digits(month) && has_month(month)
+                    && (4..=5).contains(&number.len()) && is_digits(number)
+            }),
+        };
+        if !valid {
+            return Err(IdentifierError::Arxiv(id.to_string()));
+        }
+
+        Ok(Self(id.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the abstract page of the preprint
+    pub fn url(&self) -> String {
+        format!("https://arxiv.org/abs/{}", self.0)
+    }
+}
+
+/// An ISBN-10 or ISBN-13 with a verified check digit
+///
+/// Hyphens, spaces and an `ISBN` prefix are accepted; the stored form has none of them.
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Display)]
+pub struct Isbn(String);
+
+impl Isbn {
+    pub fn new(isbn: impl AsRef<str>) -> Result<Self, IdentifierError> {
+        let raw = isbn.as_ref().trim();
+        let isbn = strip_prefix_ignore_case(raw, &["ISBN-13:", "ISBN-10:", "ISBN-13", "ISBN-10", "ISBN:", "ISBN"]);
+        let isbn: String = isbn.chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        let digits: Option<Vec<u32>> = isbn.chars().enumerate()
+            .map(|(index, c)| match c {
+                'X' if index == 9 && isbn.len() == 10 => Some(10),
+                _ => c.to_digit(10),
+            })
+            .collect();
+        let checksum_matches = match digits {
+            Some(digits) if digits.len() == 10 => {
+                digits.iter().enumerate().map(|(index, digit)| (10 - index as u32) * digit).sum::<u32>() % 11 == 0
+            }
+            Some(digits) if digits.len() == 13 && (isbn.starts_with("978") || isbn.starts_with("979")) => {
+                digits.iter().enumerate().map(|(index, digit)| if index % 2 == 0 { *digit } else { 3 * digit }).sum::<u32>() % 10 == 0
+            }
+            _ => return Err(IdentifierError::Isbn(raw.to_string())),
+        };
+        if !checksum_matches {
+            return Err(IdentifierError::IsbnChecksum(raw.to_string()));
+        }
+
+        Ok(Self(isbn))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the Open Library page of the book
+    pub fn url(&self) -> String {
+        format!("https://openlibrary.org/isbn/{}", self.0)
+    }
+}
+
+/// The number of an IETF Request for Comments
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Display)]
+#[display("RFC {}", _0)]
+pub struct RfcNumber(u32);
+
+impl RfcNumber {
+    /// Parses `RFC 9110`, `rfc9110`, `9110` or an RFC Editor link
+    pub fn new(rfc: impl AsRef<str>) -> Result<Self, IdentifierError> {
+        let raw = rfc.as_ref().trim();
+        let rfc = strip_prefix_ignore_case(raw, &[
+            "https://www.rfc-editor.org/rfc/", "https://rfc-editor.org/rfc/", "https://datatracker.ietf.org/doc/html/",
+        ]);
+        let rfc = rfc.trim_end_matches(".html").trim_end_matches(".txt");
+        let rfc = strip_prefix_ignore_case(rfc, &["RFC"]).trim_start_matches(['-', ' ']);
+
+        match rfc.parse::<u32>() {
+            Ok(number) if number > 0 && is_digits(rfc) => Ok(Self(number)),
+            _ => Err(IdentifierError::Rfc(raw.to_string())),
+        }
+    }
+
+    pub fn number(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the page of the RFC on the RFC Editor site
+    pub fn url(&self) -> String {
+        format!("https://www.rfc-editor.org/rfc/rfc{}", self.0)
+    }
+}
+
+/// A repository on GitHub, identified by its owner and name
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Display)]
+#[display("{}/{}", owner, name)]
+pub struct GitHubRepository {
+    owner: String,
+    name: String,
+}
+
+impl GitHubRepository {
+    /// Maximum length of a GitHub user or organization name
+    pub const MAX_OWNER_LENGTH: usize = 39;
+    /// Maximum length of a GitHub repository name
+    pub const MAX_NAME_LENGTH: usize = 100;
+
+    /// Parses `owner/name` or a `github.com` link, which may point into the repository
+    pub fn new(repository: impl AsRef<str>) -> Result<Self, IdentifierError> {
+        let raw = repository.as_ref().trim();
+        let path = strip_prefix_ignore_case(raw, &["https://github.com/", "http://github.com/", "github.com/", "github:"]);
+        let is_link = path.len() != raw.len();
+
+        let mut segments = path.trim_end_matches('/').split('/');
+        let owner = segments.next().unwrap_or_default();
+        let name = segments.next().unwrap_or_default();
+        let name = name.strip_suffix(".git").unwrap_or(name);
+        // Only links may carry a path below the repository, such as `/tree/main`
+        let has_rest = segments.next().is_some();
+
+        let owner_valid = (1..=Self::MAX_OWNER_LENGTH).contains(&owner.len())
+            && owner.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+            && !owner.starts_with('-') && !owner.ends_with('-');
+        let name_valid = (1..=Self::MAX_NAME_LENGTH).contains(&name.len())
+            && name.bytes().all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'-'))
+            && name != "." && name != "..";
+        if !owner_valid || !name_valid || (has_rest && !is_link) {
+            return Err(IdentifierError::GitHub(raw.to_string()));
+        }
+
+        Ok(Self { owner: owner.to_string(), name: name.to_string() })
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the page of the repository
+    pub fn url(&self) -> String {
+        format!("https://github.com/{}/{}", self.owner, self.name)
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($identifier:ty),*) => {
+        $(
+            impl FromStr for $identifier {
+                type Err = IdentifierError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::new(s)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str!(Doi, ArxivId, Isbn, RfcNumber, GitHubRepository);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doi() {
+        assert_eq!(Doi::new("10.1145/3290380").unwrap().as_str(), "10.1145/3290380");
+        assert_eq!(Doi::new("doi:10.1000/xyz123").unwrap().as_str(), "10.1000/xyz123");
+        assert_eq!(Doi::new("https://doi.org/10.1000.10/a(b)c").unwrap().url(), "https://doi.org/10.1000.10/a(b)c");
+
+        assert!(Doi::new("11.1000/xyz").is_err());
+        assert!(Doi::new("10.1000").is_err());
+        assert!(Doi::new("10.abc/xyz").is_err());
+        assert!(Doi::new("10.1000/with space").is_err());
+    }
+
+    #[test]
+    fn test_arxiv_id() {
+        assert_eq!(ArxivId::new("2101.00001").unwrap().as_str(), "2101.00001");
+        assert_eq!(ArxivId::new("arXiv:1501.0001v3").unwrap().as_str(), "1501.0001v3");
+        assert_eq!(ArxivId::new("hep-th/9901001").unwrap().url(), "https://arxiv.org/abs/hep-th/9901001");
+        assert_eq!(ArxivId::new("https://arxiv.org/pdf/math.GT/0309136v1.pdf").unwrap().as_str(), "math.GT/0309136v1");
+
+        assert!(ArxivId::new("2113.00001").is_err());
+        assert!(ArxivId::new("2101.001").is_err());
+        assert!(ArxivId::new("hep-th/99010").is_err());
+        assert!(ArxivId::new("HEP/9901001").is_err());
+    }
+
+    #[test]
+    fn test_isbn() {
+        assert_eq!(Isbn::new("0-306-40615-2").unwrap().as_str(), "0306406152");
+        assert_eq!(Isbn::new("ISBN 978-0-306-40615-7").unwrap().url(), "https://openlibrary.org/isbn/9780306406157");
+        assert_eq!(Isbn::new("080442957x").unwrap().as_str(), "080442957X");
+
+        assert_eq!(Isbn::new("0-306-40615-3"), Err(IdentifierError::IsbnChecksum("0-306-40615-3".to_string())));
+        assert_eq!(Isbn::new("978-0-306-40615-8"), Err(IdentifierError::IsbnChecksum("978-0-306-40615-8".to_string())));
+        assert!(matches!(Isbn::new("123-0-306-40615-7"), Err(IdentifierError::Isbn(_))));
+        assert!(matches!(Isbn::new("X306406152"), Err(IdentifierError::Isbn(_))));
+    }
+
+    #[test]
+    fn test_rfc_number() {
+        assert_eq!(RfcNumber::new("RFC 9110").unwrap().number(), 9110);
+        assert_eq!(RfcNumber::new("rfc2616").unwrap().to_string(), "RFC 2616");
+        assert_eq!(RfcNumber::new("https://www.rfc-editor.org/rfc/rfc793.html").unwrap().url(), "https://www.rfc-editor.org/rfc/rfc793");
+
+        assert!(RfcNumber::new("RFC 0").is_err());
+        assert!(RfcNumber::new("RFC +12").is_err());
+        assert!(RfcNumber::new("draft-ietf-httpbis").is_err());
+    }
+
+    #[test]
+    fn test_github_repository() {
+        let repository = GitHubRepository::new("rust-lang/rust").unwrap();
+        assert_eq!((repository.owner(), repository.name()), ("rust-lang", "rust"));
+        assert_eq!(repository.url(), "https://github.com/rust-lang/rust");
+        assert_eq!(GitHubRepository::new("https://github.com/typst/typst.git").unwrap().to_string(), "typst/typst");
+        assert_eq!(GitHubRepository::new("https://github.com/dfinity/ic/tree/master/rs").unwrap().to_string(), "dfinity/ic");
+
+        assert!(GitHubRepository::new("rust-lang").is_err());
+        assert!(GitHubRepository::new("-owner/repo").is_err());
+        assert!(GitHubRepository::new("owner/..").is_err());
+        assert!(GitHubRepository::new("owner/repo/tree/main").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use super::PaperId;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt, str::FromStr};
+use thiserror::Error;
+
+/// Orders paper listings can be sorted in
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum PaperSort {
+    /// Most recently created first
+    Newest,
+    /// Most recently updated first
+    RecentlyUpdated,
+    /// Most cited first
+    MostCited,
+    /// Alphabetically by title, ignoring case
+    Title,
+}
+
+impl PaperSort {
+    /// Returns true if papers with greater sort keys come first
+    pub fn is_descending(&self) -> bool {
+        !matches!(self, PaperSort::Title)
+    }
+
+    fn tag(&self) -> char {
+        match self {
+            PaperSort::Newest => 'n',
+            PaperSort::RecentlyUpdated => 'u',
+            PaperSort::MostCited => 'c',
+            PaperSort::Title => 't',
+        }
+    }
+
+    fn from_tag(tag: char) -> Option<Self> {
+        match tag {
+            'n' => Some(PaperSort::Newest),
+            'u' => Some(PaperSort::RecentlyUpdated),
+            'c' => Some(PaperSort::MostCited),
+            't' => Some(PaperSort::Title),
+            _ => None,
+        }
+    }
+}
+
+/// Value a listing is sorted by: a date or count, or a title in lowercase
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SortKey {
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid listing cursor: '{0}'")]
+pub struct ListingCursorError(String);
+
+/// Position of a paper in a listing.
+///
+/// A cursor holds the sort key of the paper rather than its offset, so the page after
+/// it starts at the same place however many papers were added or removed since.
+///
+/// # Format
+/// A letter for the sort, the key and the identifier of the paper, separated by a dot.
+/// Titles are written in hexadecimal so that they cannot contain the separator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListingCursor {
+    pub sort: PaperSort,
+    pub key: SortKey,
+    pub paper_id: PaperId,
+}
+
+impl ListingCursor {
+    /// Compares two positions in the order papers are listed
+    pub fn cmp_listed(&self, other: &Self) -> Ordering {
+        let order = (&self.key, &self.paper_id).cmp(&(&other.key, &other.paper_id));
+        if self.sort.is_descending() {
+            order.reverse()
+        } else {
+            order
+        }
+    }
+}
+
+impl fmt::Display for ListingCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sort.tag())?;
+        match &self.key {
+            SortKey::Number(number) => write!(f, "{}", number)?,
+            SortKey::Text(text) => {
+                for byte in text.as_bytes() {
+                    write!(f, "{:02x}", byte)?;
+                }
+            }
+        }
+        write!(f, ".{}", self.paper_id)
+    }
+}
+
+impl FromStr for ListingCursor {
+    type Err = ListingCursorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ListingCursorError(s.to_string());
+
+        let mut chars = s.chars();
+        let sort = chars.next().and_then(PaperSort::from_tag).ok_or_else(error)?;
+        let (key, paper_id) = chars.as_str().split_once('.').ok_or_else(error)?;
+
+        let key = match sort {
+            PaperSort::Title => {
+                if key.len() % 2 != 0 || !key.is_ascii() {
+                    return Err(error());
+                }
+                let bytes = (0..key.len()).step_by(2)
+                    .map(|index| u8::from_str_radix(&key[index..index + 2], 16))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| error())?;
+                SortKey::Text(String::from_utf8(bytes).map_err(|_| error())?)
+            }
+            _ => SortKey::Number(key.parse().map_err(|_| error())?),
+        };
+        let paper_id = paper_id.parse().map_err(|_| error())?;
+
+        Ok(ListingCursor { sort, key, paper_id })
+    }
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for SortKey {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            match self {
+                SortKey::Number(number) => Cow::Owned([[0].as_slice(), &number.to_be_bytes()].concat()),
+                SortKey::Text(text) => Cow::Owned([[1].as_slice(), text.as_bytes()].concat()),
+            }
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            match bytes[0] {
+                0 => SortKey::Number(u64::from_be_bytes(bytes[1..9].try_into().expect("Failed to decode SortKey"))),
+                _ => SortKey::Text(String::from_utf8_lossy(&bytes[1..]).into_owned()),
+            }
+        }
+
+        const BOUND: Bound = Bound::Unbounded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let paper_id = PaperId::from_str("2025-01-0001-v2").unwrap();
+        let cursors = [
+            ListingCursor { sort: PaperSort::Newest, key: SortKey::Number(1_736_899_200_000_000_000), paper_id },
+            ListingCursor { sort: PaperSort::Title, key: SortKey::Text("typst. 入門".to_string()), paper_id },
+        ];
+        for cursor in cursors {
+            assert_eq!(cursor.to_string().parse::<ListingCursor>(), Ok(cursor));
+        }
+
+        for invalid in ["", "x1.2025-01-0001", "n1", "nabc.2025-01-0001", "t0.2025-01-0001", "tzz.2025-01-0001", "n1.2025"] {
+            assert!(invalid.parse::<ListingCursor>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_cmp_listed() {
+        let cursor = |sort, key, number| ListingCursor { sort, key, paper_id: PaperId::from_str(&format!("2025-01-{:04}", number)).unwrap() };
+
+        // Newest first, and later papers first among papers created at once
+        let (older, newer) = (cursor(PaperSort::Newest, SortKey::Number(1), 2), cursor(PaperSort::Newest, SortKey::Number(2), 1));
+        assert_eq!(newer.cmp_listed(&older), Ordering::Less);
+        let tied = cursor(PaperSort::Newest, SortKey::Number(1), 3);
+        assert_eq!(tied.cmp_listed(&older), Ordering::Less);
+
+        let (a, b) = (cursor(PaperSort::Title, SortKey::Text("a".to_string()), 2), cursor(PaperSort::Title, SortKey::Text("b".to_string()), 1));
+        assert_eq!(a.cmp_listed(&b), Ordering::Less);
+    }
+}
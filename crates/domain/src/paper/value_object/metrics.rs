@@ -0,0 +1,57 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Citation metrics of an author, computed from the citation counts of their papers
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct AuthorCitationMetrics {
+    /// Number of papers the metrics were computed from
+    pub papers: u32,
+    /// Total number of citations across those papers
+    pub citations: u32,
+    /// Largest h such that h papers have at least h citations each
+    pub h_index: u32,
+    /// Number of papers with at least 10 citations
+    pub i10_index: u32,
+}
+
+impl AuthorCitationMetrics {
+    /// Computes the metrics from the citation count of each paper
+    pub fn from_counts(counts: impl IntoIterator<Item = u32>) -> Self {
+        let mut counts: Vec<u32> = counts.into_iter().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        let h_index = counts.iter()
+            .enumerate()
+            .take_while(|(rank, count)| **count as usize > *rank)
+            .count();
+
+        Self {
+            papers: counts.len() as u32,
+            citations: counts.iter().sum(),
+            h_index: h_index as u32,
+            i10_index: counts.iter().filter(|count| **count >= 10).count() as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_counts() {
+        let metrics = AuthorCitationMetrics::from_counts([10, 8, 5, 4, 3]);
+        assert_eq!(metrics, AuthorCitationMetrics { papers: 5, citations: 30, h_index: 4, i10_index: 1 });
+
+        let metrics = AuthorCitationMetrics::from_counts([25, 8, 5, 3, 3]);
+        assert_eq!(metrics.h_index, 3);
+
+        let metrics = AuthorCitationMetrics::from_counts([0, 0]);
+        assert_eq!(metrics, AuthorCitationMetrics { papers: 2, citations: 0, h_index: 0, i10_index: 0 });
+    }
+
+    #[test]
+    fn test_from_no_papers() {
+        assert_eq!(AuthorCitationMetrics::from_counts([]), AuthorCitationMetrics::default());
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Citation, IdentifierError, PaperId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Error returned when a reference of a paper is invalid
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ReferenceError {
+    #[error(transparent)]
+    Identifier(#[from] IdentifierError),
+
+    #[error("locator exceeds maximum length of {0} characters")]
+    LocatorTooLong(usize),
+
+    #[error("quoted passage exceeds maximum length of {0} characters")]
+    QuoteTooLong(usize),
+}
+
+/// Why a paper cites a work
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum CitationIntent {
+    /// The work provides background or related work
+    Background,
+    /// The citing paper uses a method, tool or dataset of the work
+    UsesMethod,
+    /// The citing paper builds on the results of the work
+    Extends,
+    /// The citing paper disagrees with the work
+    Disputes,
+}
+
+impl CitationIntent {
+    /// Returns a short lowercase description of the intent
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CitationIntent::Background => "background",
+            CitationIntent::UsesMethod => "uses method",
+            CitationIntent::Extends => "extends",
+            CitationIntent::Disputes => "disputes",
+        }
+    }
+}
+
+/// How a paper cites a work; every part is optional
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CitationContext {
+    /// Page, section or other position within the cited work
+    pub locator: Option<String>,
+    /// Short passage quoted from the cited work
+    pub quote: Option<String>,
+    pub intent: Option<CitationIntent>,
+}
+
+impl CitationContext {
+    /// Maximum length of a locator
+    pub const MAX_LOCATOR_LENGTH: usize = 100;
+    /// Maximum length of a quoted passage
+    pub const MAX_QUOTE_LENGTH: usize = 500;
+
+    /// Returns true if the context says nothing about the citation
+    pub fn is_empty(&self) -> bool {
+        self.locator.is_none() && self.quote.is_none() && self.intent.is_none()
+    }
+
+    /// Trims the locator and quote, dropping the ones left blank
+    pub fn normalized(self) -> Self {
+        let normalize = |value: Option<String>| value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        CitationContext {
+            locator: normalize(self.locator),
+            quote: normalize(self.quote),
+            intent: self.intent,
+        }
+    }
+}
+
+/// An entry of the reference list of a paper
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    pub citation: Citation,
+    pub context: CitationContext,
+}
+
+impl Reference {
+    /// Creates a reference without context
+    pub fn new(citation: Citation) -> Self {
+        Self { citation, context: CitationContext::default() }
+    }
+
+    /// Checks the identifier of the cited work and the length of the context, and
+    /// returns the reference with the identifier in its canonical form and blank
+    /// parts of the context dropped
+    pub fn normalized(self) -> Result<Self, ReferenceError> {
+        let citation = self.citation.normalized()?;
+
+        let too_long = |value: &Option<String>, max: usize| value.as_ref().is_some_and(|value| value.chars().count() > max);
+        if too_long(&self.context.locator, CitationContext::MAX_LOCATOR_LENGTH) {
+            return Err(ReferenceError::LocatorTooLong(CitationContext::MAX_LOCATOR_LENGTH));
+        }
+        if too_long(&self.context.quote, CitationContext::MAX_QUOTE_LENGTH) {
+            return Err(ReferenceError::QuoteTooLong(CitationContext::MAX_QUOTE_LENGTH));
+        }
+        Ok(Reference { citation, context: self.context.normalized() })
+    }
+}
+
+impl From<Citation> for Reference {
+    fn from(citation: Citation) -> Self {
+        Self::new(citation)
+    }
+}
+
+/// Why a reference to a paper of this platform cannot be followed
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum BrokenReferenceReason {
+    /// No revision of the referenced paper exists anymore; repairing drops the reference
+    Deleted,
+    /// The referenced revision was deleted while later ones remain; repairing moves the
+    /// reference to the latest of them
+    RevisionDeleted,
+    /// The referenced paper exists but cannot be read by the caller
+    Unavailable,
+}
+
+/// A reference of a paper pointing at a paper that cannot be followed
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct BrokenReference {
+    /// Position of the reference in the reference list, starting at 0
+    pub index: u32,
+    pub paper_id: PaperId,
+    pub reason: BrokenReferenceReason,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn reference(context: CitationContext) -> Reference {
+        Reference { citation: Citation::Paper(PaperId::from_str("2025-01-0001").unwrap()), context }
+    }
+
+    #[test]
+    fn test_normalized() {
+        let context = CitationContext {
+            locator: Some("  p. 12 ".to_string()),
+            quote: Some("   ".to_string()),
+            intent: Some(CitationIntent::Extends),
+        }.normalized();
+
+        assert_eq!(context.locator.as_deref(), Some("p. 12"));
+        assert_eq!(context.quote, None);
+        assert!(!context.is_empty());
+        assert!(CitationContext::default().is_empty());
+    }
+
+    #[test]
+    fn test_reference_normalized() {
+        assert_eq!(reference(CitationContext::default()).normalized(), Ok(reference(CitationContext::default())));
+
+        let quote = "a".repeat(CitationContext::MAX_QUOTE_LENGTH + 1);
+        let result = reference(CitationContext { quote: Some(quote), ..Default::default() }).normalized();
+        assert_eq!(result, Err(ReferenceError::QuoteTooLong(CitationContext::MAX_QUOTE_LENGTH)));
+
+        let locator = "§".repeat(CitationContext::MAX_LOCATOR_LENGTH + 1);
+        let result = reference(CitationContext { locator: Some(locator), ..Default::default() }).normalized();
+        assert_eq!(result, Err(ReferenceError::LocatorTooLong(CitationContext::MAX_LOCATOR_LENGTH)));
+    }
+}
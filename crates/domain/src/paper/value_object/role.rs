@@ -0,0 +1,191 @@
+use super::PaperStatus;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// Role a co-author holds on a paper
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CoAuthorRole {
+    /// Can edit and publish the paper alongside the lead author
+    #[default]
+    Editor,
+    /// Is credited on the paper but cannot change it
+    ReadOnly,
+}
+
+/// Role a user holds on a paper, deciding which actions they may take
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum PaperRole {
+    /// The author who created the paper
+    LeadAuthor,
+    /// A co-author allowed to edit the paper
+    CoAuthorEditor,
+    /// A co-author who can only read the paper
+    CoAuthorReadOnly,
+    /// A reviewer assigned to the paper
+    Reviewer,
+    /// An editor handling the paper while it is under review
+    Editor,
+    /// A canister administrator
+    Admin,
+}
+
+/// An operation on a paper that requires permission
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum PaperAction {
+    /// Read the paper, including drafts
+    Read,
+    /// Edit the contents of the paper
+    Update,
+    /// Change the status of the paper or publish a revision
+    Publish,
+    /// Delete the paper and all of its revisions
+    Delete,
+    /// Invite, remove and change the role of co-authors
+    ManageAuthors,
+}
+
+impl PaperRole {
+    /// Returns true if this role may perform `action`.
+    ///
+    /// | Role               | Read | Update | Publish | Delete | ManageAuthors |
+    /// |--------------------|------|--------|---------|--------|---------------|
+    /// | `LeadAuthor`       | yes  | yes    | yes     | yes    | yes           |
+    /// | `CoAuthorEditor`   | yes  | yes    | yes     | no     | no            |
+    /// | `CoAuthorReadOnly` | yes  | no     | no      | no     | no            |
+    /// | `Reviewer`         | yes  | no     | no      | no     | no            |
+    /// | `Editor`           | yes  | no     | no      | no     | no            |
+    /// | `Admin`            | yes  | yes    | yes     | yes    | yes           |
+    pub fn allows(&self, action: PaperAction) -> bool {
+        match self {
+            Self::LeadAuthor | Self::Admin => true,
+            Self::CoAuthorEditor => matches!(action, PaperAction::Read | PaperAction::Update | PaperAction::Publish),
+            Self::CoAuthorReadOnly | Self::Reviewer | Self::Editor => action == PaperAction::Read,
+        }
+    }
+
+    /// Returns true if this role may read a paper in `status`.
+    ///
+    /// Drafts are only readable by authors and admins, while papers under
+    /// review are also readable by their reviewers and editors.
+    pub fn can_read(&self, status: PaperStatus) -> bool {
+        match status {
+            PaperStatus::Draft => !matches!(self, Self::Reviewer | Self::Editor),
+            PaperStatus::UnderReview | PaperStatus::Published | PaperStatus::Archived => true,
+        }
+    }
+
+    /// Checks that this role may perform `action` on a paper in `status`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the action is allowed
+    /// * `Err(PaperPermissionError::Forbidden)` - If the role does not allow the action
+    /// * `Err(PaperPermissionError::Hidden)` - If the role cannot read papers in this status
+    pub fn check(&self, action: PaperAction, status: PaperStatus) -> Result<(), PaperPermissionError> {
+        if !self.allows(action) {
+            return Err(PaperPermissionError::Forbidden { role: *self, action });
+        }
+        if !self.can_read(status) {
+            return Err(PaperPermissionError::Hidden(status));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<CoAuthorRole> for PaperRole {
+    fn from(role: CoAuthorRole) -> Self {
+        match role {
+            CoAuthorRole::Editor => Self::CoAuthorEditor,
+            CoAuthorRole::ReadOnly => Self::CoAuthorReadOnly,
+        }
+    }
+}
+
+impl fmt::Display for PaperRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeadAuthor => write!(f, "lead author"),
+            Self::CoAuthorEditor => write!(f, "co-author"),
+            Self::CoAuthorReadOnly => write!(f, "read-only co-author"),
+            Self::Reviewer => write!(f, "reviewer"),
+            Self::Editor => write!(f, "editor"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl fmt::Display for PaperAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Update => write!(f, "update"),
+            Self::Publish => write!(f, "change the status of"),
+            Self::Delete => write!(f, "delete"),
+            Self::ManageAuthors => write!(f, "manage the authors of"),
+        }
+    }
+}
+
+/// Error returned when a user may not perform an action on a paper
+#[derive(Error, CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PaperPermissionError {
+    #[error("a {role} cannot {action} this paper")]
+    Forbidden { role: PaperRole, action: PaperAction },
+
+    #[error("users without a role on this paper cannot {0} it")]
+    NoRole(PaperAction),
+
+    #[error("this {0} paper is not visible to the user")]
+    Hidden(PaperStatus),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        assert!(PaperRole::LeadAuthor.allows(PaperAction::ManageAuthors));
+        assert!(PaperRole::CoAuthorEditor.allows(PaperAction::Update));
+        assert!(!PaperRole::CoAuthorEditor.allows(PaperAction::Delete));
+        assert!(!PaperRole::CoAuthorReadOnly.allows(PaperAction::Update));
+        assert!(PaperRole::Reviewer.allows(PaperAction::Read));
+        assert!(!PaperRole::Editor.allows(PaperAction::Update));
+        assert!(PaperRole::Admin.allows(PaperAction::Delete));
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(PaperRole::CoAuthorEditor.check(PaperAction::Publish, PaperStatus::Draft), Ok(()));
+        assert_eq!(
+            PaperRole::Reviewer.check(PaperAction::Update, PaperStatus::UnderReview),
+            Err(PaperPermissionError::Forbidden {
+                role: PaperRole::Reviewer,
+                action: PaperAction::Update,
+            })
+        );
+        assert_eq!(
+            PaperRole::Reviewer.check(PaperAction::Read, PaperStatus::Draft),
+            Err(PaperPermissionError::Hidden(PaperStatus::Draft))
+        );
+    }
+
+    #[test]
+    fn test_can_read() {
+        assert!(PaperRole::CoAuthorReadOnly.can_read(PaperStatus::Draft));
+        assert!(PaperRole::Reviewer.can_read(PaperStatus::UnderReview));
+        assert!(!PaperRole::Reviewer.can_read(PaperStatus::Draft));
+        assert!(PaperRole::Reviewer.can_read(PaperStatus::Archived));
+        assert!(PaperRole::Editor.can_read(PaperStatus::UnderReview));
+        assert!(!PaperRole::Editor.can_read(PaperStatus::Draft));
+    }
+
+    #[test]
+    fn test_co_author_role() {
+        assert_eq!(CoAuthorRole::default(), CoAuthorRole::Editor);
+        assert_eq!(PaperRole::from(CoAuthorRole::ReadOnly), PaperRole::CoAuthorReadOnly);
+    }
+}
@@ -1,8 +1,10 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
 
 /// Status of an paper
-#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PaperStatus {
     /// Draft paper, only visible to authors
     Draft,
@@ -13,3 +15,250 @@ pub enum PaperStatus {
     /// Paper archived/no longer active
     Archived,
 }
+
+impl PaperStatus {
+    /// Returns true if papers in this status can be read by anyone
+    pub fn is_public(&self) -> bool {
+        matches!(self, PaperStatus::Published | PaperStatus::Archived)
+    }
+
+    /// Returns true if the public only sees a tombstone of papers in this status
+    pub fn is_tombstone(&self) -> bool {
+        matches!(self, PaperStatus::Archived)
+    }
+
+    /// Applies a transition to this status.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PaperStatus)` - The status the paper moves to
+    /// * `Err(PaperStatusTransitionError::InvalidTransition)` - If the transition does not start from this status
+    pub fn apply(&self, transition: PaperStatusTransition) -> Result<PaperStatus, PaperStatusTransitionError> {
+        if transition.source() != *self {
+            return Err(PaperStatusTransitionError::InvalidTransition {
+                from: *self,
+                transition,
+            });
+        }
+
+        Ok(transition.target())
+    }
+
+    /// Looks up the transition leading from this status to `target`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If `target` is the current status
+    /// * `Ok(Some(transition))` - The transition in the table leading to `target`
+    /// * `Err(PaperStatusTransitionError::Unreachable)` - If no single transition leads to `target`
+    pub fn transition_to(&self, target: PaperStatus) -> Result<Option<PaperStatusTransition>, PaperStatusTransitionError> {
+        if *self == target {
+            return Ok(None);
+        }
+
+        PaperStatusTransition::ALL
+            .into_iter()
+            .find(|transition| transition.source() == *self && transition.target() == target)
+            .map(Some)
+            .ok_or(PaperStatusTransitionError::Unreachable { from: *self, to: target })
+    }
+}
+
+impl fmt::Display for PaperStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaperStatus::Draft => write!(f, "draft"),
+            PaperStatus::Published => write!(f, "published"),
+            PaperStatus::UnderReview => write!(f, "under review"),
+            PaperStatus::Archived => write!(f, "archived"),
+        }
+    }
+}
+
+/// A change of status a paper can go through.
+///
+/// The variants make up the complete transition table:
+///
+/// | Transition | From        | To          |
+/// |------------|-------------|-------------|
+/// | `Submit`   | Draft       | UnderReview |
+/// | `Withdraw` | UnderReview | Draft       |
+/// | `Accept`   | UnderReview | Published   |
+/// | `Reject`   | UnderReview | Draft       |
+/// | `Publish`  | Draft       | Published   |
+/// | `Retract`  | Published   | Draft       |
+/// | `Archive`  | Published   | Archived    |
+/// | `Restore`  | Archived    | Published   |
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum PaperStatusTransition {
+    /// Authors submit a draft for review
+    Submit,
+    /// Authors withdraw a paper from review
+    Withdraw,
+    /// The review accepts the paper for publication
+    Accept,
+    /// The review returns the paper to its authors
+    Reject,
+    /// Authors publish a draft without review
+    Publish,
+    /// Authors take a published paper back to draft
+    Retract,
+    /// A published paper is archived
+    Archive,
+    /// An archived paper is published again
+    Restore,
+}
+
+impl PaperStatusTransition {
+    /// Every transition in the table
+    pub const ALL: [Self; 8] = [
+        Self::Submit,
+        Self::Withdraw,
+        Self::Accept,
+        Self::Reject,
+        Self::Publish,
+        Self::Retract,
+        Self::Archive,
+        Self::Restore,
+    ];
+
+    /// Returns the status this transition starts from
+    pub fn source(&self) -> PaperStatus {
+        match self {
+            Self::Submit | Self::Publish => PaperStatus::Draft,
+            Self::Withdraw | Self::Accept | Self::Reject => PaperStatus::UnderReview,
+            Self::Retract | Self::Archive => PaperStatus::Published,
+            Self::Restore => PaperStatus::Archived,
+        }
+    }
+
+    /// Returns the status this transition leads to
+    pub fn target(&self) -> PaperStatus {
+        match self {
+            Self::Withdraw | Self::Reject | Self::Retract => PaperStatus::Draft,
+            Self::Submit => PaperStatus::UnderReview,
+            Self::Accept | Self::Publish | Self::Restore => PaperStatus::Published,
+            Self::Archive => PaperStatus::Archived,
+        }
+    }
+
+    /// Returns true if the transition is an editorial decision rather than an author action
+    pub fn is_editorial(&self) -> bool {
+        matches!(self, Self::Accept | Self::Reject)
+    }
+}
+
+impl fmt::Display for PaperStatusTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Submit => write!(f, "submit"),
+            Self::Withdraw => write!(f, "withdraw"),
+            Self::Accept => write!(f, "accept"),
+            Self::Reject => write!(f, "reject"),
+            Self::Publish => write!(f, "publish"),
+            Self::Retract => write!(f, "retract"),
+            Self::Archive => write!(f, "archive"),
+            Self::Restore => write!(f, "restore"),
+        }
+    }
+}
+
+/// Error returned when a paper cannot change its status
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PaperStatusTransitionError {
+    #[error("cannot {transition} a paper that is {from}")]
+    InvalidTransition {
+        from: PaperStatus,
+        transition: PaperStatusTransition,
+    },
+
+    #[error("a paper that is {from} cannot become {to} directly")]
+    Unreachable { from: PaperStatus, to: PaperStatus },
+
+    #[error("cannot {0} a paper without an abstract")]
+    MissingAbstract(PaperStatusTransition),
+
+    #[error("cannot {0} a paper without any content")]
+    MissingContent(PaperStatusTransition),
+
+    #[error("{0} is an editorial decision and cannot be made by authors")]
+    EditorialDecision(PaperStatusTransition),
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl Storable for PaperStatus {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            let tag: u8 = match self {
+                PaperStatus::Draft => 0,
+                PaperStatus::Published => 1,
+                PaperStatus::UnderReview => 2,
+                PaperStatus::Archived => 3,
+            };
+            Cow::Owned(vec![tag])
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            match bytes[0] {
+                0 => PaperStatus::Draft,
+                1 => PaperStatus::Published,
+                2 => PaperStatus::UnderReview,
+                3 => PaperStatus::Archived,
+                tag => panic!("Failed to decode PaperStatus: unknown tag {}", tag),
+            }
+        }
+
+        const BOUND: Bound = Bound::Bounded {
+            max_size: 1,
+            is_fixed_size: true,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(PaperStatus::Draft.apply(PaperStatusTransition::Submit), Ok(PaperStatus::UnderReview));
+        assert_eq!(PaperStatus::UnderReview.apply(PaperStatusTransition::Accept), Ok(PaperStatus::Published));
+        assert_eq!(PaperStatus::Published.apply(PaperStatusTransition::Archive), Ok(PaperStatus::Archived));
+
+        assert_eq!(
+            PaperStatus::Draft.apply(PaperStatusTransition::Archive),
+            Err(PaperStatusTransitionError::InvalidTransition {
+                from: PaperStatus::Draft,
+                transition: PaperStatusTransition::Archive,
+            })
+        );
+    }
+
+    #[test]
+    fn test_transition_to() {
+        assert_eq!(PaperStatus::Draft.transition_to(PaperStatus::Draft), Ok(None));
+        assert_eq!(
+            PaperStatus::Published.transition_to(PaperStatus::Draft),
+            Ok(Some(PaperStatusTransition::Retract))
+        );
+        assert_eq!(
+            PaperStatus::Draft.transition_to(PaperStatus::Archived),
+            Err(PaperStatusTransitionError::Unreachable {
+                from: PaperStatus::Draft,
+                to: PaperStatus::Archived,
+            })
+        );
+    }
+
+    #[test]
+    fn test_table_is_consistent() {
+        for transition in PaperStatusTransition::ALL {
+            assert_eq!(transition.source().apply(transition), Ok(transition.target()));
+            assert_ne!(transition.source(), transition.target());
+        }
+    }
+}
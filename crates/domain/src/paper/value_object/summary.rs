@@ -1,6 +1,6 @@
 use crate::user::UserPrimaryKey;
 
-use super::PaperId;
+use super::{PaperId, PaperStatus};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PaperSummary<U>
@@ -9,4 +9,5 @@ where
 {
     pub id: PaperId,
     pub lead_author: U,
+    pub status: PaperStatus,
 }
@@ -0,0 +1,106 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// Error returned when creating an invalid paper version
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PaperVersionError {
+    #[error("paper version must be greater than zero")]
+    Zero,
+
+    #[error("paper version cannot exceed {0}")]
+    Overflow(u16),
+}
+
+/// Revision number of a paper.
+///
+/// Every paper starts at version 1. Publishing a new revision freezes the
+/// current text under its version and continues under the next one, so a
+/// `PaperId` with an explicit version always resolves to the same text.
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct PaperVersion(u16);
+
+impl PaperVersion {
+    /// The version assigned to newly created papers
+    pub const INITIAL: Self = Self(1);
+
+    /// Creates a new PaperVersion, validating that it is not zero
+    pub fn new(version: u16) -> Result<Self, PaperVersionError> {
+        if version == 0 {
+            return Err(PaperVersionError::Zero);
+        }
+
+        Ok(Self(version))
+    }
+
+    /// Returns the raw version number
+    pub fn get(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the version following this one
+    pub fn next(&self) -> Result<Self, PaperVersionError> {
+        self.0
+            .checked_add(1)
+            .map(Self)
+            .ok_or(PaperVersionError::Overflow(u16::MAX))
+    }
+
+    /// Returns true if this is the first version of a paper
+    pub fn is_initial(&self) -> bool {
+        *self == Self::INITIAL
+    }
+}
+
+impl Default for PaperVersion {
+    fn default() -> Self {
+        Self::INITIAL
+    }
+}
+
+impl fmt::Display for PaperVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+impl TryFrom<u16> for PaperVersion {
+    type Error = PaperVersionError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<PaperVersion> for u16 {
+    fn from(version: PaperVersion) -> Self {
+        version.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(PaperVersion::new(1).unwrap(), PaperVersion::INITIAL);
+        assert_eq!(PaperVersion::new(0), Err(PaperVersionError::Zero));
+    }
+
+    #[test]
+    fn test_next() {
+        let v2 = PaperVersion::INITIAL.next().unwrap();
+        assert_eq!(v2.get(), 2);
+        assert!(!v2.is_initial());
+
+        let last = PaperVersion::new(u16::MAX).unwrap();
+        assert_eq!(last.next(), Err(PaperVersionError::Overflow(u16::MAX)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(PaperVersion::new(3).unwrap().to_string(), "v3");
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(feature = "dto")]
+pub mod entity;
+#[cfg(feature = "entity")]
+pub mod repository;
+#[cfg(feature = "entity")]
+pub mod service;
+#[cfg(feature = "value-object")]
+pub mod value_object;
+
+#[cfg(feature = "value-object")]
+pub use value_object::*;
@@ -0,0 +1,5 @@
+#[cfg(feature = "entity")]
+pub mod dao;
+pub mod dto;
+#[cfg(feature = "entity")]
+pub mod model;
@@ -0,0 +1,42 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use crate::user::UserPrimaryKey;
+
+mod v1;
+mod v2;
+pub(crate) use v1::{RoundV1, V1};
+pub(crate) use v2::V2;
+
+/// Represents the peer review of a paper in the system
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct ReviewDao<K: UserPrimaryKey> {
+    pub(crate) version: ReviewDaoVersion<K>,
+}
+
+#[cfg(feature = "ic-stable")]
+mod ic_stable {
+    use super::*;
+    use ic_stable_structures::storable::{Bound, Storable};
+    use std::borrow::Cow;
+
+    impl<K> Storable for ReviewDao<K>
+    where
+        K: UserPrimaryKey + CandidType + for<'de> Deserialize<'de>,
+    {
+        fn to_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned(candid::encode_one(self).expect("Failed to encode ReviewDao"))
+        }
+
+        fn from_bytes(bytes: Cow<[u8]>) -> Self {
+            candid::decode_one(&bytes).expect("Failed to decode ReviewDao")
+        }
+
+        const BOUND: Bound = Bound::Unbounded;
+    }
+}
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub(crate) enum ReviewDaoVersion<K: UserPrimaryKey> {
+    V1(V1<K>),
+    V2(V2<K>),
+}
@@ -0,0 +1,25 @@
+use crate::{review::{EditorialDecision, ReviewAssignment, ReviewReport}, user::UserPrimaryKey};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct V1<K: UserPrimaryKey> {
+    /// Review rounds of the paper, oldest first
+    pub rounds: Vec<RoundV1<K>>,
+}
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct RoundV1<K: UserPrimaryKey> {
+    /// Reviewers assigned to the round
+    pub assignments: Vec<ReviewAssignment<K>>,
+    /// Reports submitted by the assigned reviewers
+    pub reports: Vec<ReviewReport<K>>,
+    /// The editorial decision closing the round, if any
+    pub decision: Option<EditorialDecision>,
+    /// The editor who made the decision
+    pub decided_by: Option<K>,
+    /// When the round was opened in nanoseconds since epoch
+    pub opened_at: u64,
+    /// When the round was closed in nanoseconds since epoch
+    pub closed_at: Option<u64>,
+}
@@ -0,0 +1,12 @@
+use crate::{review::ReviewMode, user::UserPrimaryKey};
+use super::RoundV1;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
+pub struct V2<K: UserPrimaryKey> {
+    /// How identities are disclosed during the review
+    pub mode: ReviewMode,
+    /// Review rounds of the paper, oldest first
+    pub rounds: Vec<RoundV1<K>>,
+}
@@ -0,0 +1,133 @@
+use crate::{
+    review::{EditorialDecision, ReviewMode, ReviewRecommendation, ReviewScore},
+    user::UserName,
+};
+use serde::Deserialize;
+#[cfg(feature = "entity")]
+use crate::{
+    review::entity::model,
+    user::repository::UserRepository,
+};
+
+#[derive(Clone, Debug, candid::CandidType, Deserialize)]
+pub struct Review {
+    /// The paper under review
+    pub paper_id: String,
+    /// How identities are disclosed during the review
+    pub mode: ReviewMode,
+    /// Review rounds, oldest first
+    pub rounds: Vec<ReviewRound>,
+}
+
+impl Review {
+    /// Replaces every reviewer with a numbered placeholder.
+    ///
+    /// Placeholders are numbered per round in order of assignment, so the
+    /// reports of a round can still be told apart.
+    pub fn masked(mut self) -> Self {
+        for round in &mut self.rounds {
+            let placeholder = |reviewers: &[Reviewer], reviewer: &Reviewer| {
+                let position = reviewers.iter().position(|r| r.id == reviewer.id).unwrap_or(reviewers.len());
+                Reviewer::anonymous(position + 1)
+            };
+
+            for report in &mut round.reports {
+                report.reviewer = placeholder(&round.reviewers, &report.reviewer);
+            }
+            round.reviewers = (1..=round.reviewers.len()).map(Reviewer::anonymous).collect();
+        }
+        self
+    }
+}
+
+impl Reviewer {
+    fn anonymous(number: usize) -> Self {
+        Reviewer {
+            id: String::new(),
+            name: UserName::new(&format!("Reviewer {}", number)).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, candid::CandidType, Deserialize)]
+pub struct ReviewRound {
+    /// Reviewers assigned to the round
+    pub reviewers: Vec<Reviewer>,
+    /// Reports submitted by the assigned reviewers
+    pub reports: Vec<ReviewReport>,
+    /// The editorial decision closing the round, if any
+    pub decision: Option<EditorialDecision>,
+    /// When the round was opened in nanoseconds since epoch
+    pub opened_at: u64,
+    /// When the round was closed in nanoseconds since epoch
+    pub closed_at: Option<u64>,
+}
+
+#[derive(Clone, Debug, candid::CandidType, Deserialize)]
+pub struct Reviewer {
+    /// The reviewer's user ID, or `p_`-prefixed principal if unset.
+    /// Empty if the reviewer is masked.
+    pub id: String,
+    /// The reviewer's display name
+    pub name: UserName,
+}
+
+#[derive(Clone, Debug, candid::CandidType, Deserialize)]
+pub struct ReviewReport {
+    /// The reviewer who wrote the report
+    pub reviewer: Reviewer,
+    /// Overall score of the paper
+    pub score: ReviewScore,
+    /// What the reviewer recommends the editor decides
+    pub recommendation: ReviewRecommendation,
+    /// Comments for the authors and the editor
+    pub comments: String,
+    /// When the report was submitted in nanoseconds since epoch
+    pub submitted_at: u64,
+}
+
+#[cfg(feature = "entity")]
+impl Review {
+    pub fn from_model<T: UserRepository>(review: model::Review<T::PrimaryKey>, user_repo: &T) -> Option<Self> {
+        let rounds = review.rounds.into_iter().map(|round| -> Option<_> {
+            let reviewers = round.assignments.iter()
+                .map(|assignment| Reviewer::from_primary_key(&assignment.reviewer, user_repo))
+                .collect::<Option<Vec<_>>>()?;
+            let reports = round.reports.into_iter().map(|report| -> Option<_> {
+                Some(ReviewReport {
+                    reviewer: Reviewer::from_primary_key(&report.reviewer, user_repo)?,
+                    score: report.score,
+                    recommendation: report.recommendation,
+                    comments: report.comments,
+                    submitted_at: report.submitted_at,
+                })
+            }).collect::<Option<Vec<_>>>()?;
+
+            Some(ReviewRound {
+                reviewers,
+                reports,
+                decision: round.decision,
+                opened_at: round.opened_at,
+                closed_at: round.closed_at,
+            })
+        }).collect::<Option<Vec<_>>>()?;
+
+        Some(Review {
+            paper_id: review.paper_id.to_string(),
+            mode: review.mode,
+            rounds,
+        })
+    }
+}
+
+#[cfg(feature = "entity")]
+impl Reviewer {
+    fn from_primary_key<T: UserRepository>(key: &T::PrimaryKey, user_repo: &T) -> Option<Self> {
+        let id = match user_repo.get_user_id(key) {
+            Some(id) => id.to_string(),
+            None => format!("p_{}", key),
+        };
+        let name = user_repo.get_name_by_primary_key(key)?;
+        Some(Reviewer { id, name })
+    }
+}
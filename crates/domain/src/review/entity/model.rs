@@ -0,0 +1,281 @@
+use crate::{
+    paper::{entity::model::Paper, PaperId},
+    review::{EditorialDecision, ReviewAssignment, ReviewMode, ReviewReport},
+    user::UserPrimaryKey,
+};
+use super::dao::{ReviewDao, ReviewDaoVersion, RoundV1, V2};
+use thiserror::Error;
+use util::time::now;
+
+/// Errors raised when a review round rejects an operation
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ReviewError {
+    #[error("Reviewer is already assigned to this paper")]
+    AlreadyAssigned,
+    #[error("Authors cannot review their own paper")]
+    ReviewerIsAuthor,
+    #[error("User is not assigned to review this paper")]
+    NotAssigned,
+    #[error("Reviewer has already submitted a report")]
+    AlreadyReported,
+    #[error("A decision requires at least one review report")]
+    NoReports,
+    #[error("No review round is open for this paper")]
+    NoOpenRound,
+    #[error("Review mode cannot change once a review round has started")]
+    ReviewStarted,
+}
+
+/// Peer review of a paper, made up of one round per submission
+#[derive(Clone, Debug)]
+pub struct Review<K: UserPrimaryKey> {
+    /// The paper under review
+    pub paper_id: PaperId,
+    /// How identities are disclosed during the review
+    pub mode: ReviewMode,
+    /// Review rounds, oldest first
+    pub rounds: Vec<ReviewRound<K>>,
+}
+
+/// A single round of review, from submission to editorial decision
+#[derive(Clone, Debug)]
+pub struct ReviewRound<K: UserPrimaryKey> {
+    /// Reviewers assigned to the round
+    pub assignments: Vec<ReviewAssignment<K>>,
+    /// Reports submitted by the assigned reviewers
+    pub reports: Vec<ReviewReport<K>>,
+    /// The editorial decision closing the round, if any
+    pub decision: Option<EditorialDecision>,
+    /// The editor who made the decision
+    pub decided_by: Option<K>,
+    /// When the round was opened in nanoseconds since epoch
+    pub opened_at: u64,
+    /// When the round was closed in nanoseconds since epoch
+    pub closed_at: Option<u64>,
+}
+
+impl<K: UserPrimaryKey> ReviewRound<K> {
+    fn new() -> Self {
+        Self {
+            assignments: Vec::new(),
+            reports: Vec::new(),
+            decision: None,
+            decided_by: None,
+            opened_at: now(),
+            closed_at: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.closed_at.is_none()
+    }
+
+    pub fn is_reviewer(&self, user: &K) -> bool {
+        self.assignments.iter().any(|assignment| assignment.reviewer == *user)
+    }
+}
+
+impl<K: UserPrimaryKey> Review<K> {
+    /// Creates a review without any rounds
+    pub fn new(paper_id: PaperId) -> Self {
+        Self {
+            paper_id,
+            mode: ReviewMode::default(),
+            rounds: Vec::new(),
+        }
+    }
+
+    /// Returns the round currently open, if any
+    pub fn open_round(&self) -> Option<&ReviewRound<K>> {
+        self.rounds.last().filter(|round| round.is_open())
+    }
+
+    fn open_round_mut(&mut self) -> Result<&mut ReviewRound<K>, ReviewError> {
+        self.rounds.last_mut()
+            .filter(|round| round.is_open())
+            .ok_or(ReviewError::NoOpenRound)
+    }
+
+    /// Returns true if no round of the review is open
+    pub fn is_closed(&self) -> bool {
+        self.open_round().is_none()
+    }
+
+    /// Changes the review mode, which is only possible before the first round.
+    ///
+    /// Every round is reviewed in the same mode, so that opening a double-blind review
+    /// between revision rounds cannot reveal the reviewers of the earlier rounds.
+    pub fn set_mode(&mut self, mode: ReviewMode) -> Result<(), ReviewError> {
+        if !self.rounds.is_empty() {
+            return Err(ReviewError::ReviewStarted);
+        }
+
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Returns true if the last round ended with a final decision
+    pub fn is_decided(&self) -> bool {
+        self.rounds.last()
+            .and_then(|round| round.decision)
+            .is_some_and(|decision| decision.is_final())
+    }
+
+    /// Returns true if authors and reviewers must not learn each other's identities
+    ///
+    /// Identities stay hidden through revision rounds and are revealed once the paper
+    /// is accepted or rejected.
+    pub fn hides_identities(&self) -> bool {
+        self.mode == ReviewMode::DoubleBlind && !self.is_decided()
+    }
+
+    /// Returns true if the user was assigned as a reviewer in any round
+    pub fn is_reviewer(&self, user: &K) -> bool {
+        self.rounds.iter().any(|round| round.is_reviewer(user))
+    }
+
+    /// Assigns a reviewer, opening a new round if the last one was closed
+    pub fn assign(&mut self, paper: &Paper<K>, reviewer: K, assigned_by: K) -> Result<(), ReviewError> {
+        if paper.is_author(&reviewer) {
+            return Err(ReviewError::ReviewerIsAuthor);
+        }
+        if self.is_closed() {
+            self.rounds.push(ReviewRound::new());
+        }
+
+        let round = self.open_round_mut()?;
+        if round.is_reviewer(&reviewer) {
+            return Err(ReviewError::AlreadyAssigned);
+        }
+
+        round.assignments.push(ReviewAssignment {
+            reviewer,
+            assigned_by,
+            assigned_at: now(),
+        });
+        Ok(())
+    }
+
+    /// Records a report from a reviewer assigned to the open round
+    pub fn submit_report(&mut self, report: ReviewReport<K>) -> Result<(), ReviewError> {
+        let round = self.open_round_mut()?;
+        if !round.is_reviewer(&report.reviewer) {
+            return Err(ReviewError::NotAssigned);
+        }
+        if round.reports.iter().any(|existing| existing.reviewer == report.reviewer) {
+            return Err(ReviewError::AlreadyReported);
+        }
+
+        round.reports.push(report);
+        Ok(())
+    }
+
+    /// Closes the open round with an editorial decision
+    pub fn decide(&mut self, decision: EditorialDecision, editor: K) -> Result<(), ReviewError> {
+        let round = self.open_round_mut()?;
+        if round.reports.is_empty() {
+            return Err(ReviewError::NoReports);
+        }
+
+        round.decision = Some(decision);
+        round.decided_by = Some(editor);
+        round.closed_at = Some(now());
+        Ok(())
+    }
+}
+
+impl<K: UserPrimaryKey> Review<K> {
+    pub fn from_dao(review_dao: ReviewDao<K>, paper_id: PaperId) -> Self {
+        let (mode, rounds) = match review_dao.version {
+            ReviewDaoVersion::V1(v1) => (ReviewMode::Open, v1.rounds),
+            ReviewDaoVersion::V2(v2) => (v2.mode, v2.rounds),
+        };
+
+        Review {
+            paper_id,
+            mode,
+            rounds: rounds.into_iter().map(|round| ReviewRound {
+                assignments: round.assignments,
+                reports: round.reports,
+                decision: round.decision,
+                decided_by: round.decided_by,
+                opened_at: round.opened_at,
+                closed_at: round.closed_at,
+            }).collect(),
+        }
+    }
+}
+
+impl<K: UserPrimaryKey> From<Review<K>> for ReviewDao<K> {
+    fn from(review: Review<K>) -> Self {
+        ReviewDao {
+            version: ReviewDaoVersion::V2(V2 {
+                mode: review.mode,
+                rounds: review.rounds.into_iter().map(|round| RoundV1 {
+                    assignments: round.assignments,
+                    reports: round.reports,
+                    decision: round.decision,
+                    decided_by: round.decided_by,
+                    opened_at: round.opened_at,
+                    closed_at: round.closed_at,
+                }).collect(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decided(decision: Option<EditorialDecision>) -> ReviewRound<u8> {
+        ReviewRound {
+            assignments: Vec::new(),
+            reports: Vec::new(),
+            decision,
+            decided_by: decision.map(|_| 9),
+            opened_at: 0,
+            closed_at: decision.map(|_| 1),
+        }
+    }
+
+    #[test]
+    fn test_hides_identities() {
+        let mut review = Review::<u8>::new(PaperId::new(0, 1, 1).unwrap());
+        review.mode = ReviewMode::DoubleBlind;
+        review.rounds.push(decided(None));
+        assert!(review.hides_identities());
+
+        // A revision is reviewed again in another round, still double-blind
+        review.rounds[0] = decided(Some(EditorialDecision::MajorRevision));
+        assert!(review.is_closed());
+        assert!(review.hides_identities());
+        review.rounds.push(decided(Some(EditorialDecision::MinorRevision)));
+        assert!(review.hides_identities());
+
+        review.rounds.push(decided(Some(EditorialDecision::Accept)));
+        assert!(!review.hides_identities());
+        review.rounds.push(decided(None));
+        assert!(review.hides_identities());
+
+        review.mode = ReviewMode::Open;
+        assert!(!review.hides_identities());
+    }
+
+    #[test]
+    fn test_set_mode_between_rounds() {
+        let mut review = Review::<u8>::new(PaperId::new(0, 1, 1).unwrap());
+        assert_eq!(review.set_mode(ReviewMode::DoubleBlind), Ok(()));
+        review.rounds.push(decided(Some(EditorialDecision::MajorRevision)));
+
+        // Switching to open review before the revision is reviewed again would reveal
+        // the reviewers of the first round
+        assert!(review.is_closed());
+        assert_eq!(review.set_mode(ReviewMode::Open), Err(ReviewError::ReviewStarted));
+        assert!(review.hides_identities());
+
+        review.rounds.push(decided(None));
+        assert_eq!(review.set_mode(ReviewMode::Open), Err(ReviewError::ReviewStarted));
+        assert!(review.hides_identities());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    paper::PaperId,
+    review::entity::model::Review,
+    user,
+};
+
+/// Repository trait for peer review operations.
+/// Provides methods to store reviews and manage the editors deciding on them.
+pub trait ReviewRepository {
+    type UserPrimaryKey: user::UserPrimaryKey;
+
+    /// Retrieves the review of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The unique identifier of the reviewed paper
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Review)` - The review if the paper has ever been reviewed
+    /// * `None` - If no review exists for the paper
+    fn get(&self, paper_id: &PaperId) -> Option<Review<Self::UserPrimaryKey>>;
+
+    /// Inserts or updates the review of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The unique identifier of the reviewed paper
+    /// * `review` - The review entity to store
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Review)` - The previous review of the paper, if it existed
+    /// * `None` - If there was no previous review
+    fn insert(&mut self, paper_id: PaperId, review: Review<Self::UserPrimaryKey>) -> Option<Review<Self::UserPrimaryKey>>;
+
+    /// Removes the review of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `paper_id` - The unique identifier of the reviewed paper
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Review)` - The removed review if found
+    /// * `None` - If no review exists for the paper
+    fn remove(&mut self, paper_id: &PaperId) -> Option<Review<Self::UserPrimaryKey>>;
+
+    /// Iterates over the papers a user has been assigned to review.
+    ///
+    /// # Arguments
+    ///
+    /// * `reviewer` - The primary key of the reviewer
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the identifiers of the assigned papers.
+    fn iter_assigned(&self, reviewer: &Self::UserPrimaryKey) -> impl Iterator<Item = PaperId>;
+
+    /// Checks if a user is an editor.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The primary key of the user to check
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the user can make editorial decisions
+    /// * `false` - Otherwise
+    fn is_editor(&self, user: &Self::UserPrimaryKey) -> bool;
+
+    /// Grants a user the editor role.
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the user was not an editor before
+    /// * `false` - If the user already was an editor
+    fn add_editor(&mut self, user: Self::UserPrimaryKey) -> bool;
+
+    /// Revokes the editor role from a user.
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the user was an editor
+    /// * `false` - If the user was not an editor
+    fn remove_editor(&mut self, user: &Self::UserPrimaryKey) -> bool;
+}
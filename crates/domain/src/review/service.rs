@@ -0,0 +1,158 @@
+use crate::{
+    paper::{entity::model::Paper, PaperId, PaperStatus},
+    review::{
+        entity::model::{Review, ReviewError},
+        repository::ReviewRepository,
+        EditorialDecision, ReviewMode, ReviewReport,
+    },
+};
+use thiserror::Error;
+
+/// Errors that can occur during review service operations
+#[derive(Error, Debug)]
+pub enum ReviewServiceError {
+    #[error("Review not found")]
+    NotFound,
+    #[error("Paper is not under review")]
+    NotUnderReview,
+    #[error("Only the lead author or an editor can assign reviewers")]
+    NotAllowedToAssign,
+    #[error("Only the lead author or an editor can change the review mode")]
+    NotAllowedToConfigure,
+    #[error("Only editors can decide on a review")]
+    NotEditor,
+    #[error("Editors cannot decide on their own paper")]
+    EditorIsAuthor,
+    #[error("{0}")]
+    Review(#[from] ReviewError),
+}
+
+/// Service for running the peer review of papers
+pub struct ReviewService<R>
+where
+    R: ReviewRepository,
+{
+    repository: R,
+}
+
+impl<R> ReviewService<R>
+where
+    R: ReviewRepository,
+{
+    /// Creates a new ReviewService with the provided repository
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    pub fn get(&self, paper_id: &PaperId) -> Result<Review<R::UserPrimaryKey>, ReviewServiceError> {
+        self.repository.get(paper_id).ok_or(ReviewServiceError::NotFound)
+    }
+
+    /// Sets how identities are disclosed during the review of a paper
+    ///
+    /// The mode can only change before the first review round.
+    pub fn set_mode(
+        &mut self,
+        paper: &Paper<R::UserPrimaryKey>,
+        mode: ReviewMode,
+        changed_by: R::UserPrimaryKey,
+    ) -> Result<(), ReviewServiceError> {
+        if paper.lead_author != changed_by && !self.repository.is_editor(&changed_by) {
+            return Err(ReviewServiceError::NotAllowedToConfigure);
+        }
+
+        let mut review = self.repository.get(&paper.id).unwrap_or_else(|| Review::new(paper.id));
+        review.set_mode(mode)?;
+        self.repository.insert(paper.id, review);
+        Ok(())
+    }
+
+    /// Returns true if the identities of the paper's authors and reviewers must be masked
+    pub fn hides_identities(&self, paper_id: &PaperId) -> bool {
+        self.repository.get(paper_id).is_some_and(|review| review.hides_identities())
+    }
+
+    /// Assigns a reviewer to a paper under review
+    ///
+    /// # Arguments
+    ///
+    /// * `paper` - The paper to be reviewed
+    /// * `reviewer` - The user assigned as reviewer
+    /// * `assigned_by` - The lead author or editor making the assignment
+    pub fn assign_reviewer(
+        &mut self,
+        paper: &Paper<R::UserPrimaryKey>,
+        reviewer: R::UserPrimaryKey,
+        assigned_by: R::UserPrimaryKey,
+    ) -> Result<(), ReviewServiceError> {
+        Self::check_under_review(paper)?;
+        if paper.lead_author != assigned_by && !self.repository.is_editor(&assigned_by) {
+            return Err(ReviewServiceError::NotAllowedToAssign);
+        }
+
+        let mut review = self.repository.get(&paper.id).unwrap_or_else(|| Review::new(paper.id));
+        review.assign(paper, reviewer, assigned_by)?;
+        self.repository.insert(paper.id, review);
+        Ok(())
+    }
+
+    /// Records a reviewer's report on a paper under review
+    pub fn submit_report(
+        &mut self,
+        paper: &Paper<R::UserPrimaryKey>,
+        report: ReviewReport<R::UserPrimaryKey>,
+    ) -> Result<(), ReviewServiceError> {
+        Self::check_under_review(paper)?;
+
+        let mut review = self.get(&paper.id)?;
+        review.submit_report(report)?;
+        self.repository.insert(paper.id, review);
+        Ok(())
+    }
+
+    /// Closes the open review round of a paper with an editorial decision
+    ///
+    /// The caller is responsible for applying `decision.transition()` to the paper.
+    pub fn decide(
+        &mut self,
+        paper: &Paper<R::UserPrimaryKey>,
+        decision: EditorialDecision,
+        editor: R::UserPrimaryKey,
+    ) -> Result<(), ReviewServiceError> {
+        Self::check_under_review(paper)?;
+        if !self.repository.is_editor(&editor) {
+            return Err(ReviewServiceError::NotEditor);
+        }
+        if paper.is_author(&editor) {
+            return Err(ReviewServiceError::EditorIsAuthor);
+        }
+
+        let mut review = self.get(&paper.id)?;
+        review.decide(decision, editor)?;
+        self.repository.insert(paper.id, review);
+        Ok(())
+    }
+
+    pub fn assigned_papers(&self, reviewer: &R::UserPrimaryKey) -> Vec<PaperId> {
+        self.repository.iter_assigned(reviewer).collect()
+    }
+
+    pub fn is_editor(&self, user: &R::UserPrimaryKey) -> bool {
+        self.repository.is_editor(user)
+    }
+
+    pub fn add_editor(&mut self, user: R::UserPrimaryKey) -> bool {
+        self.repository.add_editor(user)
+    }
+
+    pub fn remove_editor(&mut self, user: &R::UserPrimaryKey) -> bool {
+        self.repository.remove_editor(user)
+    }
+
+    fn check_under_review(paper: &Paper<R::UserPrimaryKey>) -> Result<(), ReviewServiceError> {
+        if paper.status != PaperStatus::UnderReview {
+            return Err(ReviewServiceError::NotUnderReview);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+pub mod assignment;
+pub mod decision;
+pub mod mode;
+pub mod recommendation;
+pub mod report;
+pub mod score;
+
+pub use assignment::ReviewAssignment;
+pub use decision::EditorialDecision;
+pub use mode::ReviewMode;
+pub use recommendation::ReviewRecommendation;
+pub use report::ReviewReport;
+pub use score::{ReviewScore, ReviewScoreError};
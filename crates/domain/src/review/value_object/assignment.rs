@@ -0,0 +1,14 @@
+use crate::user::UserPrimaryKey;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// A reviewer assigned to a review round
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ReviewAssignment<K: UserPrimaryKey> {
+    /// The assigned reviewer
+    pub reviewer: K,
+    /// The lead author or editor who made the assignment
+    pub assigned_by: K,
+    /// When the reviewer was assigned in nanoseconds since epoch
+    pub assigned_at: u64,
+}
@@ -0,0 +1,32 @@
+use crate::paper::PaperStatusTransition;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Editorial decision closing a review round
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum EditorialDecision {
+    /// The paper is published
+    Accept,
+    /// The paper goes back to its authors for small changes
+    MinorRevision,
+    /// The paper goes back to its authors for substantial changes
+    MajorRevision,
+    /// The paper is not published
+    Reject,
+}
+
+impl EditorialDecision {
+    /// Returns true if the decision ends the review, rather than asking for a revision
+    /// that is reviewed in another round
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Accept | Self::Reject)
+    }
+
+    /// Returns the status transition this decision applies to the paper
+    pub fn transition(&self) -> PaperStatusTransition {
+        match self {
+            Self::Accept => PaperStatusTransition::Accept,
+            Self::MinorRevision | Self::MajorRevision | Self::Reject => PaperStatusTransition::Reject,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// How identities are disclosed while a paper is being reviewed
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReviewMode {
+    /// Authors and reviewers see each other
+    #[default]
+    Open,
+    /// Authors are hidden from reviewers and reviewers from authors until the review closes
+    DoubleBlind,
+}
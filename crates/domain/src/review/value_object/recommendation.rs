@@ -0,0 +1,15 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// What a reviewer recommends the editor decides
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum ReviewRecommendation {
+    /// Publish as is
+    Accept,
+    /// Publish after small changes
+    MinorRevision,
+    /// Substantial changes are needed before publication
+    MajorRevision,
+    /// Do not publish
+    Reject,
+}
@@ -0,0 +1,19 @@
+use super::{ReviewRecommendation, ReviewScore};
+use crate::user::UserPrimaryKey;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// A reviewer's report on a paper
+#[derive(CandidType, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ReviewReport<K: UserPrimaryKey> {
+    /// The reviewer who wrote the report
+    pub reviewer: K,
+    /// Overall score of the paper
+    pub score: ReviewScore,
+    /// What the reviewer recommends the editor decides
+    pub recommendation: ReviewRecommendation,
+    /// Comments for the authors and the editor
+    pub comments: String,
+    /// When the report was submitted in nanoseconds since epoch
+    pub submitted_at: u64,
+}
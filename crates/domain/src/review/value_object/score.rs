@@ -0,0 +1,67 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Error returned when creating an invalid review score
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ReviewScoreError {
+    #[error("review score must be between {min} and {max}, got {value}")]
+    OutOfRange { min: u8, max: u8, value: u8 },
+}
+
+/// Overall score a reviewer gives a paper
+///
+/// Scores range from 1 (poor) to 5 (excellent).
+#[derive(CandidType, Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReviewScore(u8);
+
+impl ReviewScore {
+    /// Lowest possible score
+    pub const MIN: u8 = 1;
+    /// Highest possible score
+    pub const MAX: u8 = 5;
+
+    /// Creates a new ReviewScore, validating that it is within range
+    pub fn new(value: u8) -> Result<Self, ReviewScoreError> {
+        if !(Self::MIN..=Self::MAX).contains(&value) {
+            return Err(ReviewScoreError::OutOfRange {
+                min: Self::MIN,
+                max: Self::MAX,
+                value,
+            });
+        }
+
+        Ok(Self(value))
+    }
+
+    /// Returns the raw score
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for ReviewScore {
+    type Error = ReviewScoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(ReviewScore::new(1).unwrap().get(), 1);
+        assert_eq!(ReviewScore::new(5).unwrap().get(), 5);
+
+        for value in [0, 6, u8::MAX] {
+            assert_eq!(
+                ReviewScore::new(value),
+                Err(ReviewScoreError::OutOfRange { min: 1, max: 5, value })
+            );
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Doubles shared by the tests of services.
+
+use crate::user::UserPrimaryKey;
+
+/// Small integers stand in for users in tests
+impl UserPrimaryKey for u8 {
+    fn generate() -> Self {
+        0
+    }
+
+    #[cfg(feature = "canister")]
+    fn as_principal(&self) -> crate::UserPrincipal {
+        candid::Principal::from_slice(&[*self]).into()
+    }
+}
+
+#[cfg(feature = "entity")]
+pub use paper::*;
+
+#[cfg(feature = "entity")]
+mod paper {
+    use crate::paper::{
+        entity::model::{Paper, PaperMetadata},
+        repository::{PaperRepository, UploadRepository},
+        search::SearchDocument,
+        upload::{UploadChunkKey, UploadSession},
+        AuthorCitationMetrics, BlobHash, ListingCursor, PaperCategory, PaperContents, PaperId, PaperSort, PaperStatus, PaperSummary, PaperTitle, SortKey,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        collections::{BTreeMap, BTreeSet},
+        ops::{Bound, RangeInclusive},
+        rc::Rc,
+    };
+
+    /// Returns a paper with nothing but an identifier, a lead author and a status
+    pub fn paper(id: PaperId, lead_author: u8, status: PaperStatus) -> Paper<u8> {
+        Paper {
+            id,
+            lead_author,
+            co_authors: Vec::new(),
+            invited_co_authors: Vec::new(),
+            co_author_roles: BTreeMap::new(),
+            title: PaperTitle::new(format!("Paper {}", id)).unwrap(),
+            ab: String::new(),
+            content: PaperContents::default(),
+            categories: Vec::new(),
+            tags: Vec::new(),
+            status,
+            created_at: id.number() as u64,
+            updated_at: id.number() as u64,
+            cover_image: None,
+            references: Vec::new(),
+            citations: Vec::new(),
+        }
+    }
+
+    /// Returns the identifier of the first revision of paper `number`
+    pub fn id(number: u32) -> PaperId {
+        PaperId::new(0, number, 1).unwrap()
+    }
+
+    /// Papers kept in a map shared by every clone, answering every query by scanning it
+    #[derive(Clone, Default)]
+    pub struct MemoryPaperRepository {
+        papers: Rc<RefCell<BTreeMap<PaperId, Paper<u8>>>>,
+    }
+
+    impl MemoryPaperRepository {
+        pub fn with(papers: impl IntoIterator<Item = Paper<u8>>) -> Self {
+            let repository = Self::default();
+            repository.papers.borrow_mut().extend(papers.into_iter().map(|paper| (paper.id, paper)));
+            repository
+        }
+
+        fn filtered(&self, filter: impl Fn(&Paper<u8>) -> bool) -> std::vec::IntoIter<PaperId> {
+            self.papers.borrow().values()
+                .filter(|paper| filter(paper))
+                .map(|paper| paper.id)
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn sort_key(paper: &Paper<u8>, sort: PaperSort) -> SortKey {
+            match sort {
+                PaperSort::Newest => SortKey::Number(paper.created_at),
+                PaperSort::RecentlyUpdated => SortKey::Number(paper.updated_at),
+                PaperSort::MostCited => SortKey::Number(paper.citing_papers().len() as u64),
+                PaperSort::Title => SortKey::Text(paper.title.as_str().to_lowercase()),
+            }
+        }
+
+        fn document(paper: &Paper<u8>) -> SearchDocument {
+            SearchDocument::new(paper.title.as_str(), &paper.ab, paper.content.text.as_deref().unwrap_or_default(), &paper.tags)
+        }
+    }
+
+    impl PaperRepository for MemoryPaperRepository {
+        type UserPrimaryKey = u8;
+
+        fn get(&self, paper_id: &PaperId) -> Option<Paper<u8>> {
+            self.papers.borrow().get(paper_id).cloned()
+        }
+
+        fn get_metadata(&self, paper_id: &PaperId) -> Option<PaperMetadata<u8>> {
+            self.get(paper_id).map(|paper| paper.into_parts().0)
+        }
+
+        fn get_text(&self, paper_id: &PaperId) -> Option<String> {
+            self.get(paper_id).and_then(|paper| paper.content.text)
+        }
+
+        fn read_blob(&self, _hash: &BlobHash, _offset: u64, _len: u64) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn get_summary(&self, paper_id: &PaperId) -> Option<PaperSummary<u8>> {
+            self.papers.borrow().get(paper_id).map(|paper| PaperSummary {
+                id: paper.id,
+                lead_author: paper.lead_author,
+                status: paper.status,
+            })
+        }
+
+        fn get_title(&self, paper_id: &PaperId) -> Option<PaperTitle> {
+            self.papers.borrow().get(paper_id).map(|paper| paper.title.clone())
+        }
+
+        fn contains(&self, paper_id: &PaperId) -> bool {
+            self.papers.borrow().contains_key(paper_id)
+        }
+
+        fn latest_version(&self, paper_id: &PaperId) -> Option<PaperId> {
+            self.iter_versions(paper_id).last()
+        }
+
+        fn iter_versions(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| paper.id.is_same_paper(paper_id))
+        }
+
+        fn iter_ids(&self, after: Option<&PaperId>, limit: usize) -> impl Iterator<Item = PaperId> {
+            let start = after.map_or(Bound::Unbounded, |after| Bound::Excluded(*after));
+            self.papers.borrow()
+                .range((start, Bound::Unbounded))
+                .take(limit)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn iter_invitations(&self, user: &u8) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| paper.is_invited(user))
+        }
+
+        fn iter_authors(&self, paper_id: &PaperId) -> impl Iterator<Item = u8> {
+            self.get(paper_id)
+                .map(|paper| std::iter::once(paper.lead_author).chain(paper.co_authors).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+        }
+
+        fn iter_referenced(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+            self.get(paper_id).map(|paper| paper.referenced_papers()).unwrap_or_default().into_iter()
+        }
+
+        fn iter_citing(&self, paper_id: &PaperId) -> impl Iterator<Item = PaperId> {
+            self.get(paper_id).map(|paper| paper.citing_papers()).unwrap_or_default().into_iter()
+        }
+
+        fn get_author_metrics(&self, user: &u8) -> AuthorCitationMetrics {
+            let papers: BTreeSet<_> = self.papers.borrow().values()
+                .filter(|paper| paper.is_author(user))
+                .map(|paper| paper.id.first_version())
+                .collect();
+            AuthorCitationMetrics::from_counts(papers.iter().map(|paper| {
+                let citing: BTreeSet<_> = self.iter_versions(paper)
+                    .flat_map(|version| self.iter_citing(&version).collect::<Vec<_>>())
+                    .filter(|citing| !citing.is_same_paper(paper))
+                    .map(|citing| citing.first_version())
+                    .collect();
+                citing.len() as u32
+            }))
+        }
+
+        fn iter_postings(&self, term: &str, limit: usize) -> impl Iterator<Item = (PaperId, u32)> {
+            self.papers.borrow().values()
+                .filter_map(|paper| Some((paper.id, *Self::document(paper).terms.get(term)?)))
+                .take(limit)
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn get_indexed_length(&self, paper_id: &PaperId) -> Option<u32> {
+            self.papers.borrow().get(paper_id).map(|paper| Self::document(paper).length)
+        }
+
+        fn index_statistics(&self) -> (u64, u64) {
+            let papers = self.papers.borrow();
+            (papers.len() as u64, papers.values().map(|paper| Self::document(paper).length as u64).sum())
+        }
+
+        fn iter_sorted(&self, sort: PaperSort, status: PaperStatus, after: Option<&ListingCursor>, limit: usize) -> impl Iterator<Item = ListingCursor> {
+            let mut positions: Vec<_> = self.papers.borrow().values()
+                .filter(|paper| paper.status == status && self.latest_version(&paper.id) == Some(paper.id))
+                .map(|paper| ListingCursor { sort, key: Self::sort_key(paper, sort), paper_id: paper.id })
+                .filter(|position| after.is_none_or(|after| position.cmp_listed(after).is_gt()))
+                .collect();
+            positions.sort_by(ListingCursor::cmp_listed);
+            positions.truncate(limit);
+            positions.into_iter()
+        }
+
+        fn get_listing_cursor(&self, sort: PaperSort, paper_id: &PaperId) -> Option<ListingCursor> {
+            let key = Self::sort_key(self.papers.borrow().get(paper_id)?, sort);
+            Some(ListingCursor { sort, key, paper_id: *paper_id })
+        }
+
+        fn iter_by_author(&self, user: &u8, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| paper.is_author(user) && months.contains(&paper.id.months())).take(limit)
+        }
+
+        fn iter_by_category(&self, category: &PaperCategory, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| paper.categories.contains(category) && months.contains(&paper.id.months())).take(limit)
+        }
+
+        fn iter_by_tag(&self, tag: &str, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| paper.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) && months.contains(&paper.id.months())).take(limit)
+        }
+
+        fn iter_by_month(&self, months: RangeInclusive<u16>, limit: usize) -> impl Iterator<Item = PaperId> {
+            self.filtered(|paper| months.contains(&paper.id.months())).take(limit)
+        }
+
+        fn is_by_author(&self, user: &u8, paper_id: &PaperId) -> bool {
+            self.papers.borrow().get(paper_id).is_some_and(|paper| paper.is_author(user))
+        }
+
+        fn has_category(&self, category: &PaperCategory, paper_id: &PaperId) -> bool {
+            self.papers.borrow().get(paper_id).is_some_and(|paper| paper.categories.contains(category))
+        }
+
+        fn has_tag(&self, tag: &str, paper_id: &PaperId) -> bool {
+            self.papers.borrow().get(paper_id).is_some_and(|paper| paper.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        }
+
+        fn iter_summary(&self) -> impl Iterator<Item = PaperSummary<u8>> {
+            self.papers.borrow().keys()
+                .filter_map(|id| self.get_summary(id))
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn insert(&mut self, paper_id: PaperId, paper: Paper<u8>) -> Option<PaperMetadata<u8>> {
+            self.papers.borrow_mut().insert(paper_id, paper).map(|paper| paper.into_parts().0)
+        }
+
+        fn remove(&mut self, paper_id: &PaperId) -> Option<PaperMetadata<u8>> {
+            self.papers.borrow_mut().remove(paper_id).map(|paper| paper.into_parts().0)
+        }
+
+        fn generate_id(&mut self) -> PaperId {
+            let numbers: BTreeSet<_> = self.papers.borrow().keys().map(|id| id.number()).collect();
+            id(numbers.last().map_or(1, |number| number + 1))
+        }
+    }
+
+    /// Uploads kept in maps shared by every clone
+    #[derive(Clone, Default)]
+    pub struct MemoryUploadRepository {
+        uploads: Rc<RefCell<BTreeMap<u64, UploadSession<u8>>>>,
+        chunks: Rc<RefCell<BTreeMap<UploadChunkKey, Vec<u8>>>>,
+        last_id: Rc<Cell<u64>>,
+    }
+
+    impl MemoryUploadRepository {
+        /// Returns the number of chunks stored across every upload
+        pub fn chunk_count(&self) -> usize {
+            self.chunks.borrow().len()
+        }
+    }
+
+    impl UploadRepository for MemoryUploadRepository {
+        type UserPrimaryKey = u8;
+
+        fn get(&self, upload_id: u64) -> Option<UploadSession<u8>> {
+            self.uploads.borrow().get(&upload_id).cloned()
+        }
+
+        fn iter(&self) -> impl Iterator<Item = (u64, UploadSession<u8>)> {
+            self.uploads.borrow().clone().into_iter()
+        }
+
+        fn open_bytes(&self) -> u64 {
+            self.uploads.borrow().values().map(|session| session.size).sum()
+        }
+
+        fn insert(&mut self, upload_id: u64, session: UploadSession<u8>) {
+            self.uploads.borrow_mut().insert(upload_id, session);
+        }
+
+        fn insert_chunk(&mut self, upload_id: u64, offset: u64, bytes: Vec<u8>) {
+            self.chunks.borrow_mut().insert(UploadChunkKey { upload_id, offset }, bytes);
+        }
+
+        fn iter_chunks(&self, upload_id: u64) -> impl Iterator<Item = Vec<u8>> {
+            self.chunks.borrow()
+                .range(UploadChunkKey::range(upload_id))
+                .map(|(_, bytes)| bytes.clone())
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn remove(&mut self, upload_id: u64) -> Option<UploadSession<u8>> {
+            self.chunks.borrow_mut().retain(|key, _| key.upload_id != upload_id);
+            self.uploads.borrow_mut().remove(&upload_id)
+        }
+
+        fn generate_id(&mut self) -> u64 {
+            self.last_id.set(self.last_id.get() + 1);
+            self.last_id.get()
+        }
+    }
+}
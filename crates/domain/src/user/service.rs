@@ -17,6 +17,8 @@ pub enum UserServiceError {
     PrimaryKeyAlreadyExists,
     #[error("Invalid user ID format: {0}")]
     InvalidId(HexaUrlError),
+    #[error("Paper is not listed on the user's profile")]
+    PaperNotListed,
 }
 
 impl From<UserRepositoryError> for UserServiceError {
@@ -151,10 +153,19 @@ where
             Ok(())
         } else {
             // Paper not found in user's authored papers
-            Err(UserServiceError::NotFound)
+            Err(UserServiceError::PaperNotListed)
         }
     }
 
+    pub fn add_paper_as_co_author(&mut self, primary_key: &R::PrimaryKey, paper_id: &PaperId) -> Result<(), UserServiceError> {
+        let mut user = self.repository.get_by_primary_key(primary_key).ok_or(UserServiceError::NotFound)?;
+        if !user.co_authored_papers.contains(paper_id) {
+            user.co_authored_papers.push(*paper_id);
+            self.repository.update(primary_key, user)?;
+        }
+        Ok(())
+    }
+
     pub fn remove_paper_as_co_author(&mut self, primary_key: &R::PrimaryKey, paper_id: &PaperId) -> Result<(), UserServiceError> {
         let mut user = self.repository.get_by_primary_key(primary_key)
             .ok_or(UserServiceError::NotFound)?;
@@ -167,7 +178,28 @@ where
             Ok(())
         } else {
             // Paper not found in user's co-authored papers
-            Err(UserServiceError::NotFound)
+            Err(UserServiceError::PaperNotListed)
         }
     }
+
+    /// Points every paper list of a user at a new revision of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary_key` - The user whose lists should be updated
+    /// * `old_id` - The revision currently listed
+    /// * `new_id` - The revision to list instead
+    pub fn replace_paper(&mut self, primary_key: &R::PrimaryKey, old_id: &PaperId, new_id: &PaperId) -> Result<(), UserServiceError> {
+        let mut user = self.repository.get_by_primary_key(primary_key)
+            .ok_or(UserServiceError::NotFound)?;
+
+        for id in user.lead_authored_papers.iter_mut().chain(user.co_authored_papers.iter_mut()) {
+            if id == old_id {
+                *id = *new_id;
+            }
+        }
+
+        self.repository.update(primary_key, user)?;
+        Ok(())
+    }
 }
@@ -3,4 +3,5 @@ pub(crate) use serde::{Serialize, Deserialize};
 pub use interface_traits::{Request, Response};
 
 pub mod paper;
+pub mod review;
 pub mod user;
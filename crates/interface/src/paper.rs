@@ -1,6 +1,6 @@
 use crate::{Request, Response, CandidType, Deserialize, Serialize};
 use domain::{
-    paper::{entity::dto::Paper, value_object::{PaperCategory, PaperStatus}},
+    paper::{entity::dto::Paper, search::SnippetSegment, value_object::{BrokenReference, CitationContext, CoAuthorRole, PaperCategory, PaperStatus, Reference}},
     PaperId, UserId,
 };
 
@@ -12,3 +12,168 @@ pub struct PaperSummaryDto {
     pub lead_author_id: String,
     pub lead_author_name: String,
 }
+
+/// Criteria of a paper listing; absent fields match every paper
+#[derive(CandidType, Clone, Serialize, Deserialize, Request, Debug, PartialEq)]
+pub struct PaperListFilter {
+    /// Status of the listed papers, published if absent; only public statuses can be listed
+    pub status: Option<PaperStatus>,
+    pub category: Option<PaperCategory>,
+    /// Tag of the listed papers, whatever its case
+    pub tag: Option<String>,
+    /// UserId of a lead author or co-author of the listed papers
+    pub author_id: Option<String>,
+    /// Earliest creation date in nanoseconds since epoch
+    pub created_from: Option<u64>,
+    /// Creation date in nanoseconds since epoch the papers were created before
+    pub created_until: Option<u64>,
+}
+
+/// A page of a paper listing
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct PaperListDto {
+    /// Papers on the page, which may be fewer than asked for, or none, while a cursor follows
+    pub papers: Vec<PaperSummaryDto>,
+    /// Cursor to pass back for the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// A single revision of a paper, as listed in its revision history
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct PaperRevisionDto {
+    pub id: String,
+    pub version: u16,
+    pub title: String,
+    pub status: PaperStatus,
+    pub updated_at: u64,
+}
+
+/// A paper citing another one, directly or through a chain of citations
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct CitingPaperDto {
+    pub id: String,
+    pub title: String,
+    /// Length of the shortest citation chain, 1 for a direct citation
+    pub hops: u8,
+    /// How a direct citation cites the paper, once per reference to it
+    pub contexts: Vec<CitationContext>,
+}
+
+/// A paper with references that cannot be followed by the caller
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct BrokenReferencesDto {
+    pub paper_id: String,
+    pub title: String,
+    /// Stamp to pass back when fixing the references
+    pub updated_at: u64,
+    pub references: Vec<BrokenReference>,
+}
+
+/// A page of an exported network.
+/// The documents of all pages joined in order make up the whole export.
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct NetworkPageDto {
+    pub document: String,
+    /// Cursor to pass back for the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Narrows down full-text search results; empty fields match every paper
+#[derive(CandidType, Clone, Serialize, Deserialize, Request, Debug, PartialEq)]
+pub struct SearchFilters {
+    /// Papers in any of these categories
+    pub categories: Vec<PaperCategory>,
+    /// Papers with all of these tags, whatever their case
+    pub tags: Vec<String>,
+    /// Papers lead-authored by this user
+    pub lead_author_id: Option<String>,
+}
+
+/// A paper matching a full-text query
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct SearchResultDto {
+    pub id: String,
+    pub title: String,
+    pub lead_author_id: String,
+    pub lead_author_name: String,
+    /// BM25 relevance to the query, only meaningful against the other results
+    pub score: f64,
+    /// Passage of the abstract or content around the words of the query
+    pub snippet: Vec<SnippetSegment>,
+}
+
+/// A page of full-text search results, best first
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct SearchResultsDto {
+    pub results: Vec<SearchResultDto>,
+    pub has_more: bool,
+}
+
+/// An entry of an imported reference list that could not be turned into a citation
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct ReferenceImportErrorDto {
+    /// Position of the entry in the document, starting at 0
+    pub index: u32,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+/// References read from a reference list, with the entries that were skipped
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct ImportedReferencesDto {
+    pub references: Vec<Reference>,
+    pub errors: Vec<ReferenceImportErrorDto>,
+}
+
+/// A pending invitation to co-author a paper
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct CoAuthorInvitationDto {
+    pub paper_id: String,
+    pub title: String,
+    pub lead_author_id: String,
+    pub lead_author_name: String,
+}
+
+/// A co-author of a paper, or a user invited to become one
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct CoAuthorDto {
+    pub id: String,
+    pub name: String,
+    pub role: CoAuthorRole,
+}
+
+/// The co-authors of a paper and its pending invitations
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct CoAuthorsDto {
+    pub co_authors: Vec<CoAuthorDto>,
+    pub invitations: Vec<CoAuthorDto>,
+}
+
+/// Field-level changes to a paper; fields left as `None` keep their current value
+///
+/// Server-owned fields such as authorship, `status`, `created_at` and `citations`
+/// cannot be changed through a patch.
+#[derive(CandidType, Clone, Serialize, Deserialize, Request, Debug, PartialEq)]
+pub struct PaperPatch {
+    pub id: String,
+    /// The `updated_at` of the paper as the client last read it
+    pub updated_at: u64,
+    pub title: Option<String>,
+    pub ab: Option<String>,
+    /// `Some(None)` removes the text content
+    pub text: Option<Option<String>>,
+    pub tags: Option<Vec<String>>,
+    pub categories: Option<Vec<PaperCategory>>,
+    pub references: Option<Vec<Reference>>,
+    /// `Some(None)` removes the cover image
+    pub cover_image: Option<Option<String>>,
+}
+
+/// The outcome of rebuilding the citations of one batch of papers.
+/// Passing the cursor back continues the rebuild until no cursor is returned.
+#[derive(CandidType, Clone, Serialize, Deserialize, Response, Debug, PartialEq)]
+pub struct CitationRebuildDto {
+    /// Number of corrections made in this batch
+    pub corrected: u64,
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,10 @@
+use crate::{Request, CandidType, Deserialize, Serialize};
+use domain::review::value_object::ReviewRecommendation;
+
+/// Report submitted by an assigned reviewer
+#[derive(CandidType, Clone, Serialize, Deserialize, Request, Debug, PartialEq)]
+pub struct SubmitReviewReportRequest {
+    pub score: u8,
+    pub recommendation: ReviewRecommendation,
+    pub comments: String,
+}
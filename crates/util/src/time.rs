@@ -45,3 +45,69 @@ fn now_std() -> u64 {
         .expect("Time went backwards")
         .as_nanos() as u64
 }
+
+/// Converts a timestamp in nanoseconds since the Unix epoch into a UTC calendar date.
+///
+/// Returns `(year, month, day)` with months and days starting at 1.
+pub fn date_from_nanos(nanos: u64) -> (u32, u32, u32) {
+    const NANOS_PER_DAY: u64 = 86_400_000_000_000;
+
+    // Days since 0000-03-01, so that leap days fall at the end of each 400-year era
+    let days = nanos / NANOS_PER_DAY + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year as u32, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NANOS_PER_DAY: u64 = 86_400_000_000_000;
+
+    /// Nanoseconds at the start of a day counted from the Unix epoch
+    fn day(days: u64) -> u64 {
+        days * NANOS_PER_DAY
+    }
+
+    #[test]
+    fn test_date_from_nanos_epoch() {
+        assert_eq!(date_from_nanos(0), (1970, 1, 1));
+        assert_eq!(date_from_nanos(NANOS_PER_DAY - 1), (1970, 1, 1));
+        assert_eq!(date_from_nanos(day(1)), (1970, 1, 2));
+    }
+
+    #[test]
+    fn test_date_from_nanos_leap_days() {
+        // 2000 is a leap year as a multiple of 400, 2100 is not as a multiple of 100
+        assert_eq!(date_from_nanos(day(11_016)), (2000, 2, 29));
+        assert_eq!(date_from_nanos(day(11_017)), (2000, 3, 1));
+        assert_eq!(date_from_nanos(day(19_782)), (2024, 2, 29));
+        assert_eq!(date_from_nanos(day(47_540)), (2100, 2, 28));
+        assert_eq!(date_from_nanos(day(47_541)), (2100, 3, 1));
+        assert_eq!(date_from_nanos(day(19_416)), (2023, 2, 28));
+        assert_eq!(date_from_nanos(day(19_417)), (2023, 3, 1));
+    }
+
+    #[test]
+    fn test_date_from_nanos_month_boundaries() {
+        assert_eq!(date_from_nanos(day(20_088)), (2024, 12, 31));
+        assert_eq!(date_from_nanos(day(20_089)), (2025, 1, 1));
+        assert_eq!(date_from_nanos(day(20_118)), (2025, 1, 30));
+        assert_eq!(date_from_nanos(day(20_119)), (2025, 1, 31));
+        assert_eq!(date_from_nanos(day(20_120)), (2025, 2, 1));
+        assert_eq!(date_from_nanos(day(20_208)), (2025, 4, 30));
+        assert_eq!(date_from_nanos(day(20_209)), (2025, 5, 1));
+        // 2025-01-15T00:00:00Z, the last nanosecond of the day before and of the day itself
+        assert_eq!(date_from_nanos(1_736_899_200_000_000_000 - 1), (2025, 1, 14));
+        assert_eq!(date_from_nanos(1_736_899_200_000_000_000 + NANOS_PER_DAY - 1), (2025, 1, 15));
+    }
+}
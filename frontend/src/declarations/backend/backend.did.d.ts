@@ -2,11 +2,99 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
-export type Citation = { 'Url' : string } |
+export interface AuthorCitationMetrics {
+  'h_index' : number,
+  'citations' : number,
+  'papers' : number,
+  'i10_index' : number,
+}
+export interface BlobFile {
+  'hash' : Uint8Array | number[],
+  'name' : string,
+  'size' : bigint,
+}
+export interface BrokenReference {
+  'paper_id' : PaperId,
+  'index' : number,
+  'reason' : BrokenReferenceReason,
+}
+export type BrokenReferenceReason = { 'RevisionDeleted' : null } |
+  { 'Deleted' : null } |
+  { 'Unavailable' : null };
+export interface BrokenReferencesDto {
+  'paper_id' : string,
+  'title' : string,
+  'updated_at' : bigint,
+  'references' : Array<BrokenReference>,
+}
+export type Citation = { 'Doi' : { 'doi' : string, 'work' : WorkMetadata } } |
+  { 'Rfc' : { 'work' : WorkMetadata, 'number' : number } } |
+  { 'Url' : string } |
+  { 'GitHub' : { 'work' : WorkMetadata, 'repository' : GitHubRepository } } |
+  { 'Isbn' : { 'isbn' : string, 'work' : WorkMetadata } } |
   { 'Paper' : PaperId } |
+  { 'Arxiv' : { 'id' : string, 'work' : WorkMetadata } } |
   { 'Other' : string };
+export interface CitationContext {
+  'locator' : [] | [string],
+  'quote' : [] | [string],
+  'intent' : [] | [CitationIntent],
+}
+export type CitationFormat = { 'Ris' : null } |
+  { 'BibTeX' : null } |
+  { 'CslJson' : null };
+export type CitationIntent = { 'Disputes' : null } |
+  { 'UsesMethod' : null } |
+  { 'Background' : null } |
+  { 'Extends' : null };
+export interface CitationRebuildDto {
+  'next_cursor' : [] | [string],
+  'corrected' : bigint,
+}
+export type CitationStyle = { 'Apa' : null } |
+  { 'Chicago' : null } |
+  { 'Ieee' : null };
+export interface CitingPaperDto {
+  'id' : string,
+  'title' : string,
+  'contexts' : Array<CitationContext>,
+  'hops' : number,
+}
+export interface CoAuthorDto {
+  'id' : string,
+  'name' : string,
+  'role' : CoAuthorRole,
+}
+export interface CoAuthorInvitationDto {
+  'paper_id' : string,
+  'title' : string,
+  'lead_author_name' : string,
+  'lead_author_id' : string,
+}
+export type CoAuthorRole = { 'ReadOnly' : null } |
+  { 'Editor' : null };
+export interface CoAuthorsDto {
+  'co_authors' : Array<CoAuthorDto>,
+  'invitations' : Array<CoAuthorDto>,
+}
 export type ContentFileSource = { 'Raw' : RawFile } |
+  { 'Blob' : BlobFile } |
   { 'Http' : string };
+export type EditorialDecision = { 'Reject' : null } |
+  { 'MajorRevision' : null } |
+  { 'Accept' : null } |
+  { 'MinorRevision' : null };
+export interface GitHubRepository { 'owner' : string, 'name' : string }
+export type GraphFormat = { 'Dot' : null } |
+  { 'GraphML' : null };
+export interface ImportedReferencesDto {
+  'references' : Array<Reference>,
+  'errors' : Array<ReferenceImportErrorDto>,
+}
+export interface NetworkPageDto {
+  'document' : string,
+  'next_cursor' : [] | [string],
+}
 export interface Paper {
   'ab' : string,
   'id' : string,
@@ -14,7 +102,7 @@ export interface Paper {
   'status' : PaperStatus,
   'title' : string,
   'updated_at' : bigint,
-  'references' : Array<Citation>,
+  'references' : Array<Reference>,
   'content' : PaperContents,
   'cover_image' : [] | [string],
   'tags' : Array<string>,
@@ -23,6 +111,11 @@ export interface Paper {
   'co_authors' : Array<[string, string]>,
   'citations' : Array<Citation>,
 }
+export type PaperAction = { 'Read' : null } |
+  { 'Publish' : null } |
+  { 'Delete' : null } |
+  { 'Update' : null } |
+  { 'ManageAuthors' : null };
 export type PaperCategory = { 'MachineLearning' : null } |
   { 'SystemDesign' : null } |
   { 'Security' : null } |
@@ -40,10 +133,69 @@ export interface PaperId {
   'months' : number,
 }
 export interface PaperIdTitle { 'id' : string, 'title' : string }
+export interface PaperListDto {
+  'next_cursor' : [] | [string],
+  'papers' : Array<PaperSummaryDto>,
+}
+export interface PaperListFilter {
+  'tag' : [] | [string],
+  'status' : [] | [PaperStatus],
+  'category' : [] | [PaperCategory],
+  'author_id' : [] | [string],
+  'created_from' : [] | [bigint],
+  'created_until' : [] | [bigint],
+}
+export interface PaperPatch {
+  'ab' : [] | [string],
+  'id' : string,
+  'categories' : [] | [Array<PaperCategory>],
+  'title' : [] | [string],
+  'updated_at' : bigint,
+  'references' : [] | [Array<Reference>],
+  'cover_image' : [] | [[] | [string]],
+  'tags' : [] | [Array<string>],
+  'text' : [] | [[] | [string]],
+}
+export type PaperPermissionError = { 'NoRole' : PaperAction } |
+  { 'Hidden' : PaperStatus } |
+  { 'Forbidden' : { 'action' : PaperAction, 'role' : PaperRole } };
+export interface PaperRevisionDto {
+  'id' : string,
+  'status' : PaperStatus,
+  'title' : string,
+  'updated_at' : bigint,
+  'version' : number,
+}
+export type PaperRole = { 'CoAuthorEditor' : null } |
+  { 'Editor' : null } |
+  { 'Reviewer' : null } |
+  { 'Admin' : null } |
+  { 'CoAuthorReadOnly' : null } |
+  { 'LeadAuthor' : null };
+export type PaperServiceError = { 'InvalidId' : string } |
+  { 'NotFound' : string } |
+  { 'Rejected' : string } |
+  { 'Revision' : string } |
+  { 'Upload' : UploadError } |
+  { 'UserNotFound' : string } |
+  { 'Permission' : PaperPermissionError } |
+  { 'Conflict' : { 'paper_id' : string, 'updated_at' : bigint } };
+export type PaperSort = { 'MostCited' : null } |
+  { 'RecentlyUpdated' : null } |
+  { 'Title' : null } |
+  { 'Newest' : null };
 export type PaperStatus = { 'UnderReview' : null } |
   { 'Draft' : null } |
   { 'Archived' : null } |
   { 'Published' : null };
+export type PaperStatusTransition = { 'Withdraw' : null } |
+  { 'Reject' : null } |
+  { 'Restore' : null } |
+  { 'Accept' : null } |
+  { 'Archive' : null } |
+  { 'Publish' : null } |
+  { 'Retract' : null } |
+  { 'Submit' : null };
 export interface PaperSummaryDto {
   'id' : string,
   'title' : string,
@@ -51,33 +203,203 @@ export interface PaperSummaryDto {
   'lead_author_id' : string,
 }
 export interface RawFile { 'content' : Uint8Array | number[], 'name' : string }
+export interface Reference {
+  'context' : CitationContext,
+  'citation' : Citation,
+}
+export interface ReferenceImportErrorDto {
+  'key' : [] | [string],
+  'message' : string,
+  'index' : number,
+}
 export type Result = { 'Ok' : null } |
+  { 'Err' : PaperServiceError };
+export type Result_1 = { 'Ok' : boolean } |
   { 'Err' : string };
-export type Result_1 = { 'Ok' : User } |
+export type Result_10 = { 'Ok' : Array<CitingPaperDto> } |
+  { 'Err' : PaperServiceError };
+export type Result_11 = { 'Ok' : CoAuthorsDto } |
+  { 'Err' : PaperServiceError };
+export type Result_12 = { 'Ok' : Paper } |
+  { 'Err' : PaperServiceError };
+export type Result_13 = { 'Ok' : BrokenReferencesDto } |
+  { 'Err' : PaperServiceError };
+export type Result_14 = { 'Ok' : Array<PaperRevisionDto> } |
+  { 'Err' : PaperServiceError };
+export type Result_15 = { 'Ok' : Review } |
   { 'Err' : string };
-export type Result_2 = { 'Ok' : Paper } |
+export type Result_16 = { 'Ok' : [] | [Array<string>] } |
+  { 'Err' : PaperServiceError };
+export type Result_17 = { 'Ok' : Array<string> } |
+  { 'Err' : PaperServiceError };
+export type Result_18 = { 'Ok' : PaperListDto } |
+  { 'Err' : PaperServiceError };
+export type Result_19 = { 'Ok' : Uint8Array | number[] } |
+  { 'Err' : PaperServiceError };
+export type Result_2 = { 'Ok' : null } |
   { 'Err' : string };
-export type Result_3 = { 'Ok' : boolean } |
+export type Result_20 = { 'Ok' : CitationRebuildDto } |
+  { 'Err' : PaperServiceError };
+export type Result_21 = { 'Ok' : SearchResultsDto } |
+  { 'Err' : PaperServiceError };
+export type Result_22 = { 'Ok' : PaperStatus } |
+  { 'Err' : PaperServiceError };
+export type Result_3 = { 'Ok' : bigint } |
+  { 'Err' : PaperServiceError };
+export type Result_4 = { 'Ok' : PaperStatus } |
   { 'Err' : string };
+export type Result_5 = { 'Ok' : string } |
+  { 'Err' : PaperServiceError };
+export type Result_6 = { 'Ok' : NetworkPageDto } |
+  { 'Err' : PaperServiceError };
+export type Result_7 = { 'Ok' : AuthorCitationMetrics } |
+  { 'Err' : PaperServiceError };
+export type Result_8 = { 'Ok' : User } |
+  { 'Err' : string };
+export type Result_9 = { 'Ok' : number } |
+  { 'Err' : PaperServiceError };
+export interface Review {
+  'paper_id' : string,
+  'mode' : ReviewMode,
+  'rounds' : Array<ReviewRound>,
+}
+export type ReviewMode = { 'DoubleBlind' : null } |
+  { 'Open' : null };
+export interface ReviewReport {
+  'score' : number,
+  'comments' : string,
+  'recommendation' : EditorialDecision,
+  'reviewer' : Reviewer,
+  'submitted_at' : bigint,
+}
+export interface ReviewRound {
+  'decision' : [] | [EditorialDecision],
+  'reviewers' : Array<Reviewer>,
+  'closed_at' : [] | [bigint],
+  'opened_at' : bigint,
+  'reports' : Array<ReviewReport>,
+}
+export interface Reviewer { 'id' : string, 'name' : string }
+export interface SearchFilters {
+  'categories' : Array<PaperCategory>,
+  'tags' : Array<string>,
+  'lead_author_id' : [] | [string],
+}
+export interface SearchResultDto {
+  'id' : string,
+  'title' : string,
+  'lead_author_name' : string,
+  'snippet' : Array<SnippetSegment>,
+  'score' : number,
+  'lead_author_id' : string,
+}
+export interface SearchResultsDto {
+  'results' : Array<SearchResultDto>,
+  'has_more' : boolean,
+}
+export interface SnippetSegment { 'text' : string, 'highlighted' : boolean }
+export interface SubmitReviewReportRequest {
+  'score' : number,
+  'comments' : string,
+  'recommendation' : EditorialDecision,
+}
+export type UploadError = { 'InvalidDigest' : string } |
+  { 'BlankName' : null } |
+  { 'ChunkPastEnd' : { 'size' : bigint } } |
+  { 'NotFound' : bigint } |
+  { 'InvalidSize' : { 'max' : bigint } } |
+  { 'UnexpectedOffset' : { 'expected' : bigint } } |
+  { 'DigestMismatch' : null } |
+  { 'UploadsFull' : { 'max' : bigint } } |
+  { 'InvalidChunkSize' : { 'max' : bigint } } |
+  { 'Expired' : bigint } |
+  { 'Incomplete' : { 'size' : bigint, 'received' : bigint } } |
+  { 'TooManyUploads' : { 'max' : number } };
 export interface User {
   'id' : [] | [string],
   'name' : string,
   'lead_authored_papers' : Array<PaperIdTitle>,
   'co_authored_papers' : Array<PaperIdTitle>,
 }
+export interface WorkMetadata {
+  'title' : [] | [string],
+  'year' : [] | [number],
+  'authors' : Array<string>,
+}
 export interface _SERVICE {
+  'accept_co_author_invitation' : ActorMethod<[string], Result>,
+  'add_editor' : ActorMethod<[string], Result_1>,
+  'assign_reviewer' : ActorMethod<[string, string], Result_2>,
+  'begin_upload' : ActorMethod<[string, bigint, string], Result_3>,
+  'commit_upload' : ActorMethod<[bigint, string, bigint], Result_3>,
   'create_draft' : ActorMethod<[], string>,
+  'decide_review' : ActorMethod<[string, EditorialDecision], Result_4>,
+  'decline_co_author_invitation' : ActorMethod<[string], Result>,
   'delete_paper' : ActorMethod<[string], Result>,
+  'export_citation' : ActorMethod<[string, CitationFormat], Result_5>,
+  'export_citation_network' : ActorMethod<
+    [GraphFormat, [] | [string], number, [] | [string]],
+    Result_6
+  >,
+  'export_co_authorship_network' : ActorMethod<
+    [GraphFormat, [] | [string], number, [] | [string]],
+    Result_6
+  >,
+  'export_references' : ActorMethod<[string, CitationFormat], Result_5>,
   'fetch_all_paper_summaries' : ActorMethod<[], Array<PaperSummaryDto>>,
-  'fetch_caller' : ActorMethod<[], Result_1>,
-  'fetch_paper' : ActorMethod<[string], Result_2>,
-  'fetch_paper_as_author' : ActorMethod<[string], Result_2>,
-  'fetch_user' : ActorMethod<[string], Result_1>,
+  'fetch_assigned_reviews' : ActorMethod<[], Array<Review>>,
+  'fetch_author_citation_metrics' : ActorMethod<[string], Result_7>,
+  'fetch_broken_references' : ActorMethod<[], Array<BrokenReferencesDto>>,
+  'fetch_caller' : ActorMethod<[], Result_8>,
+  'fetch_caller_invitations' : ActorMethod<[], Array<CoAuthorInvitationDto>>,
+  'fetch_citation_count' : ActorMethod<[string], Result_9>,
+  'fetch_citing_papers' : ActorMethod<[string, number], Result_10>,
+  'fetch_co_authors' : ActorMethod<[string], Result_11>,
+  'fetch_paper' : ActorMethod<[string], Result_12>,
+  'fetch_paper_as_author' : ActorMethod<[string], Result_12>,
+  'fetch_paper_broken_references' : ActorMethod<[string], Result_13>,
+  'fetch_paper_revisions' : ActorMethod<[string], Result_14>,
+  'fetch_review' : ActorMethod<[string], Result_15>,
+  'fetch_user' : ActorMethod<[string], Result_8>,
+  'find_citation_cycle' : ActorMethod<[string], Result_16>,
+  'fix_broken_references' : ActorMethod<[string, bigint], Result_3>,
+  'format_citation' : ActorMethod<[string, CitationStyle], Result_5>,
+  'format_references' : ActorMethod<[string, CitationStyle], Result_17>,
+  'import_references' : ActorMethod<
+    [CitationFormat, string],
+    ImportedReferencesDto
+  >,
+  'invite_co_author' : ActorMethod<[string, string, CoAuthorRole], Result>,
   'is_registered' : ActorMethod<[], boolean>,
-  'register_user' : ActorMethod<[], Result>,
-  'update_caller' : ActorMethod<[User], Result>,
-  'update_paper' : ActorMethod<[Paper], Result>,
-  'user_exists_by_id' : ActorMethod<[string], Result_3>,
+  'list_papers' : ActorMethod<
+    [PaperListFilter, PaperSort, number, [] | [string]],
+    Result_18
+  >,
+  'patch_paper' : ActorMethod<[PaperPatch], Result_3>,
+  'publish_paper_revision' : ActorMethod<[Paper], Result_5>,
+  'read_blob' : ActorMethod<[string, bigint, bigint], Result_19>,
+  'rebuild_citation_index' : ActorMethod<[[] | [string]], Result_20>,
+  'register_user' : ActorMethod<[], Result_2>,
+  'remove_co_author' : ActorMethod<[string, string], Result>,
+  'remove_editor' : ActorMethod<[string], Result_1>,
+  'search_papers' : ActorMethod<[string, SearchFilters, number], Result_21>,
+  'set_co_author_role' : ActorMethod<[string, string, CoAuthorRole], Result>,
+  'set_review_mode' : ActorMethod<[string, ReviewMode], Result_2>,
+  'submit_review_report' : ActorMethod<
+    [string, SubmitReviewReportRequest],
+    Result_2
+  >,
+  'transition_paper_status' : ActorMethod<
+    [string, PaperStatusTransition],
+    Result_22
+  >,
+  'update_caller' : ActorMethod<[User], Result_2>,
+  'update_paper' : ActorMethod<[Paper], Result_3>,
+  'upload_chunk' : ActorMethod<
+    [bigint, bigint, Uint8Array | number[]],
+    Result_3
+  >,
+  'user_exists_by_id' : ActorMethod<[string], Result_1>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
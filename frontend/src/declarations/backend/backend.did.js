@@ -1,11 +1,144 @@
 export const idlFactory = ({ IDL }) => {
-  const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const UploadError = IDL.Variant({
+    'InvalidDigest' : IDL.Text,
+    'BlankName' : IDL.Null,
+    'ChunkPastEnd' : IDL.Record({ 'size' : IDL.Nat64 }),
+    'NotFound' : IDL.Nat64,
+    'InvalidSize' : IDL.Record({ 'max' : IDL.Nat64 }),
+    'UnexpectedOffset' : IDL.Record({ 'expected' : IDL.Nat64 }),
+    'DigestMismatch' : IDL.Null,
+    'UploadsFull' : IDL.Record({ 'max' : IDL.Nat64 }),
+    'InvalidChunkSize' : IDL.Record({ 'max' : IDL.Nat64 }),
+    'Expired' : IDL.Nat64,
+    'Incomplete' : IDL.Record({ 'size' : IDL.Nat64, 'received' : IDL.Nat64 }),
+    'TooManyUploads' : IDL.Record({ 'max' : IDL.Nat32 }),
+  });
+  const PaperAction = IDL.Variant({
+    'Read' : IDL.Null,
+    'Publish' : IDL.Null,
+    'Delete' : IDL.Null,
+    'Update' : IDL.Null,
+    'ManageAuthors' : IDL.Null,
+  });
+  const PaperStatus = IDL.Variant({
+    'UnderReview' : IDL.Null,
+    'Draft' : IDL.Null,
+    'Archived' : IDL.Null,
+    'Published' : IDL.Null,
+  });
+  const PaperRole = IDL.Variant({
+    'CoAuthorEditor' : IDL.Null,
+    'Editor' : IDL.Null,
+    'Reviewer' : IDL.Null,
+    'Admin' : IDL.Null,
+    'CoAuthorReadOnly' : IDL.Null,
+    'LeadAuthor' : IDL.Null,
+  });
+  const PaperPermissionError = IDL.Variant({
+    'NoRole' : PaperAction,
+    'Hidden' : PaperStatus,
+    'Forbidden' : IDL.Record({ 'action' : PaperAction, 'role' : PaperRole }),
+  });
+  const PaperServiceError = IDL.Variant({
+    'InvalidId' : IDL.Text,
+    'NotFound' : IDL.Text,
+    'Rejected' : IDL.Text,
+    'Revision' : IDL.Text,
+    'Upload' : UploadError,
+    'UserNotFound' : IDL.Text,
+    'Permission' : PaperPermissionError,
+    'Conflict' : IDL.Record({
+      'paper_id' : IDL.Text,
+      'updated_at' : IDL.Nat64,
+    }),
+  });
+  const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : PaperServiceError });
+  const Result_1 = IDL.Variant({ 'Ok' : IDL.Bool, 'Err' : IDL.Text });
+  const Result_2 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const Result_3 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : PaperServiceError });
+  const EditorialDecision = IDL.Variant({
+    'Reject' : IDL.Null,
+    'MajorRevision' : IDL.Null,
+    'Accept' : IDL.Null,
+    'MinorRevision' : IDL.Null,
+  });
+  const Result_4 = IDL.Variant({ 'Ok' : PaperStatus, 'Err' : IDL.Text });
+  const CitationFormat = IDL.Variant({
+    'Ris' : IDL.Null,
+    'BibTeX' : IDL.Null,
+    'CslJson' : IDL.Null,
+  });
+  const Result_5 = IDL.Variant({ 'Ok' : IDL.Text, 'Err' : PaperServiceError });
+  const GraphFormat = IDL.Variant({ 'Dot' : IDL.Null, 'GraphML' : IDL.Null });
+  const NetworkPageDto = IDL.Record({
+    'document' : IDL.Text,
+    'next_cursor' : IDL.Opt(IDL.Text),
+  });
+  const Result_6 = IDL.Variant({
+    'Ok' : NetworkPageDto,
+    'Err' : PaperServiceError,
+  });
   const PaperSummaryDto = IDL.Record({
     'id' : IDL.Text,
     'title' : IDL.Text,
     'lead_author_name' : IDL.Text,
     'lead_author_id' : IDL.Text,
   });
+  const ReviewMode = IDL.Variant({
+    'DoubleBlind' : IDL.Null,
+    'Open' : IDL.Null,
+  });
+  const Reviewer = IDL.Record({ 'id' : IDL.Text, 'name' : IDL.Text });
+  const ReviewReport = IDL.Record({
+    'score' : IDL.Nat8,
+    'comments' : IDL.Text,
+    'recommendation' : EditorialDecision,
+    'reviewer' : Reviewer,
+    'submitted_at' : IDL.Nat64,
+  });
+  const ReviewRound = IDL.Record({
+    'decision' : IDL.Opt(EditorialDecision),
+    'reviewers' : IDL.Vec(Reviewer),
+    'closed_at' : IDL.Opt(IDL.Nat64),
+    'opened_at' : IDL.Nat64,
+    'reports' : IDL.Vec(ReviewReport),
+  });
+  const Review = IDL.Record({
+    'paper_id' : IDL.Text,
+    'mode' : ReviewMode,
+    'rounds' : IDL.Vec(ReviewRound),
+  });
+  const AuthorCitationMetrics = IDL.Record({
+    'h_index' : IDL.Nat32,
+    'citations' : IDL.Nat32,
+    'papers' : IDL.Nat32,
+    'i10_index' : IDL.Nat32,
+  });
+  const Result_7 = IDL.Variant({
+    'Ok' : AuthorCitationMetrics,
+    'Err' : PaperServiceError,
+  });
+  const PaperId = IDL.Record({
+    'version' : IDL.Nat16,
+    'number' : IDL.Nat32,
+    'months' : IDL.Nat16,
+  });
+  const BrokenReferenceReason = IDL.Variant({
+    'RevisionDeleted' : IDL.Null,
+    'Deleted' : IDL.Null,
+    'Unavailable' : IDL.Null,
+  });
+  const BrokenReference = IDL.Record({
+    'paper_id' : PaperId,
+    'index' : IDL.Nat32,
+    'reason' : BrokenReferenceReason,
+  });
+  const BrokenReferencesDto = IDL.Record({
+    'paper_id' : IDL.Text,
+    'title' : IDL.Text,
+    'updated_at' : IDL.Nat64,
+    'references' : IDL.Vec(BrokenReference),
+  });
   const PaperIdTitle = IDL.Record({ 'id' : IDL.Text, 'title' : IDL.Text });
   const User = IDL.Record({
     'id' : IDL.Opt(IDL.Text),
@@ -13,7 +146,52 @@ export const idlFactory = ({ IDL }) => {
     'lead_authored_papers' : IDL.Vec(PaperIdTitle),
     'co_authored_papers' : IDL.Vec(PaperIdTitle),
   });
-  const Result_1 = IDL.Variant({ 'Ok' : User, 'Err' : IDL.Text });
+  const Result_8 = IDL.Variant({ 'Ok' : User, 'Err' : IDL.Text });
+  const CoAuthorInvitationDto = IDL.Record({
+    'paper_id' : IDL.Text,
+    'title' : IDL.Text,
+    'lead_author_name' : IDL.Text,
+    'lead_author_id' : IDL.Text,
+  });
+  const Result_9 = IDL.Variant({ 'Ok' : IDL.Nat32, 'Err' : PaperServiceError });
+  const CitationIntent = IDL.Variant({
+    'Disputes' : IDL.Null,
+    'UsesMethod' : IDL.Null,
+    'Background' : IDL.Null,
+    'Extends' : IDL.Null,
+  });
+  const CitationContext = IDL.Record({
+    'locator' : IDL.Opt(IDL.Text),
+    'quote' : IDL.Opt(IDL.Text),
+    'intent' : IDL.Opt(CitationIntent),
+  });
+  const CitingPaperDto = IDL.Record({
+    'id' : IDL.Text,
+    'title' : IDL.Text,
+    'contexts' : IDL.Vec(CitationContext),
+    'hops' : IDL.Nat8,
+  });
+  const Result_10 = IDL.Variant({
+    'Ok' : IDL.Vec(CitingPaperDto),
+    'Err' : PaperServiceError,
+  });
+  const CoAuthorRole = IDL.Variant({
+    'ReadOnly' : IDL.Null,
+    'Editor' : IDL.Null,
+  });
+  const CoAuthorDto = IDL.Record({
+    'id' : IDL.Text,
+    'name' : IDL.Text,
+    'role' : CoAuthorRole,
+  });
+  const CoAuthorsDto = IDL.Record({
+    'co_authors' : IDL.Vec(CoAuthorDto),
+    'invitations' : IDL.Vec(CoAuthorDto),
+  });
+  const Result_11 = IDL.Variant({
+    'Ok' : CoAuthorsDto,
+    'Err' : PaperServiceError,
+  });
   const PaperCategory = IDL.Variant({
     'MachineLearning' : IDL.Null,
     'SystemDesign' : IDL.Null,
@@ -23,27 +201,46 @@ export const idlFactory = ({ IDL }) => {
     'Programming' : IDL.Null,
     'Other' : IDL.Text,
   });
-  const PaperStatus = IDL.Variant({
-    'UnderReview' : IDL.Null,
-    'Draft' : IDL.Null,
-    'Archived' : IDL.Null,
-    'Published' : IDL.Null,
+  const WorkMetadata = IDL.Record({
+    'title' : IDL.Opt(IDL.Text),
+    'year' : IDL.Opt(IDL.Nat16),
+    'authors' : IDL.Vec(IDL.Text),
   });
-  const PaperId = IDL.Record({
-    'version' : IDL.Nat16,
-    'number' : IDL.Nat32,
-    'months' : IDL.Nat16,
+  const GitHubRepository = IDL.Record({
+    'owner' : IDL.Text,
+    'name' : IDL.Text,
   });
   const Citation = IDL.Variant({
+    'Doi' : IDL.Record({ 'doi' : IDL.Text, 'work' : WorkMetadata }),
+    'Rfc' : IDL.Record({ 'work' : WorkMetadata, 'number' : IDL.Nat32 }),
     'Url' : IDL.Text,
+    'GitHub' : IDL.Record({
+      'work' : WorkMetadata,
+      'repository' : GitHubRepository,
+    }),
+    'Isbn' : IDL.Record({ 'isbn' : IDL.Text, 'work' : WorkMetadata }),
     'Paper' : PaperId,
+    'Arxiv' : IDL.Record({ 'id' : IDL.Text, 'work' : WorkMetadata }),
     'Other' : IDL.Text,
   });
+  const Reference = IDL.Record({
+    'context' : CitationContext,
+    'citation' : Citation,
+  });
   const RawFile = IDL.Record({
     'content' : IDL.Vec(IDL.Nat8),
     'name' : IDL.Text,
   });
-  const ContentFileSource = IDL.Variant({ 'Raw' : RawFile, 'Http' : IDL.Text });
+  const BlobFile = IDL.Record({
+    'hash' : IDL.Vec(IDL.Nat8),
+    'name' : IDL.Text,
+    'size' : IDL.Nat64,
+  });
+  const ContentFileSource = IDL.Variant({
+    'Raw' : RawFile,
+    'Blob' : BlobFile,
+    'Http' : IDL.Text,
+  });
   const PaperContents = IDL.Record({
     'pdf' : IDL.Opt(ContentFileSource),
     'text' : IDL.Opt(IDL.Text),
@@ -55,7 +252,7 @@ export const idlFactory = ({ IDL }) => {
     'status' : PaperStatus,
     'title' : IDL.Text,
     'updated_at' : IDL.Nat64,
-    'references' : IDL.Vec(Citation),
+    'references' : IDL.Vec(Reference),
     'content' : PaperContents,
     'cover_image' : IDL.Opt(IDL.Text),
     'tags' : IDL.Vec(IDL.Text),
@@ -64,25 +261,275 @@ export const idlFactory = ({ IDL }) => {
     'co_authors' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
     'citations' : IDL.Vec(Citation),
   });
-  const Result_2 = IDL.Variant({ 'Ok' : Paper, 'Err' : IDL.Text });
-  const Result_3 = IDL.Variant({ 'Ok' : IDL.Bool, 'Err' : IDL.Text });
+  const Result_12 = IDL.Variant({ 'Ok' : Paper, 'Err' : PaperServiceError });
+  const Result_13 = IDL.Variant({
+    'Ok' : BrokenReferencesDto,
+    'Err' : PaperServiceError,
+  });
+  const PaperRevisionDto = IDL.Record({
+    'id' : IDL.Text,
+    'status' : PaperStatus,
+    'title' : IDL.Text,
+    'updated_at' : IDL.Nat64,
+    'version' : IDL.Nat16,
+  });
+  const Result_14 = IDL.Variant({
+    'Ok' : IDL.Vec(PaperRevisionDto),
+    'Err' : PaperServiceError,
+  });
+  const Result_15 = IDL.Variant({ 'Ok' : Review, 'Err' : IDL.Text });
+  const Result_16 = IDL.Variant({
+    'Ok' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'Err' : PaperServiceError,
+  });
+  const CitationStyle = IDL.Variant({
+    'Apa' : IDL.Null,
+    'Chicago' : IDL.Null,
+    'Ieee' : IDL.Null,
+  });
+  const Result_17 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Text),
+    'Err' : PaperServiceError,
+  });
+  const ReferenceImportErrorDto = IDL.Record({
+    'key' : IDL.Opt(IDL.Text),
+    'message' : IDL.Text,
+    'index' : IDL.Nat32,
+  });
+  const ImportedReferencesDto = IDL.Record({
+    'references' : IDL.Vec(Reference),
+    'errors' : IDL.Vec(ReferenceImportErrorDto),
+  });
+  const PaperListFilter = IDL.Record({
+    'tag' : IDL.Opt(IDL.Text),
+    'status' : IDL.Opt(PaperStatus),
+    'category' : IDL.Opt(PaperCategory),
+    'author_id' : IDL.Opt(IDL.Text),
+    'created_from' : IDL.Opt(IDL.Nat64),
+    'created_until' : IDL.Opt(IDL.Nat64),
+  });
+  const PaperSort = IDL.Variant({
+    'MostCited' : IDL.Null,
+    'RecentlyUpdated' : IDL.Null,
+    'Title' : IDL.Null,
+    'Newest' : IDL.Null,
+  });
+  const PaperListDto = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Text),
+    'papers' : IDL.Vec(PaperSummaryDto),
+  });
+  const Result_18 = IDL.Variant({
+    'Ok' : PaperListDto,
+    'Err' : PaperServiceError,
+  });
+  const PaperPatch = IDL.Record({
+    'ab' : IDL.Opt(IDL.Text),
+    'id' : IDL.Text,
+    'categories' : IDL.Opt(IDL.Vec(PaperCategory)),
+    'title' : IDL.Opt(IDL.Text),
+    'updated_at' : IDL.Nat64,
+    'references' : IDL.Opt(IDL.Vec(Reference)),
+    'cover_image' : IDL.Opt(IDL.Opt(IDL.Text)),
+    'tags' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'text' : IDL.Opt(IDL.Opt(IDL.Text)),
+  });
+  const Result_19 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Nat8),
+    'Err' : PaperServiceError,
+  });
+  const CitationRebuildDto = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Text),
+    'corrected' : IDL.Nat64,
+  });
+  const Result_20 = IDL.Variant({
+    'Ok' : CitationRebuildDto,
+    'Err' : PaperServiceError,
+  });
+  const SearchFilters = IDL.Record({
+    'categories' : IDL.Vec(PaperCategory),
+    'tags' : IDL.Vec(IDL.Text),
+    'lead_author_id' : IDL.Opt(IDL.Text),
+  });
+  const SnippetSegment = IDL.Record({
+    'text' : IDL.Text,
+    'highlighted' : IDL.Bool,
+  });
+  const SearchResultDto = IDL.Record({
+    'id' : IDL.Text,
+    'title' : IDL.Text,
+    'lead_author_name' : IDL.Text,
+    'snippet' : IDL.Vec(SnippetSegment),
+    'score' : IDL.Float64,
+    'lead_author_id' : IDL.Text,
+  });
+  const SearchResultsDto = IDL.Record({
+    'results' : IDL.Vec(SearchResultDto),
+    'has_more' : IDL.Bool,
+  });
+  const Result_21 = IDL.Variant({
+    'Ok' : SearchResultsDto,
+    'Err' : PaperServiceError,
+  });
+  const SubmitReviewReportRequest = IDL.Record({
+    'score' : IDL.Nat8,
+    'comments' : IDL.Text,
+    'recommendation' : EditorialDecision,
+  });
+  const PaperStatusTransition = IDL.Variant({
+    'Withdraw' : IDL.Null,
+    'Reject' : IDL.Null,
+    'Restore' : IDL.Null,
+    'Accept' : IDL.Null,
+    'Archive' : IDL.Null,
+    'Publish' : IDL.Null,
+    'Retract' : IDL.Null,
+    'Submit' : IDL.Null,
+  });
+  const Result_22 = IDL.Variant({
+    'Ok' : PaperStatus,
+    'Err' : PaperServiceError,
+  });
   return IDL.Service({
+    'accept_co_author_invitation' : IDL.Func([IDL.Text], [Result], []),
+    'add_editor' : IDL.Func([IDL.Text], [Result_1], []),
+    'assign_reviewer' : IDL.Func([IDL.Text, IDL.Text], [Result_2], []),
+    'begin_upload' : IDL.Func([IDL.Text, IDL.Nat64, IDL.Text], [Result_3], []),
+    'commit_upload' : IDL.Func(
+        [IDL.Nat64, IDL.Text, IDL.Nat64],
+        [Result_3],
+        [],
+      ),
     'create_draft' : IDL.Func([], [IDL.Text], []),
+    'decide_review' : IDL.Func([IDL.Text, EditorialDecision], [Result_4], []),
+    'decline_co_author_invitation' : IDL.Func([IDL.Text], [Result], []),
     'delete_paper' : IDL.Func([IDL.Text], [Result], []),
+    'export_citation' : IDL.Func(
+        [IDL.Text, CitationFormat],
+        [Result_5],
+        ['query'],
+      ),
+    'export_citation_network' : IDL.Func(
+        [GraphFormat, IDL.Opt(IDL.Text), IDL.Nat8, IDL.Opt(IDL.Text)],
+        [Result_6],
+        ['query'],
+      ),
+    'export_co_authorship_network' : IDL.Func(
+        [GraphFormat, IDL.Opt(IDL.Text), IDL.Nat8, IDL.Opt(IDL.Text)],
+        [Result_6],
+        ['query'],
+      ),
+    'export_references' : IDL.Func(
+        [IDL.Text, CitationFormat],
+        [Result_5],
+        ['query'],
+      ),
     'fetch_all_paper_summaries' : IDL.Func(
         [],
         [IDL.Vec(PaperSummaryDto)],
         ['query'],
       ),
-    'fetch_caller' : IDL.Func([], [Result_1], ['query']),
-    'fetch_paper' : IDL.Func([IDL.Text], [Result_2], ['query']),
-    'fetch_paper_as_author' : IDL.Func([IDL.Text], [Result_2], ['query']),
-    'fetch_user' : IDL.Func([IDL.Text], [Result_1], ['query']),
+    'fetch_assigned_reviews' : IDL.Func([], [IDL.Vec(Review)], ['query']),
+    'fetch_author_citation_metrics' : IDL.Func(
+        [IDL.Text],
+        [Result_7],
+        ['query'],
+      ),
+    'fetch_broken_references' : IDL.Func(
+        [],
+        [IDL.Vec(BrokenReferencesDto)],
+        ['query'],
+      ),
+    'fetch_caller' : IDL.Func([], [Result_8], ['query']),
+    'fetch_caller_invitations' : IDL.Func(
+        [],
+        [IDL.Vec(CoAuthorInvitationDto)],
+        ['query'],
+      ),
+    'fetch_citation_count' : IDL.Func([IDL.Text], [Result_9], ['query']),
+    'fetch_citing_papers' : IDL.Func(
+        [IDL.Text, IDL.Nat8],
+        [Result_10],
+        ['query'],
+      ),
+    'fetch_co_authors' : IDL.Func([IDL.Text], [Result_11], ['query']),
+    'fetch_paper' : IDL.Func([IDL.Text], [Result_12], ['query']),
+    'fetch_paper_as_author' : IDL.Func([IDL.Text], [Result_12], ['query']),
+    'fetch_paper_broken_references' : IDL.Func(
+        [IDL.Text],
+        [Result_13],
+        ['query'],
+      ),
+    'fetch_paper_revisions' : IDL.Func([IDL.Text], [Result_14], ['query']),
+    'fetch_review' : IDL.Func([IDL.Text], [Result_15], ['query']),
+    'fetch_user' : IDL.Func([IDL.Text], [Result_8], ['query']),
+    'find_citation_cycle' : IDL.Func([IDL.Text], [Result_16], ['query']),
+    'fix_broken_references' : IDL.Func([IDL.Text, IDL.Nat64], [Result_3], []),
+    'format_citation' : IDL.Func(
+        [IDL.Text, CitationStyle],
+        [Result_5],
+        ['query'],
+      ),
+    'format_references' : IDL.Func(
+        [IDL.Text, CitationStyle],
+        [Result_17],
+        ['query'],
+      ),
+    'import_references' : IDL.Func(
+        [CitationFormat, IDL.Text],
+        [ImportedReferencesDto],
+        ['query'],
+      ),
+    'invite_co_author' : IDL.Func(
+        [IDL.Text, IDL.Text, CoAuthorRole],
+        [Result],
+        [],
+      ),
     'is_registered' : IDL.Func([], [IDL.Bool], ['query']),
-    'register_user' : IDL.Func([], [Result], []),
-    'update_caller' : IDL.Func([User], [Result], []),
-    'update_paper' : IDL.Func([Paper], [Result], []),
-    'user_exists_by_id' : IDL.Func([IDL.Text], [Result_3], ['query']),
+    'list_papers' : IDL.Func(
+        [PaperListFilter, PaperSort, IDL.Nat32, IDL.Opt(IDL.Text)],
+        [Result_18],
+        ['query'],
+      ),
+    'patch_paper' : IDL.Func([PaperPatch], [Result_3], []),
+    'publish_paper_revision' : IDL.Func([Paper], [Result_5], []),
+    'read_blob' : IDL.Func(
+        [IDL.Text, IDL.Nat64, IDL.Nat64],
+        [Result_19],
+        ['query'],
+      ),
+    'rebuild_citation_index' : IDL.Func([IDL.Opt(IDL.Text)], [Result_20], []),
+    'register_user' : IDL.Func([], [Result_2], []),
+    'remove_co_author' : IDL.Func([IDL.Text, IDL.Text], [Result], []),
+    'remove_editor' : IDL.Func([IDL.Text], [Result_1], []),
+    'search_papers' : IDL.Func(
+        [IDL.Text, SearchFilters, IDL.Nat32],
+        [Result_21],
+        ['query'],
+      ),
+    'set_co_author_role' : IDL.Func(
+        [IDL.Text, IDL.Text, CoAuthorRole],
+        [Result],
+        [],
+      ),
+    'set_review_mode' : IDL.Func([IDL.Text, ReviewMode], [Result_2], []),
+    'submit_review_report' : IDL.Func(
+        [IDL.Text, SubmitReviewReportRequest],
+        [Result_2],
+        [],
+      ),
+    'transition_paper_status' : IDL.Func(
+        [IDL.Text, PaperStatusTransition],
+        [Result_22],
+        [],
+      ),
+    'update_caller' : IDL.Func([User], [Result_2], []),
+    'update_paper' : IDL.Func([Paper], [Result_3], []),
+    'upload_chunk' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64, IDL.Vec(IDL.Nat8)],
+        [Result_3],
+        [],
+      ),
+    'user_exists_by_id' : IDL.Func([IDL.Text], [Result_1], ['query']),
   });
 };
 export const init = ({ IDL }) => { return []; };
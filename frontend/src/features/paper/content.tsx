@@ -1,5 +1,6 @@
 import { type JSXElement } from "solid-js";
 import type { PaperContents } from "../../declarations/backend/backend.did";
+import { BlobSource } from "./content/source/blob";
 import { HttpSource } from "./content/source/http";
 import { RawSource } from "./content/source/raw";
 import { PlainText } from "./content/plaintext";
@@ -20,6 +21,8 @@ export function getContent(content: PaperContents): JSXElement {
         return <HttpSource url={source.Http} />;
       } else if ("Raw" in source && source.Raw) {
         return <RawSource file={source.Raw} />;
+      } else if ("Blob" in source && source.Blob) {
+        return <BlobSource file={source.Blob} />;
       }
     }
   }
@@ -0,0 +1,57 @@
+import { type JSXElement, createResource, Show } from "solid-js";
+import type { BlobFile } from "../../../../declarations/backend/backend.did";
+import { useAuth } from "../../../../contexts/auth";
+import { RawSource } from "./raw";
+
+// Most bytes the backend returns from a single read
+const READ_SIZE = 2n * 1024n * 1024n;
+
+interface BlobSourceProps {
+  file: BlobFile;
+}
+
+function toHex(hash: Uint8Array | number[]): string {
+  return Array.from(hash, (byte) => byte.toString(16).padStart(2, "0")).join(
+    "",
+  );
+}
+
+export function BlobSource({ file }: BlobSourceProps): JSXElement {
+  const auth = useAuth();
+
+  // Files may be larger than a reply, so they are read in ranges
+  const [content] = createResource(async () => {
+    const hash = toHex(file.hash);
+    const content = new Uint8Array(Number(file.size));
+    for (let offset = 0n; offset < file.size; offset += READ_SIZE) {
+      const result = await auth.backend.read_blob(hash, offset, READ_SIZE);
+      if ("Err" in result) {
+        const reason = Object.keys(result.Err)[0];
+        return { error: `Failed to read the file: ${reason}` };
+      }
+      content.set(new Uint8Array(result.Ok), Number(offset));
+    }
+    return { content };
+  });
+
+  return (
+    <Show
+      when={content()?.content}
+      fallback={
+        <div class="bg-base-200/50 border-base-300 rounded-md border p-4">
+          <h3 class="font-semibold">{file.name || "PDF Document"}</h3>
+          {content()?.error ? (
+            <p class="text-sm text-red-500">{content()?.error}</p>
+          ) : (
+            <div class="text-base-content/50 flex items-center gap-2">
+              <div class="h-4 w-4 animate-spin rounded-full border-2 border-current border-t-transparent"></div>
+              <span>Loading document...</span>
+            </div>
+          )}
+        </div>
+      }
+    >
+      {(content) => <RawSource file={{ name: file.name, content: content() }} />}
+    </Show>
+  );
+}
@@ -109,15 +109,18 @@ function Form({ paper, auth }: { paper: Paper; auth: AuthStoreType }) {
   const [pdfFileName, setPdfFileName] = createSignal<string>(
     paper.content.pdf[0] && "Raw" in paper.content.pdf[0]
       ? paper.content.pdf[0].Raw.name
-      : "",
+      : paper.content.pdf[0] && "Blob" in paper.content.pdf[0]
+        ? paper.content.pdf[0].Blob.name
+        : "",
   );
   const [pdfUrl, setPdfUrl] = createSignal<string>(
     paper.content.pdf[0] && "Http" in paper.content.pdf[0]
       ? paper.content.pdf[0].Http
       : "",
   );
+  // A stored file is sent back as the reference the paper was fetched with
   const [hasUploadedPdf, setHasUploadedPdf] = createSignal<boolean>(
-    paper.content.pdf[0] && "Raw" in paper.content.pdf[0] ? true : false,
+    paper.content.pdf[0] && !("Http" in paper.content.pdf[0]) ? true : false,
   );
 
   const form = createForm(() => ({
@@ -438,7 +441,7 @@ function Form({ paper, auth }: { paper: Paper; auth: AuthStoreType }) {
                         when={
                           hasUploadedPdf() ||
                           (paper.content.pdf[0] &&
-                            "Raw" in paper.content.pdf[0])
+                            !("Http" in paper.content.pdf[0]))
                         }
                       >
                         <div class="flex items-center gap-2 text-sm">